@@ -12,6 +12,8 @@ fn main() {
         class: "stats".to_owned(),
         display_heading: true,
         pins: vec![],
+        variadic_pins: vec![],
+        resizable: false,
         widgets: vec![TextBox {
             key: "stats".to_owned(),
             capacity: 1000,
@@ -25,6 +27,8 @@ fn main() {
         class: "comment".to_owned(),
         display_heading: true,
         pins: vec![],
+        variadic_pins: vec![],
+        resizable: true,
         widgets: vec![TextBox {
             key: "comment".to_owned(),
             capacity: 1000,
@@ -42,8 +46,23 @@ fn main() {
             class: "input".to_owned(),
             direction: Input,
         }],
-        widgets: vec![Canvas {
+        variadic_pins: vec![],
+        resizable: true,
+        widgets: vec![Plot {
             key: "scope".to_owned(),
+            y_range: (-1.0, 1.0),
+            series: vec![
+                PlotSeries {
+                    label: "Sine".to_owned(),
+                    color: [1.0, 0.8, 0.0, 1.0],
+                },
+                PlotSeries {
+                    label: "Cosine".to_owned(),
+                    color: [0.0, 0.8, 1.0, 1.0],
+                },
+            ],
+            axes: true,
+            grid: Some([8, 4]),
             size: [300.0, 100.0],
         }],
     };
@@ -69,14 +88,18 @@ fn main() {
                 direction: Output,
             },
         ],
+        variadic_pins: vec![],
+        resizable: false,
         widgets: vec![
             Slider {
                 key: "slider".to_owned(),
-                min: 0.0,
-                max: 10.0,
-                default: 5.0,
-                format: "%.1f".to_owned(),
+                min: 20.0,
+                max: 20000.0,
+                default: 440.0,
+                format: "%.0f".to_owned(),
                 width: 150.0,
+                scale: Scale::Logarithmic,
+                unit: Some("Hz".to_owned()),
             },
             Trigger {
                 label: "Trigger".to_owned(),
@@ -119,6 +142,8 @@ fn main() {
             class: "output".to_owned(),
             direction: Output,
         }],
+        variadic_pins: vec![],
+        resizable: false,
         widgets: vec![Slider {
             key: "slider".to_owned(),
             min: -100.0,
@@ -126,6 +151,8 @@ fn main() {
             default: 0.0,
             format: "%.1f".to_owned(),
             width: 150.0,
+            scale: Scale::Linear,
+            unit: None,
         }],
     };
 
@@ -133,28 +160,23 @@ fn main() {
         label: "Mixer".to_owned(),
         class: "mixer".to_owned(),
         display_heading: true,
-        pins: vec![
-            Pin {
-                label: "Input 1".to_owned(),
-                class: "input1".to_owned(),
-                direction: Input,
-            },
-            Pin {
-                label: "Input 2".to_owned(),
-                class: "input2".to_owned(),
-                direction: Input,
-            },
-            Pin {
-                label: "Output 2".to_owned(),
-                class: "output2".to_owned(),
-                direction: Output,
-            },
-        ],
+        pins: vec![Pin {
+            label: "Output".to_owned(),
+            class: "output".to_owned(),
+            direction: Output,
+        }],
+        variadic_pins: vec![Pin {
+            label: "Input".to_owned(),
+            class: "input".to_owned(),
+            direction: Input,
+        }],
+        resizable: false,
         widgets: vec![],
     };
 
     let config = Config {
         node_templates: vec![stats, comment, generator, scope, oscillator, mixer],
+        report_layout: false,
     };
 
     gazpatcho::run_with_callback("Gazpatcho", config, |report| {
@@ -167,7 +189,8 @@ fn main() {
                 report.nodes.len(),
                 report.patches.len()
             );
-            let rendered_sine = draw_sine(300.0, 100.0, report.nodes.len() as f32);
+            let sine = sample_sine(300, report.nodes.len() as f32, 0.0);
+            let cosine = sample_sine(300, report.nodes.len() as f32, PI / 2.0);
 
             requests.extend(report.nodes.iter().filter(|n| n.class == "stats").map(|n| {
                 Request::SetValue {
@@ -180,7 +203,7 @@ fn main() {
                 Request::SetValue {
                     node_id: n.id.to_owned(),
                     key: "scope".to_owned(),
-                    value: Value::VecF32F32(rendered_sine.clone()),
+                    value: Value::VecVecF32(vec![sine.clone(), cosine.clone()]),
                 }
             }));
 
@@ -194,14 +217,8 @@ fn main() {
     });
 }
 
-fn draw_sine(width: f32, height: f32, frequency: f32) -> Vec<(f32, f32)> {
-    let mut dots = Vec::new();
-
-    for x in 0..width as usize {
-        let relative_y = (x as f32 / width * frequency * 2.0 * PI).sin();
-        let y = (relative_y + 1.0) * height / 2.0;
-        dots.push((x as f32, y));
-    }
-
-    dots
+fn sample_sine(length: usize, frequency: f32, phase: f32) -> Vec<f32> {
+    (0..length)
+        .map(|i| (i as f32 / length as f32 * frequency * 2.0 * PI + phase).sin())
+        .collect()
 }
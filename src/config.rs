@@ -28,6 +28,8 @@
 //!                     direction: Output,
 //!                 },
 //!             ],
+//!             variadic_pins: vec![],
+//!             resizable: false,
 //!             widgets: vec![
 //!                 TextBox {
 //!                     key: "comment".to_owned(),
@@ -42,6 +44,56 @@
 //!                     default: 5.0,
 //!                     format: "%.1f".to_owned(),
 //!                     width: 150.0,
+//!                     scale: Scale::Linear,
+//!                     unit: None,
+//!                 },
+//!                 RangeSlider {
+//!                     key: "range".to_owned(),
+//!                     min: 0.0,
+//!                     max: 10.0,
+//!                     default: (2.0, 8.0),
+//!                     format: "%.1f".to_owned(),
+//!                     width: 150.0,
+//!                 },
+//!                 XYPad {
+//!                     key: "pan".to_owned(),
+//!                     x_range: (-1.0, 1.0),
+//!                     y_range: (-1.0, 1.0),
+//!                     default: (0.0, 0.0),
+//!                     size: 100.0,
+//!                 },
+//!                 Envelope {
+//!                     key: "envelope".to_owned(),
+//!                     x_range: (0.0, 1.0),
+//!                     y_range: (0.0, 1.0),
+//!                     default: vec![(0.0, 0.0), (0.2, 1.0), (1.0, 0.0)],
+//!                     curved: true,
+//!                     size: [200.0, 100.0],
+//!                 },
+//!                 Knob {
+//!                     key: "attack".to_owned(),
+//!                     min: 0.0,
+//!                     max: 1.0,
+//!                     default: 0.1,
+//!                     format: "%.2f".to_owned(),
+//!                     size: 40.0,
+//!                 },
+//!                 Knob {
+//!                     key: "release".to_owned(),
+//!                     min: 0.0,
+//!                     max: 1.0,
+//!                     default: 0.5,
+//!                     format: "%.2f".to_owned(),
+//!                     size: 40.0,
+//!                 },
+//!                 NumberInput {
+//!                     key: "steps".to_owned(),
+//!                     default: Number::I32(8),
+//!                     min: Some(Number::I32(1)),
+//!                     max: None,
+//!                     step: 0.1,
+//!                     format: "%d".to_owned(),
+//!                     width: 150.0,
 //!                 },
 //!                 Trigger {
 //!                     label: "Trigger".to_owned(),
@@ -67,21 +119,46 @@
 //!                 Canvas {
 //!                     key: "canvas".to_owned(),
 //!                     size: [300.0, 100.0],
+//!                     interactive: false,
+//!                 },
+//!                 Image {
+//!                     key: "image".to_owned(),
+//!                     size: [200.0, 150.0],
+//!                 },
+//!                 Plot {
+//!                     key: "plot".to_owned(),
+//!                     y_range: (-1.0, 1.0),
+//!                     series: vec![PlotSeries {
+//!                         label: "Output".to_owned(),
+//!                         color: [1.0, 1.0, 0.0, 1.0],
+//!                     }],
+//!                     axes: true,
+//!                     grid: Some([8, 4]),
+//!                     size: [300.0, 100.0],
 //!                 },
 //!             ],
 //!         },
 //!     ],
+//!     report_layout: false,
 //! };
 //! ```
 
 /// The structure holding the whole configuration.
 ///
 /// See the [module documentation](index.html) to see an example of a fully
-/// defined `Config`.
+/// defined `Config`. Fields that are not needed can be left at their defaults
+/// through `..Default::default()`.
+#[derive(Default)]
 pub struct Config {
     /// List of all node templates available in the application. Users can
     /// instantiate these templates to initialize a node.
     pub node_templates: Vec<NodeTemplate>,
+    /// Whether the [`Report`](../report/struct.Report.html) should include
+    /// [layout](../report/struct.Layout.html) of the graph, i.e. positions of
+    /// nodes, their labels and pins, frames grouping nodes, and the offset of
+    /// the canvas. When enabled, a new report is also sent every time the
+    /// layout changes, e.g. when a node is moved or the canvas is scrolled.
+    pub report_layout: bool,
 }
 
 /// The structure specifying format of a node.
@@ -91,7 +168,9 @@ pub struct Config {
 /// per-node values.
 ///
 /// See the [module documentation](index.html) to see an example of a fully
-/// defined `NodeTemplate` inside a config.
+/// defined `NodeTemplate` inside a config. Fields that are not needed can be
+/// left at their defaults through `..Default::default()`.
+#[derive(Clone, Default, Debug)]
 pub struct NodeTemplate {
     /// Label showing on top of each node.
     pub label: String,
@@ -102,6 +181,16 @@ pub struct NodeTemplate {
     pub display_heading: bool,
     /// Input and output `Pins` serve as contact points for inter-node patches.
     pub pins: Vec<Pin>,
+    /// Variadic `Pins` are multiplied as they get connected. Each node starts
+    /// with a single instance of every variadic pin, e.g. a pin with label
+    /// `Input` and class `input` is instantiated as `Input 1` of class
+    /// `input1`. Once the last instance gets connected, a new one is
+    /// appended. Unconnected trailing instances are removed again.
+    pub variadic_pins: Vec<Pin>,
+    /// Whether users can resize nodes of this template through a handle in
+    /// their bottom right corner. `TextBox` and `Canvas` widgets are stretched
+    /// to fill the additional height.
+    pub resizable: bool,
     /// Widgets can be manipulated by users to select or record values.
     pub widgets: Vec<Widget>,
 }
@@ -118,6 +207,7 @@ pub struct NodeTemplate {
 ///     direction: Output,
 /// };
 /// ```
+#[derive(Clone, PartialEq, Debug)]
 pub struct Pin {
     /// Label will be the title shown next to the pin in the UI.
     pub label: String,
@@ -128,6 +218,7 @@ pub struct Pin {
 }
 
 /// The direction type specifying the orientation of node [`Pins`](struct.Pin.html).
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Direction {
     Input,
     Output,
@@ -139,6 +230,7 @@ pub use Direction::*;
 ///
 /// Each widget must have a unique `key` within the node it's registered to.
 /// This `key` is then used to read values recorded by the user.
+#[derive(Clone, Debug)]
 pub enum Widget {
     /// Multiline input provides text box for the user to type into and record a
     /// `String`.
@@ -167,7 +259,8 @@ pub enum Widget {
         read_only: bool,
     },
     /// Slider is a drag and drop dialog allowing users to dial-in a `f32` value
-    /// within given borders.
+    /// within given borders. Double-click resets the slider to its default
+    /// value, Ctrl-click allows users to type in an exact value.
     Slider {
         key: String,
         /// Minimum allowed value.
@@ -181,6 +274,105 @@ pub enum Widget {
         /// Minimal width of the widget. The width may be increased in case the
         /// node contains another widget that is wider.
         width: f32,
+        /// Mapping of the slider position to the value.
+        scale: Scale,
+        /// Optional unit shown after the value, e.g. `Hz`.
+        unit: Option<String>,
+    },
+    /// Range slider is a drag and drop dialog with two handles allowing users
+    /// to dial-in a pair of `f32` values, the lower and the upper end of a
+    /// range within given borders.
+    RangeSlider {
+        key: String,
+        /// Minimum allowed value.
+        min: f32,
+        /// Maximum allowed value.
+        max: f32,
+        /// Initially set lower and upper end of the range.
+        default: (f32, f32),
+        /// Format of the shown values as C-format string. e.g. `%.3f`.
+        format: String,
+        /// Minimal width of the widget. The width may be increased in case the
+        /// node contains another widget that is wider.
+        width: f32,
+    },
+    /// XY pad is a square area where users drag a point to dial-in a pair of
+    /// `f32` values, `x` and `y`, each within its own borders.
+    XYPad {
+        key: String,
+        /// Minimum and maximum allowed value of `x`, shown from left to right.
+        x_range: (f32, f32),
+        /// Minimum and maximum allowed value of `y`, shown from bottom to top.
+        y_range: (f32, f32),
+        /// Initially set `x` and `y`.
+        default: (f32, f32),
+        /// Width and height of the pad.
+        size: f32,
+    },
+    /// Envelope is an editor of a curve given by breakpoints. Users drag the
+    /// breakpoints around and double-click to add or remove them. The
+    /// breakpoints are recorded as `Vec<(f32, f32)>` of `x` and `y` ordered by
+    /// `x`.
+    ///
+    /// If `curved` is enabled, each segment between two breakpoints has its
+    /// own curvature between -1.0 and 1.0, set by dragging a handle in the
+    /// middle of the segment. The breakpoints are then recorded as
+    /// `Vec<(f32, f32, f32)>`, the third item being the curvature of the
+    /// segment starting at the breakpoint. The segment follows `t^(4^c)`,
+    /// where `t` is the relative position within the segment and `c` its
+    /// curvature.
+    Envelope {
+        key: String,
+        /// Minimum and maximum allowed value of `x`, shown from left to right.
+        x_range: (f32, f32),
+        /// Minimum and maximum allowed value of `y`, shown from bottom to top.
+        y_range: (f32, f32),
+        /// Initially set breakpoints.
+        default: Vec<(f32, f32)>,
+        /// Allow users to set curvature of each segment.
+        curved: bool,
+        /// Width and height of the widget shown in a node. The width will be
+        /// treated as a minimal weight that may be increased in case there is
+        /// another widget that is wider.
+        size: [f32; 2],
+    },
+    /// Knob is a compact rotary dialog allowing users to dial-in a `f32` value
+    /// within given borders by dragging the mouse up and down. Consecutive
+    /// knobs are shown next to each other in a row.
+    Knob {
+        key: String,
+        /// Minimum allowed value.
+        min: f32,
+        /// Maximum allowed value.
+        max: f32,
+        /// Initially set value.
+        default: f32,
+        /// Format of the shown value as C-format string. e.g. `%.3f`.
+        format: String,
+        /// Diameter of the knob.
+        size: f32,
+    },
+    /// Number input is a field allowing users to dial-in a number by dragging
+    /// the mouse over it, or to type it in after Ctrl-click or double-click.
+    /// The variant of `default` selects whether the recorded value is an
+    /// `f32` or an `i32`.
+    NumberInput {
+        key: String,
+        /// Initially set value.
+        default: Number,
+        /// Optional minimum allowed value. Must be of the same variant as
+        /// `default`.
+        min: Option<Number>,
+        /// Optional maximum allowed value. Must be of the same variant as
+        /// `default`.
+        max: Option<Number>,
+        /// Change of the value per pixel of mouse movement.
+        step: f32,
+        /// Format of the shown value as C-format string. e.g. `%.3f` or `%d`.
+        format: String,
+        /// Minimal width of the widget. The width may be increased in case the
+        /// node contains another widget that is wider.
+        width: f32,
     },
     /// Trigger is nothing but a simple button. When clicked, it sets value of
     /// given `key` to `true`. When released, it turns back to `false`.
@@ -202,20 +394,92 @@ pub enum Widget {
         /// List of values to choose from.
         items: Vec<DropDownItem>,
     },
+    /// Plot is a visualization widget drawing series of samples as connected
+    /// lines. Samples are set as `Vec<f32>` for a plot with a single series or
+    /// as `Vec<Vec<f32>>` with one list of samples per series. Samples of each
+    /// series are spread evenly across the width of the plot.
+    Plot {
+        key: String,
+        /// Minimum and maximum value shown, from bottom to top. Samples out of
+        /// the range are cut off.
+        y_range: (f32, f32),
+        /// Series drawn in the plot. There must be at least one.
+        series: Vec<PlotSeries>,
+        /// Show the horizontal axis and labels of the range.
+        axes: bool,
+        /// Number of columns and rows of the background grid, if any.
+        grid: Option<[u32; 2]>,
+        /// Width and height of the widget shown in a node. The width will be
+        /// treated as a minimal weight that may be increased in case there is
+        /// another widget that is wider.
+        size: [f32; 2],
+    },
+    /// Image shows a picture sent by the backend as `model::Value::Rgba8`. The
+    /// picture is scaled to fit the widget, keeping its aspect ratio. This can
+    /// be used to preview images, spectrograms or video frames.
+    Image {
+        key: String,
+        /// Width and height of the widget shown in a node. The width will be
+        /// treated as a minimal weight that may be increased in case there is
+        /// another widget that is wider.
+        size: [f32; 2],
+    },
     /// Canvas is a visualization widget that can be fed with coordinates of
-    /// to-be-enabled pixels.
+    /// to-be-enabled pixels as `Vec<(f32, f32)>`, or with a list of
+    /// primitives to draw as `Vec<model::Shape>`.
+    ///
+    /// An interactive canvas reports mouse clicks, drags and scrolls within
+    /// its area through
+    /// [`Report::canvas_input`](../report/struct.Report.html#structfield.canvas_input),
+    /// while the backend keeps drawing its content.
     Canvas {
         key: String,
         /// Width and height of the widget shown in a node. The width will be
         /// treated as a minimal weight that may be increased in case there is
         /// another widget that is wider.
         size: [f32; 2],
+        /// Report mouse input received within the canvas.
+        interactive: bool,
     },
 }
 
 pub use Widget::*;
 
+/// Mapping of a position of the `Slider` widget to its value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scale {
+    /// The value changes evenly along the slider.
+    Linear,
+    /// Each ratio of values, e.g. an octave, takes the same space on the
+    /// slider. Both `min` and `max` must be positive.
+    Logarithmic,
+    /// The value is the position on the slider raised to the power of
+    /// `curve`. Curves above 1.0 leave more space for lower values, curves
+    /// below 1.0 for higher values.
+    Exponential { curve: f32 },
+    /// The value jumps between multiples of `step` added to `min`.
+    Steps { step: f32 },
+}
+
+/// A number recorded by the `NumberInput` widget.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Number {
+    F32(f32),
+    I32(i32),
+}
+
+/// A series of samples drawn in the `Plot` widget.
+#[derive(Clone, Debug)]
+pub struct PlotSeries {
+    /// Label shown in the legend of the plot. Series with an empty label are
+    /// not listed.
+    pub label: String,
+    /// RGBA color of the line, each component between 0.0 and 1.0.
+    pub color: [f32; 4],
+}
+
 /// An item listed in the `DropDown` widget.
+#[derive(Clone, Debug)]
 pub struct DropDownItem {
     /// Label shown on the item.
     pub label: String,
@@ -2,13 +2,27 @@
 //! passed to reduce function which, based on the action type, applies requested
 //! changes on the state.
 
-use crate::engine::state::{Patch, PinAddress};
+use crate::engine::state::{
+    CanvasEvent, DropDownItem, ImageData, NodeTemplate, Patch, Pin, PinAddress, Shape,
+};
 
 #[derive(Debug)]
 pub enum Action {
     Scroll {
         offset: [f32; 2],
     },
+    SetOffset {
+        offset: [f32; 2],
+    },
+    SetMinimapVisible {
+        visible: bool,
+    },
+    AddNodeTemplate {
+        node_template: NodeTemplate,
+    },
+    RemoveNodeTemplate {
+        class: String,
+    },
     AddNode {
         class: String,
         position: [f32; 2],
@@ -17,12 +31,48 @@ pub enum Action {
         node_id: String,
         offset: [f32; 2],
     },
+    SetNodeSize {
+        node_id: String,
+        size: [f32; 2],
+    },
+    AutoLayout {
+        root_node_id: Option<String>,
+    },
+    FinishNodeDrag,
+    SetBypass {
+        node_id: String,
+        bypassed: bool,
+    },
+    ResizeNode {
+        node_id: String,
+        size: [f32; 2],
+    },
+    SetNodeCollapsed {
+        node_id: String,
+        collapsed: bool,
+    },
+    SetGridVisible {
+        visible: bool,
+    },
+    SetSnapToGrid {
+        snap: bool,
+    },
+    ArrangeSelection {
+        arrangement: Arrangement,
+    },
     RemoveNode {
         node_id: String,
     },
     RemovePatch {
         patch: Patch,
     },
+    AddPin {
+        node_id: String,
+        pin: Pin,
+    },
+    RemovePin {
+        pin_address: PinAddress,
+    },
     SetTriggeredPin {
         pin_address: PinAddress,
     },
@@ -35,11 +85,107 @@ pub enum Action {
         patch: Patch,
     },
     ResetTriggeredPatch,
+    SetDropTargetPatch {
+        patch: Option<Patch>,
+    },
+    InsertNodeIntoPatch {
+        node_id: String,
+        patch: Patch,
+    },
+    AddWaypoint {
+        patch: Patch,
+        index: usize,
+        position: [f32; 2],
+    },
+    MoveWaypoint {
+        patch: Patch,
+        index: usize,
+        offset: [f32; 2],
+    },
+    RemoveWaypoint {
+        patch: Patch,
+        index: usize,
+    },
+    SetNodeLabel {
+        node_id: String,
+        label: String,
+    },
+    StartNodeRename {
+        node_id: String,
+    },
+    SetNodeRenameBuffer {
+        value: String,
+    },
+    CancelNodeRename,
+    StartSliderTextInput {
+        node_id: String,
+        key: String,
+    },
+    FinishSliderTextInput,
+    AddFrame {
+        position: [f32; 2],
+    },
+    MoveFrame {
+        frame_id: String,
+        offset: [f32; 2],
+    },
+    ResizeFrame {
+        frame_id: String,
+        size: [f32; 2],
+    },
+    RemoveFrame {
+        frame_id: String,
+    },
+    SetFrameTitle {
+        frame_id: String,
+        title: String,
+    },
+    SetFrameColor {
+        frame_id: String,
+        color: [f32; 4],
+    },
+    SetTriggeredFrame {
+        frame_id: String,
+    },
+    ResetTriggeredFrame,
+    ToggleSelection {
+        id: String,
+    },
+    ResetSelection,
+    StartSelectionRectangle {
+        position: [f32; 2],
+    },
+    UpdateSelectionRectangle {
+        position: [f32; 2],
+    },
+    FinishSelectionRectangle,
+    GroupSelection,
+    UngroupSubgraph {
+        subgraph_id: String,
+    },
+    OpenSubgraph {
+        subgraph_id: Option<String>,
+    },
+    MoveSubgraph {
+        subgraph_id: String,
+        offset: [f32; 2],
+    },
     SetValue {
         node_id: String,
         key: String,
         value: Value,
     },
+    UpdateWidget {
+        node_id: String,
+        key: String,
+        update: WidgetUpdate,
+    },
+    CanvasInput {
+        node_id: String,
+        key: String,
+        position: [f32; 2],
+        event: CanvasEvent,
+    },
     OpenFileLoadDialog,
     OpenFileSaveDialog,
     SetFileDialogBuffer {
@@ -54,12 +200,39 @@ pub enum Action {
     CloseFileDialog,
 }
 
+#[derive(Debug)]
+pub enum WidgetUpdate {
+    SliderRange { min: f32, max: f32 },
+    SliderFormat { format: String },
+    DropDownItems { items: Vec<DropDownItem> },
+    Label { label: String },
+    ReadOnly { read_only: bool },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Arrangement {
+    AlignLeft,
+    AlignRight,
+    AlignTop,
+    AlignBottom,
+    DistributeHorizontally,
+    DistributeVertically,
+}
+
 #[derive(Clone, Debug)]
 pub enum Value {
     String(String),
     F32(f32),
+    F32F32((f32, f32)),
+    Point((f32, f32)),
+    I32(i32),
     Bool(bool),
     VecF32F32(Vec<(f32, f32)>),
+    VecF32F32F32(Vec<(f32, f32, f32)>),
+    VecF32(Vec<f32>),
+    VecVecF32(Vec<Vec<f32>>),
+    Shapes(Vec<Shape>),
+    Rgba8(ImageData),
 }
 
 impl Value {
@@ -79,16 +252,40 @@ impl Value {
         }
     }
 
-    pub fn expect_string(self, message: &str) -> String {
-        if let Self::String(value) = self {
+    pub fn expect_f32_f32(self, message: &str) -> (f32, f32) {
+        if let Self::F32F32(value) = self {
+            value
+        } else {
+            panic!("{}", message);
+        }
+    }
+
+    pub fn expect_point(self, message: &str) -> (f32, f32) {
+        if let Self::Point(value) = self {
             value
         } else {
             panic!("{}", message);
         }
     }
 
-    pub fn expect_vec_f32_f32(self, message: &str) -> Vec<(f32, f32)> {
-        if let Self::VecF32F32(value) = self {
+    pub fn expect_i32(self, message: &str) -> i32 {
+        if let Self::I32(value) = self {
+            value
+        } else {
+            panic!("{}", message);
+        }
+    }
+
+    pub fn expect_rgba8(self, message: &str) -> ImageData {
+        if let Self::Rgba8(value) = self {
+            value
+        } else {
+            panic!("{}", message);
+        }
+    }
+
+    pub fn expect_string(self, message: &str) -> String {
+        if let Self::String(value) = self {
             value
         } else {
             panic!("{}", message);
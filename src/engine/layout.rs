@@ -0,0 +1,597 @@
+//! Layered left-to-right layout of the graph, loosely following the Sugiyama
+//! method. Items are first assigned to layers so that patches lead from left to
+//! right, then the order within each layer is adjusted to reduce crossing of
+//! patches and finally the items are placed next to each other, respecting
+//! their sizes.
+//!
+//! Each level of nesting is arranged separately. Subgraphs are treated as any
+//! other item on their level.
+//!
+//! Apart from that, the module provides alignment and distribution of nodes
+//! picked by the user.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::action::Arrangement;
+use crate::engine::state::State;
+
+const HORIZONTAL_SPACING: f32 = 80.0;
+const VERTICAL_SPACING: f32 = 30.0;
+const DEFAULT_ITEM_SIZE: [f32; 2] = [150.0, 100.0];
+const ORDERING_SWEEPS: usize = 4;
+
+/// Arrange all nodes and subgraphs, starting from the top left corner of the
+/// current content of each level.
+pub fn arrange_all(state: &mut State) {
+    let mut levels = vec![None];
+    levels.extend(state.subgraphs().iter().map(|s| Some(s.id().to_owned())));
+
+    for level in levels {
+        let graph = Graph::from_level(state, level.as_deref());
+        if let Some(origin) = graph.top_left_corner() {
+            let layers = graph.assign_layers(&graph.ids, None);
+            let positions = graph.place(graph.order(layers), origin);
+            apply_positions(state, positions);
+        }
+    }
+}
+
+/// Arrange only items downstream of the given node, i.e. those reachable
+/// through its outputs on its level of nesting. The node itself stays in
+/// place.
+///
+/// # Panics
+///
+/// Panics if the node does not exist.
+pub fn arrange_downstream(state: &mut State, node_id: &str) {
+    let level = state.parent_of(node_id).map(str::to_owned);
+    let graph = Graph::from_level(state, level.as_deref());
+
+    let origin = graph
+        .items
+        .get(node_id)
+        .expect("node_id must match an existing node")
+        .position;
+    let reachable = graph.reachable_from(node_id);
+    let layers = graph.assign_layers(&reachable, Some(node_id));
+    let positions = graph.place(graph.order(layers), origin);
+    apply_positions(state, positions);
+}
+
+/// Align or distribute the given nodes. Ids which do not belong to a node, e.g.
+/// those of subgraphs, are ignored. Distribution keeps the outermost nodes in
+/// place and spreads the rest between them so the gaps are equal.
+pub fn arrange(state: &mut State, ids: &[String], arrangement: Arrangement) {
+    let mut nodes: Vec<_> = state
+        .nodes_mut()
+        .iter_mut()
+        .filter(|n| ids.iter().any(|id| id == n.id()))
+        .collect();
+    if nodes.len() < 2 {
+        return;
+    }
+
+    let size_of = |size: [f32; 2]| {
+        if size == [0.0, 0.0] {
+            DEFAULT_ITEM_SIZE
+        } else {
+            size
+        }
+    };
+
+    match arrangement {
+        Arrangement::AlignLeft => {
+            let left = nodes.iter().map(|n| n.position[0]).fold(f32::MAX, f32::min);
+            nodes.iter_mut().for_each(|n| n.position[0] = left);
+        }
+        Arrangement::AlignRight => {
+            let right = nodes
+                .iter()
+                .map(|n| n.position[0] + size_of(n.size)[0])
+                .fold(f32::MIN, f32::max);
+            nodes
+                .iter_mut()
+                .for_each(|n| n.position[0] = right - size_of(n.size)[0]);
+        }
+        Arrangement::AlignTop => {
+            let top = nodes.iter().map(|n| n.position[1]).fold(f32::MAX, f32::min);
+            nodes.iter_mut().for_each(|n| n.position[1] = top);
+        }
+        Arrangement::AlignBottom => {
+            let bottom = nodes
+                .iter()
+                .map(|n| n.position[1] + size_of(n.size)[1])
+                .fold(f32::MIN, f32::max);
+            nodes
+                .iter_mut()
+                .for_each(|n| n.position[1] = bottom - size_of(n.size)[1]);
+        }
+        Arrangement::DistributeHorizontally | Arrangement::DistributeVertically => {
+            let axis = if arrangement == Arrangement::DistributeHorizontally {
+                0
+            } else {
+                1
+            };
+            nodes.sort_by(|a, b| a.position[axis].total_cmp(&b.position[axis]));
+            let start = nodes[0].position[axis];
+            let end = nodes
+                .iter()
+                .map(|n| n.position[axis] + size_of(n.size)[axis])
+                .fold(f32::MIN, f32::max);
+            let occupied: f32 = nodes.iter().map(|n| size_of(n.size)[axis]).sum();
+            let gap = (end - start - occupied) / (nodes.len() - 1) as f32;
+            let mut cursor = start;
+            for node in nodes.iter_mut() {
+                node.position[axis] = cursor;
+                cursor += size_of(node.size)[axis] + gap;
+            }
+        }
+    }
+}
+
+struct Item {
+    position: [f32; 2],
+    size: [f32; 2],
+}
+
+struct Graph {
+    ids: Vec<String>,
+    items: HashMap<String, Item>,
+    edges: HashSet<(String, String)>,
+}
+
+impl Graph {
+    fn from_level(state: &State, level: Option<&str>) -> Self {
+        let mut ids = Vec::new();
+        let mut items = HashMap::new();
+
+        for node in state
+            .nodes()
+            .iter()
+            .filter(|n| n.parent.as_deref() == level)
+        {
+            let size = if node.size == [0.0, 0.0] {
+                DEFAULT_ITEM_SIZE
+            } else {
+                node.size
+            };
+            ids.push(node.id().to_owned());
+            items.insert(
+                node.id().to_owned(),
+                Item {
+                    position: node.position,
+                    size,
+                },
+            );
+        }
+
+        for subgraph in state
+            .subgraphs()
+            .iter()
+            .filter(|s| s.parent.as_deref() == level)
+        {
+            ids.push(subgraph.id().to_owned());
+            items.insert(
+                subgraph.id().to_owned(),
+                Item {
+                    position: subgraph.position,
+                    size: DEFAULT_ITEM_SIZE,
+                },
+            );
+        }
+
+        let edges = state
+            .patches()
+            .iter()
+            .filter_map(|p| {
+                let source = state.representative_on_level(p.source().node_id(), level)?;
+                let destination =
+                    state.representative_on_level(p.destination().node_id(), level)?;
+                if source != destination {
+                    Some((source, destination))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self { ids, items, edges }
+    }
+
+    fn top_left_corner(&self) -> Option<[f32; 2]> {
+        self.items
+            .values()
+            .map(|i| i.position)
+            .fold(None, |corner, p| {
+                Some(match corner {
+                    Some([x, y]) => [f32::min(x, p[0]), f32::min(y, p[1])],
+                    None => p,
+                })
+            })
+    }
+
+    fn successors(&self, id: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(s, _)| s == id)
+            .map(|(_, d)| d.as_str())
+            .collect()
+    }
+
+    fn predecessors(&self, id: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(_, d)| d == id)
+            .map(|(s, _)| s.as_str())
+            .collect()
+    }
+
+    fn reachable_from(&self, root: &str) -> Vec<String> {
+        let mut reachable = vec![root.to_owned()];
+        let mut index = 0;
+        while index < reachable.len() {
+            let mut successors: Vec<_> = self
+                .successors(&reachable[index])
+                .into_iter()
+                .filter(|s| !reachable.iter().any(|r| r == s))
+                .map(str::to_owned)
+                .collect();
+            successors.sort();
+            successors.dedup();
+            reachable.extend(successors);
+            index += 1;
+        }
+        reachable
+    }
+
+    /// Assign each of the given items to a layer using the longest path from
+    /// the sources. Cycles are broken by ignoring edges leading back to an item
+    /// which is currently being visited by a depth-first search. If `root` is
+    /// given, the search starts from it, making sure it ends up in the first
+    /// layer.
+    fn assign_layers(&self, ids: &[String], root: Option<&str>) -> HashMap<String, usize> {
+        let subset: HashSet<&str> = ids.iter().map(String::as_str).collect();
+
+        let mut back_edges = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut stack = Vec::new();
+        for start in root.into_iter().chain(ids.iter().map(String::as_str)) {
+            if visited.contains(start) {
+                continue;
+            }
+            visited.insert(start);
+            stack.push((start, self.sorted_successors(start, &subset)));
+            while let Some((current, successors)) = stack.last_mut() {
+                let current = *current;
+                if let Some(next) = successors.pop() {
+                    if stack.iter().any(|(id, _)| *id == next) {
+                        back_edges.insert((current, next));
+                    } else if !visited.contains(next) {
+                        visited.insert(next);
+                        stack.push((next, self.sorted_successors(next, &subset)));
+                    }
+                } else {
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut layers = HashMap::new();
+        let mut remaining: Vec<&str> = ids.iter().map(String::as_str).collect();
+        while !remaining.is_empty() {
+            let ready = remaining
+                .iter()
+                .position(|id| {
+                    self.predecessors(id)
+                        .into_iter()
+                        .filter(|p| subset.contains(p) && !back_edges.contains(&(*p, *id)))
+                        .all(|p| layers.contains_key(p))
+                })
+                .expect("Graph without back edges must be acyclic");
+            let id = remaining.remove(ready);
+            let layer = self
+                .predecessors(id)
+                .into_iter()
+                .filter(|p| subset.contains(p) && !back_edges.contains(&(*p, id)))
+                .map(|p| layers[p] + 1)
+                .max()
+                .unwrap_or(0);
+            layers.insert(id.to_owned(), layer);
+        }
+
+        layers
+    }
+
+    fn sorted_successors<'a>(&'a self, id: &'a str, subset: &HashSet<&str>) -> Vec<&'a str> {
+        let mut successors: Vec<_> = self
+            .successors(id)
+            .into_iter()
+            .filter(|s| subset.contains(s))
+            .collect();
+        successors.sort_unstable_by(|a, b| b.cmp(a));
+        successors
+    }
+
+    /// Order items within layers, starting from their current vertical
+    /// position and then repeatedly sorting them by the average index of their
+    /// neighbours in the adjacent layer.
+    fn order(&self, layers: HashMap<String, usize>) -> Vec<Vec<String>> {
+        let count = layers.values().max().map_or(0, |m| m + 1);
+        let mut ordered: Vec<Vec<String>> = vec![Vec::new(); count];
+        for id in self.ids.iter().filter(|id| layers.contains_key(*id)) {
+            ordered[layers[id]].push(id.clone());
+        }
+        for layer in ordered.iter_mut() {
+            layer.sort_by(|a, b| self.items[a].position[1].total_cmp(&self.items[b].position[1]));
+        }
+
+        for _ in 0..ORDERING_SWEEPS {
+            for i in 1..count {
+                let (fixed, free) = ordered.split_at_mut(i);
+                self.sort_by_barycenter(&mut free[0], &fixed[i - 1], true);
+            }
+            for i in (0..count.saturating_sub(1)).rev() {
+                let (free, fixed) = ordered.split_at_mut(i + 1);
+                self.sort_by_barycenter(&mut free[i], &fixed[0], false);
+            }
+        }
+
+        ordered
+    }
+
+    /// Sort the layer by the average index of neighbours of each item in the
+    /// fixed layer. The fixed layer is either the preceding or the following
+    /// one.
+    fn sort_by_barycenter(&self, layer: &mut [String], fixed: &[String], fixed_precedes: bool) {
+        let barycenters: HashMap<String, f32> = layer
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let neighbours = if fixed_precedes {
+                    self.predecessors(id)
+                } else {
+                    self.successors(id)
+                };
+                let indices: Vec<usize> = neighbours
+                    .iter()
+                    .filter_map(|n| fixed.iter().position(|f| f == n))
+                    .collect();
+                let barycenter = if indices.is_empty() {
+                    index as f32
+                } else {
+                    indices.iter().sum::<usize>() as f32 / indices.len() as f32
+                };
+                (id.clone(), barycenter)
+            })
+            .collect();
+        layer.sort_by(|a, b| barycenters[a].total_cmp(&barycenters[b]));
+    }
+
+    /// Place layers next to each other, each as wide as its widest item, and
+    /// stack items of a layer on top of each other.
+    fn place(&self, layers: Vec<Vec<String>>, origin: [f32; 2]) -> HashMap<String, [f32; 2]> {
+        let mut positions = HashMap::new();
+        let mut x = origin[0];
+        for layer in layers.iter() {
+            let mut y = origin[1];
+            for id in layer.iter() {
+                positions.insert(id.clone(), [x, y]);
+                y += self.items[id].size[1] + VERTICAL_SPACING;
+            }
+            let width = layer
+                .iter()
+                .map(|id| self.items[id].size[0])
+                .fold(0.0, f32::max);
+            x += width + HORIZONTAL_SPACING;
+        }
+        positions
+    }
+}
+
+fn apply_positions(state: &mut State, positions: HashMap<String, [f32; 2]>) {
+    for node in state.nodes_mut().iter_mut() {
+        if let Some(position) = positions.get(node.id()) {
+            node.position = *position;
+        }
+    }
+    for subgraph in state.subgraphs_mut().iter_mut() {
+        if let Some(position) = positions.get(subgraph.id()) {
+            subgraph.position = *position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::state::{Direction, NodeTemplate, Pin, PinAddress};
+
+    fn initialize_state_with_chain(count: usize) -> State {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "node".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        for _ in 0..count {
+            state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        }
+        for i in 1..count {
+            state
+                .add_patch(
+                    PinAddress::new(format!("node:{}", i - 1), "out".to_owned()),
+                    PinAddress::new(format!("node:{}", i), "in".to_owned()),
+                )
+                .unwrap();
+        }
+        state
+    }
+
+    fn position_of(state: &State, id: &str) -> [f32; 2] {
+        state
+            .nodes()
+            .iter()
+            .find(|n| n.id() == id)
+            .unwrap()
+            .position
+    }
+
+    #[test]
+    fn arrange_chain_left_to_right() {
+        let mut state = initialize_state_with_chain(3);
+
+        arrange_all(&mut state);
+
+        let step = DEFAULT_ITEM_SIZE[0] + HORIZONTAL_SPACING;
+        assert_eq!(position_of(&state, "node:0"), [0.0, 0.0]);
+        assert_eq!(position_of(&state, "node:1"), [step, 0.0]);
+        assert_eq!(position_of(&state, "node:2"), [2.0 * step, 0.0]);
+    }
+
+    #[test]
+    fn stack_unconnected_nodes_respecting_their_size() {
+        let mut state = initialize_state_with_chain(2);
+        state.patches_mut().clear();
+        state.nodes_mut()[0].size = [100.0, 200.0];
+
+        arrange_all(&mut state);
+
+        assert_eq!(position_of(&state, "node:0"), [0.0, 0.0]);
+        assert_eq!(
+            position_of(&state, "node:1"),
+            [0.0, 200.0 + VERTICAL_SPACING]
+        );
+    }
+
+    #[test]
+    fn arrange_cyclic_graph() {
+        let mut state = initialize_state_with_chain(3);
+        state
+            .add_patch(
+                PinAddress::new("node:2".to_owned(), "out".to_owned()),
+                PinAddress::new("node:0".to_owned(), "in".to_owned()),
+            )
+            .unwrap();
+
+        arrange_all(&mut state);
+
+        let x: HashSet<_> = (0..3)
+            .map(|i| position_of(&state, &format!("node:{}", i))[0] as i32)
+            .collect();
+        assert_eq!(x.len(), 3);
+    }
+
+    #[test]
+    fn arrange_nodes_with_non_finite_position() {
+        let mut state = initialize_state_with_chain(3);
+        state.patches_mut().clear();
+        state.nodes_mut()[1].position = [f32::NAN, f32::NAN];
+
+        arrange_all(&mut state);
+
+        assert!(state
+            .nodes()
+            .iter()
+            .all(|n| n.position[0].is_finite() && n.position[1].is_finite()));
+    }
+
+    #[test]
+    fn align_left() {
+        let mut state = initialize_state_with_chain(3);
+        state.nodes_mut()[0].position = [100.0, 0.0];
+        state.nodes_mut()[1].position = [50.0, 100.0];
+        state.nodes_mut()[2].position = [500.0, 200.0];
+
+        arrange(
+            &mut state,
+            &["node:0".to_owned(), "node:1".to_owned()],
+            Arrangement::AlignLeft,
+        );
+
+        assert_eq!(position_of(&state, "node:0"), [50.0, 0.0]);
+        assert_eq!(position_of(&state, "node:1"), [50.0, 100.0]);
+        assert_eq!(position_of(&state, "node:2"), [500.0, 200.0]);
+    }
+
+    #[test]
+    fn align_bottom_respecting_size() {
+        let mut state = initialize_state_with_chain(2);
+        state.nodes_mut()[0].position = [0.0, 0.0];
+        state.nodes_mut()[0].size = [100.0, 300.0];
+        state.nodes_mut()[1].position = [200.0, 0.0];
+        state.nodes_mut()[1].size = [100.0, 100.0];
+
+        arrange(
+            &mut state,
+            &["node:0".to_owned(), "node:1".to_owned()],
+            Arrangement::AlignBottom,
+        );
+
+        assert_eq!(position_of(&state, "node:0"), [0.0, 0.0]);
+        assert_eq!(position_of(&state, "node:1"), [200.0, 200.0]);
+    }
+
+    #[test]
+    fn distribute_horizontally() {
+        let mut state = initialize_state_with_chain(3);
+        for node in state.nodes_mut().iter_mut() {
+            node.size = [100.0, 100.0];
+        }
+        state.nodes_mut()[0].position = [0.0, 0.0];
+        state.nodes_mut()[1].position = [120.0, 50.0];
+        state.nodes_mut()[2].position = [500.0, 0.0];
+
+        arrange(
+            &mut state,
+            &[
+                "node:0".to_owned(),
+                "node:1".to_owned(),
+                "node:2".to_owned(),
+            ],
+            Arrangement::DistributeHorizontally,
+        );
+
+        assert_eq!(position_of(&state, "node:0"), [0.0, 0.0]);
+        assert_eq!(position_of(&state, "node:1"), [250.0, 50.0]);
+        assert_eq!(position_of(&state, "node:2"), [500.0, 0.0]);
+    }
+
+    #[test]
+    fn distribute_nodes_with_non_finite_position() {
+        let mut state = initialize_state_with_chain(3);
+        state.nodes_mut()[0].position = [0.0, 0.0];
+        state.nodes_mut()[1].position = [f32::NAN, 0.0];
+        state.nodes_mut()[2].position = [500.0, 0.0];
+
+        arrange(
+            &mut state,
+            &[
+                "node:0".to_owned(),
+                "node:1".to_owned(),
+                "node:2".to_owned(),
+            ],
+            Arrangement::DistributeHorizontally,
+        );
+
+        assert_eq!(position_of(&state, "node:2")[1], 0.0);
+    }
+
+    #[test]
+    fn arrange_only_downstream_nodes() {
+        let mut state = initialize_state_with_chain(3);
+        state.nodes_mut()[0].position = [500.0, 500.0];
+        state.nodes_mut()[1].position = [100.0, 100.0];
+
+        arrange_downstream(&mut state, "node:1");
+
+        let step = DEFAULT_ITEM_SIZE[0] + HORIZONTAL_SPACING;
+        assert_eq!(position_of(&state, "node:0"), [500.0, 500.0]);
+        assert_eq!(position_of(&state, "node:1"), [100.0, 100.0]);
+        assert_eq!(position_of(&state, "node:2"), [100.0 + step, 100.0]);
+    }
+}
@@ -19,7 +19,9 @@ pub mod action;
 pub mod reducer;
 pub mod state;
 pub mod system;
+pub mod texture;
 pub mod view;
 pub mod window;
 
+mod layout;
 mod snapshot;
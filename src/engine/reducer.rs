@@ -4,18 +4,28 @@ extern crate serde_json;
 
 use std::fs;
 
-use crate::engine::action::{Action, Value};
+use crate::engine::action::{Action, Arrangement, Value, WidgetUpdate};
+use crate::engine::layout;
 use crate::engine::snapshot::Snapshot;
-use crate::engine::state::{FileDialogMode, Node, Patch, PinAddress, State, Widget};
+use crate::engine::state::{
+    CanvasEvent, CanvasInput, FileDialogMode, Frame, Grid, Node, NodeRename, NodeTemplate, Number,
+    Patch, Pin, PinAddress, State, Widget, WidgetAddress,
+};
 use crate::vec2;
 
+const FRAME_DEFAULT_TITLE: &str = "Frame";
+const FRAME_DEFAULT_SIZE: [f32; 2] = [300.0, 200.0];
+const FRAME_DEFAULT_COLOR: [f32; 4] = [0.6, 0.7, 0.9, 0.3];
+const FRAME_MIN_SIZE: [f32; 2] = [100.0, 50.0];
+const SUBGRAPH_DEFAULT_LABEL: &str = "Subgraph";
+
 /// Type signalizing the effect of a reduce function.
 pub enum ReduceResult {
     /// The function changed the model, i.e. some of the graph modeling values
     /// was changed.
     ModelChanged,
     /// The function only changed secondary properties of the state, e.g. moved
-    /// nodes around.
+    /// nodes around while the layout is not reported.
     ModelUnchanged,
 }
 
@@ -27,24 +37,110 @@ impl ReduceResult {
     }
 }
 
+/// Changes of the layout are reported only when the report carries it.
+fn layout_changed(state: &State) -> ReduceResult {
+    if state.report_layout {
+        ModelChanged
+    } else {
+        ModelUnchanged
+    }
+}
+
 pub fn reduce(state: &mut State, action: Action) -> ReduceResult {
+    // Canvas input is an event rather than a state. It is kept only for the
+    // report that follows the action carrying it.
+    state.set_canvas_input(None);
+
     match action {
         Action::Scroll { offset } => scroll(state, offset),
+        Action::SetOffset { offset } => set_offset(state, offset),
+        Action::SetMinimapVisible { visible } => set_minimap_visible(state, visible),
+        Action::AddNodeTemplate { node_template } => add_node_template(state, node_template),
+        Action::RemoveNodeTemplate { class } => remove_node_template(state, class),
         Action::AddNode { class, position } => add_node(state, class, position),
         Action::MoveNode { node_id, offset } => move_node(state, node_id, offset),
         Action::RemoveNode { node_id } => remove_node(state, node_id),
         Action::RemovePatch { patch } => remove_patch(state, patch),
+        Action::AddPin { node_id, pin } => add_pin(state, node_id, pin),
+        Action::RemovePin { pin_address } => remove_pin(state, pin_address),
         Action::SetTriggeredNode { node_id } => set_triggered_node(state, node_id),
         Action::ResetTriggeredNode => reset_triggered_node(state),
         Action::SetTriggeredPin { pin_address } => set_triggered_pin(state, pin_address),
         Action::ResetTriggeredPin => reset_triggered_pin(state),
         Action::SetTriggeredPatch { patch } => set_triggered_patch(state, patch),
         Action::ResetTriggeredPatch => reset_triggered_patch(state),
+        Action::FinishNodeDrag => finish_node_drag(state),
+        Action::SetBypass { node_id, bypassed } => set_bypass(state, node_id, bypassed),
+        Action::ResizeNode { node_id, size } => resize_node(state, node_id, size),
+        Action::SetNodeCollapsed { node_id, collapsed } => {
+            set_node_collapsed(state, node_id, collapsed)
+        }
+        Action::SetGridVisible { visible } => set_grid_visible(state, visible),
+        Action::SetSnapToGrid { snap } => set_snap_to_grid(state, snap),
+        Action::ArrangeSelection { arrangement } => arrange_selection(state, arrangement),
+        Action::SetNodeSize { node_id, size } => set_node_size(state, node_id, size),
+        Action::AutoLayout { root_node_id } => auto_layout(state, root_node_id),
+        Action::SetDropTargetPatch { patch } => set_drop_target_patch(state, patch),
+        Action::InsertNodeIntoPatch { node_id, patch } => {
+            insert_node_into_patch(state, node_id, patch)
+        }
+        Action::AddWaypoint {
+            patch,
+            index,
+            position,
+        } => add_waypoint(state, patch, index, position),
+        Action::MoveWaypoint {
+            patch,
+            index,
+            offset,
+        } => move_waypoint(state, patch, index, offset),
+        Action::RemoveWaypoint { patch, index } => remove_waypoint(state, patch, index),
+        Action::SetNodeLabel { node_id, label } => set_node_label(state, node_id, label),
+        Action::StartNodeRename { node_id } => start_node_rename(state, node_id),
+        Action::SetNodeRenameBuffer { value } => set_node_rename_buffer(state, value),
+        Action::CancelNodeRename => cancel_node_rename(state),
+        Action::StartSliderTextInput { node_id, key } => {
+            start_slider_text_input(state, node_id, key)
+        }
+        Action::FinishSliderTextInput => finish_slider_text_input(state),
+        Action::AddFrame { position } => add_frame(state, position),
+        Action::MoveFrame { frame_id, offset } => move_frame(state, frame_id, offset),
+        Action::ResizeFrame { frame_id, size } => resize_frame(state, frame_id, size),
+        Action::RemoveFrame { frame_id } => remove_frame(state, frame_id),
+        Action::SetFrameTitle { frame_id, title } => set_frame_title(state, frame_id, title),
+        Action::SetFrameColor { frame_id, color } => set_frame_color(state, frame_id, color),
+        Action::SetTriggeredFrame { frame_id } => set_triggered_frame(state, frame_id),
+        Action::ResetTriggeredFrame => reset_triggered_frame(state),
+        Action::ToggleSelection { id } => toggle_selection(state, id),
+        Action::ResetSelection => reset_selection(state),
+        Action::StartSelectionRectangle { position } => start_selection_rectangle(state, position),
+        Action::UpdateSelectionRectangle { position } => {
+            update_selection_rectangle(state, position)
+        }
+        Action::FinishSelectionRectangle => finish_selection_rectangle(state),
+        Action::GroupSelection => group_selection(state),
+        Action::UngroupSubgraph { subgraph_id } => ungroup_subgraph(state, subgraph_id),
+        Action::OpenSubgraph { subgraph_id } => open_subgraph(state, subgraph_id),
+        Action::MoveSubgraph {
+            subgraph_id,
+            offset,
+        } => move_subgraph(state, subgraph_id, offset),
         Action::SetValue {
             node_id,
             key,
             value,
         } => set_value(state, node_id, key, value),
+        Action::UpdateWidget {
+            node_id,
+            key,
+            update,
+        } => update_widget(state, node_id, key, update),
+        Action::CanvasInput {
+            node_id,
+            key,
+            position,
+            event,
+        } => canvas_input(state, node_id, key, position, event),
         Action::OpenFileLoadDialog => open_file_dialog(state, FileDialogMode::Load),
         Action::OpenFileSaveDialog => open_file_dialog(state, FileDialogMode::Save),
         Action::SetFileDialogBuffer { value } => set_file_dialog_buffer(state, value),
@@ -56,9 +152,50 @@ pub fn reduce(state: &mut State, action: Action) -> ReduceResult {
 
 fn scroll(state: &mut State, offset: [f32; 2]) -> ReduceResult {
     state.offset = vec2::sum(&[state.offset, offset]);
+    layout_changed(state)
+}
+
+fn set_offset(state: &mut State, offset: [f32; 2]) -> ReduceResult {
+    state.offset = offset;
+    layout_changed(state)
+}
+
+fn set_minimap_visible(state: &mut State, visible: bool) -> ReduceResult {
+    state.set_minimap_visible(visible);
+    ModelUnchanged
+}
+
+fn add_node_template(state: &mut State, node_template: NodeTemplate) -> ReduceResult {
+    // Templates may be rediscovered by the user code, gracefully ignore them.
+    if !state.has_node_template(node_template.class()) {
+        state.add_node_template(node_template);
+    }
     ModelUnchanged
 }
 
+fn remove_node_template(state: &mut State, class: String) -> ReduceResult {
+    if state.remove_node_template(&class).is_none() {
+        return ModelUnchanged;
+    }
+
+    let instances: Vec<String> = state
+        .nodes()
+        .iter()
+        .filter(|n| *n.class() == class)
+        .map(|n| n.id().to_string())
+        .collect();
+
+    if instances.is_empty() {
+        return ModelUnchanged;
+    }
+
+    instances.into_iter().for_each(|node_id| {
+        remove_node(state, node_id);
+    });
+
+    ModelChanged
+}
+
 fn add_node(state: &mut State, class: String, position: [f32; 2]) -> ReduceResult {
     let node_template = state
         .node_templates()
@@ -76,14 +213,87 @@ fn remove_node(state: &mut State, node_id: String) -> ReduceResult {
     state
         .patches_mut()
         .retain(|p| *p.source().node_id() != node_id && *p.destination().node_id() != node_id);
+
+    if matches!(state.triggered_node(), Some(id) if *id == node_id) {
+        state.set_triggered_node(None);
+    }
+    if matches!(state.triggered_pin(), Some(p) if *p.node_id() == node_id) {
+        state.set_triggered_pin(None);
+    }
+    if matches!(state.triggered_patch(), Some(p) if *p.source().node_id() == node_id || *p.destination().node_id() == node_id)
+    {
+        state.set_triggered_patch(None);
+    }
+    if matches!(state.renamed_node(), Some(r) if r.node_id == node_id) {
+        state.set_renamed_node(None);
+    }
+    state.selection_mut().retain(|id| *id != node_id);
+
+    state.drop_stale_waypoints();
+    state.drop_empty_subgraphs();
+    state.update_variadic_pins();
+
     ModelChanged
 }
 
 fn remove_patch(state: &mut State, patch: Patch) -> ReduceResult {
     state.patches_mut().remove(&patch);
+    state.drop_stale_waypoints();
+    state.update_variadic_pins();
     ModelChanged
 }
 
+fn add_pin(state: &mut State, node_id: String, pin: Pin) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        // In case the node was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+
+    if node.add_pin(pin) {
+        ModelChanged
+    } else {
+        ModelUnchanged
+    }
+}
+
+fn remove_pin(state: &mut State, pin_address: PinAddress) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, pin_address.node_id()) {
+        node
+    } else {
+        // In case the node was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+
+    if node.remove_pin(pin_address.pin_class()).is_none() {
+        return ModelUnchanged;
+    }
+
+    if matches!(state.triggered_pin(), Some(p) if *p == pin_address) {
+        state.set_triggered_pin(None);
+    }
+    if matches!(state.triggered_patch(), Some(p) if *p.source() == pin_address || *p.destination() == pin_address)
+    {
+        state.set_triggered_patch(None);
+    }
+
+    let original_patches_count = state.patches().len();
+    state
+        .patches_mut()
+        .retain(|p| *p.source() != pin_address && *p.destination() != pin_address);
+    let patches_removed = state.patches().len() != original_patches_count;
+
+    state.drop_stale_waypoints();
+    state.update_variadic_pins();
+
+    if patches_removed {
+        ModelChanged
+    } else {
+        ModelUnchanged
+    }
+}
+
 fn set_triggered_node(state: &mut State, node_id: String) -> ReduceResult {
     let node_index = state
         .nodes()
@@ -100,19 +310,96 @@ fn set_triggered_node(state: &mut State, node_id: String) -> ReduceResult {
 
 fn reset_triggered_node(state: &mut State) -> ReduceResult {
     state.set_triggered_node(None);
+    state.set_drop_target_patch(None);
     ModelUnchanged
 }
 
 fn move_node(state: &mut State, node_id: String, offset: [f32; 2]) -> ReduceResult {
-    let mut node = state
-        .nodes_mut()
-        .iter_mut()
-        .find(|n| n.id() == node_id)
-        .expect("node_id must match an existing node");
-    node.position = vec2::sum(&[node.position, offset]);
+    let snap = state.grid.snap;
+    let drag_position = state.drag_positions().get(&node_id).copied();
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        // In case the node was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+
+    if snap {
+        // The unsnapped position is tracked separately for each dragged node,
+        // otherwise small mouse movements would be rounded away and the node
+        // would never move.
+        let position = vec2::sum(&[drag_position.unwrap_or(node.position), offset]);
+        node.position = Grid::snapped(position);
+        state.drag_positions_mut().insert(node_id, position);
+    } else {
+        node.position = vec2::sum(&[node.position, offset]);
+    }
+
+    layout_changed(state)
+}
+
+fn resize_node(state: &mut State, node_id: String, size: [f32; 2]) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        // In case the node was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+    if !node.resizable {
+        return ModelUnchanged;
+    }
+    node.custom_size = Some([f32::max(size[0], 0.0), f32::max(size[1], 0.0)]);
+    ModelUnchanged
+}
+
+fn set_node_collapsed(state: &mut State, node_id: String, collapsed: bool) -> ReduceResult {
+    if let Some(node) = state.nodes_mut().iter_mut().find(|n| n.id() == node_id) {
+        node.collapsed = collapsed;
+    }
+    ModelUnchanged
+}
+
+fn finish_node_drag(state: &mut State) -> ReduceResult {
+    state.drag_positions_mut().clear();
+    ModelUnchanged
+}
+
+fn set_grid_visible(state: &mut State, visible: bool) -> ReduceResult {
+    state.grid.visible = visible;
+    ModelUnchanged
+}
+
+fn set_snap_to_grid(state: &mut State, snap: bool) -> ReduceResult {
+    state.grid.snap = snap;
+    ModelUnchanged
+}
+
+fn arrange_selection(state: &mut State, arrangement: Arrangement) -> ReduceResult {
+    let selection = state.selection().clone();
+    layout::arrange(state, &selection, arrangement);
+    layout_changed(state)
+}
+
+fn set_node_size(state: &mut State, node_id: String, size: [f32; 2]) -> ReduceResult {
+    if let Some(node) = state.nodes_mut().iter_mut().find(|n| n.id() == node_id) {
+        node.size = size;
+    }
     ModelUnchanged
 }
 
+fn auto_layout(state: &mut State, root_node_id: Option<String>) -> ReduceResult {
+    match root_node_id {
+        Some(root_node_id) => {
+            if !state.nodes().iter().any(|n| n.id() == root_node_id) {
+                return ModelUnchanged;
+            }
+            layout::arrange_downstream(state, &root_node_id);
+        }
+        None => layout::arrange_all(state),
+    }
+    layout_changed(state)
+}
+
 fn set_triggered_pin(state: &mut State, pin_address: PinAddress) -> ReduceResult {
     let newly_triggered_pin = pin_address;
 
@@ -120,6 +407,7 @@ fn set_triggered_pin(state: &mut State, pin_address: PinAddress) -> ReduceResult
         match state.add_patch(previously_triggered_pin, newly_triggered_pin) {
             Ok(stored_patch) => {
                 state.set_triggered_patch(Some(stored_patch));
+                state.update_variadic_pins();
                 ModelChanged
             }
             Err(_) => ModelUnchanged,
@@ -145,7 +433,35 @@ fn reset_triggered_patch(state: &mut State) -> ReduceResult {
     ModelUnchanged
 }
 
-fn set_value(state: &mut State, node_id: String, key: String, value: Value) -> ReduceResult {
+fn set_drop_target_patch(state: &mut State, patch: Option<Patch>) -> ReduceResult {
+    state.set_drop_target_patch(patch);
+    ModelUnchanged
+}
+
+fn insert_node_into_patch(state: &mut State, node_id: String, patch: Patch) -> ReduceResult {
+    state.set_drop_target_patch(None);
+
+    if !state.nodes().iter().any(|n| n.id() == node_id) {
+        return ModelUnchanged;
+    }
+
+    match state.insert_node_into_patch(&node_id, &patch) {
+        Ok(()) => {
+            if matches!(state.triggered_patch(), Some(p) if *p == patch) {
+                state.set_triggered_patch(None);
+            }
+            state.update_variadic_pins();
+            ModelChanged
+        }
+        Err(_) => ModelUnchanged,
+    }
+}
+
+fn set_node_label(state: &mut State, node_id: String, label: String) -> ReduceResult {
+    if matches!(state.renamed_node(), Some(r) if r.node_id == node_id) {
+        state.set_renamed_node(None);
+    }
+
     let node = if let Some(node) = find_node(state, &node_id) {
         node
     } else {
@@ -153,579 +469,3081 @@ fn set_value(state: &mut State, node_id: String, key: String, value: Value) -> R
         return ModelUnchanged;
     };
 
-    match find_widget(node, &key) {
-        Widget::Button(button) => {
-            let value =
-                value.expect_bool("Given widget is a Button and accepts only values of type bool");
-            if button.active() != value {
-                button.set_active(value);
-                ModelChanged
-            } else {
-                ModelUnchanged
-            }
-        }
-        Widget::DropDown(drop_down) => {
-            let value = value
-                .expect_string("Given widget is a DropDown and accepts only values of type String");
-            if drop_down.value() != &value {
-                drop_down.set_value(value);
-                ModelChanged
-            } else {
-                ModelUnchanged
-            }
-        }
-        Widget::TextBox(text_box) => {
-            let value = value
-                .expect_string("Given widget is a TextBox and accepts only values of type String");
-            if text_box.content() != value {
-                text_box.set_content(value);
-                ModelChanged
-            } else {
-                ModelUnchanged
-            }
-        }
-        Widget::Slider(slider) => {
-            let value =
-                value.expect_f32("Given widget is a Slider and accepts only values of type f32");
-            slider.set_value(value);
-            ModelChanged
-        }
-        Widget::Canvas(canvas) => {
-            let value = value.expect_vec_f32_f32(
-                "Given widget is a Canvas and accepts only values of type Vec<(f32, f32)>",
-            );
-            if canvas.dots() != &value {
-                canvas.set_dots(value);
-                ModelChanged
-            } else {
-                ModelUnchanged
-            }
-        }
+    if node.label() != label {
+        node.set_label(label);
+        ModelChanged
+    } else {
+        ModelUnchanged
     }
 }
 
-fn find_widget<'a>(node: &'a mut Node, widget_key: &'_ str) -> &'a mut Widget {
-    node.widgets_mut()
-        .iter_mut()
-        .find(|w| w.key() == widget_key)
-        .expect("widget_key must match an existing widget")
+fn set_bypass(state: &mut State, node_id: String, bypassed: bool) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        return ModelUnchanged;
+    };
+
+    if node.bypassed != bypassed {
+        node.bypassed = bypassed;
+        ModelChanged
+    } else {
+        ModelUnchanged
+    }
 }
 
-fn find_node<'a>(state: &'a mut State, node_id: &'_ str) -> Option<&'a mut Node> {
-    state.nodes_mut().iter_mut().find(|n| n.id() == node_id)
+fn start_node_rename(state: &mut State, node_id: String) -> ReduceResult {
+    let label = if let Some(node) = find_node(state, &node_id) {
+        node.label().to_owned()
+    } else {
+        return ModelUnchanged;
+    };
+
+    state.set_renamed_node(Some(NodeRename::new(node_id, label)));
+    ModelUnchanged
 }
 
-fn open_file_dialog(state: &mut State, mode: FileDialogMode) -> ReduceResult {
-    state.file_dialog.buffer = state
-        .file_dialog
-        .recent_file
-        .as_ref()
-        .map(|s| s.to_owned())
-        .unwrap_or_else(|| dirs::home_dir().unwrap().to_str().unwrap().to_owned());
-    state.file_dialog.mode = mode;
-    state.file_dialog.result = Ok(());
+fn set_node_rename_buffer(state: &mut State, value: String) -> ReduceResult {
+    if let Some(renamed_node) = state.renamed_node() {
+        let node_id = renamed_node.node_id.clone();
+        state.set_renamed_node(Some(NodeRename::new(node_id, value)));
+    }
     ModelUnchanged
 }
 
-fn set_file_dialog_buffer(state: &mut State, value: String) -> ReduceResult {
-    state.file_dialog.buffer = value;
+fn cancel_node_rename(state: &mut State) -> ReduceResult {
+    state.set_renamed_node(None);
     ModelUnchanged
 }
 
-fn load_file(state: &mut State, path: String) -> ReduceResult {
-    let snapshot_raw = match fs::read_to_string(path.clone()) {
-        Ok(content) => content,
-        Err(err) => {
-            state.file_dialog.result = Err(format!("{}", err));
-            return ModelUnchanged;
-        }
+fn start_slider_text_input(state: &mut State, node_id: String, key: String) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        return ModelUnchanged;
     };
 
-    let snapshot = match serde_json::from_str(&snapshot_raw) {
-        Ok(snapshot) => snapshot,
-        Err(err) => {
-            state.file_dialog.result = Err(format!("{}", err));
-            return ModelUnchanged;
-        }
-    };
+    assert!(
+        find_widget(node, &key).is_slider(),
+        "Text input can be started only on a Slider"
+    );
 
-    match state.load_snapshot(snapshot) {
-        Ok(_) => {
-            state.file_dialog.result = Ok(());
-            state.file_dialog.recent_file = Some(path);
-            state.file_dialog.mode = FileDialogMode::Closed;
-            ModelChanged
-        }
-        Err(err) => {
-            state.file_dialog.result = Err(err);
-            ModelUnchanged
-        }
-    }
+    state.set_slider_text_input(Some(WidgetAddress::new(node_id, key)));
+    ModelUnchanged
 }
 
-fn save_file(state: &mut State, path: String) -> ReduceResult {
-    let serialized_state = serde_json::to_string_pretty(&Snapshot::from(&*state))
-        .expect("Failed serializing the state");
+fn finish_slider_text_input(state: &mut State) -> ReduceResult {
+    state.set_slider_text_input(None);
+    ModelUnchanged
+}
 
-    match fs::write(path.clone(), serialized_state) {
-        Ok(_) => {
-            state.file_dialog.result = Ok(());
-            state.file_dialog.recent_file = Some(path);
-            state.file_dialog.mode = FileDialogMode::Closed;
-        }
-        Err(err) => {
-            state.file_dialog.result = Err(format!("{}", err));
+fn add_frame(state: &mut State, position: [f32; 2]) -> ReduceResult {
+    let mut frame = Frame::new(
+        state.next_frame_id(),
+        FRAME_DEFAULT_TITLE.to_owned(),
+        position,
+        FRAME_DEFAULT_SIZE,
+        FRAME_DEFAULT_COLOR,
+    );
+    frame.parent = state.open_subgraph().clone();
+    state.add_frame(frame);
+    layout_changed(state)
+}
+
+fn move_frame(state: &mut State, frame_id: String, offset: [f32; 2]) -> ReduceResult {
+    let frame = find_frame(state, &frame_id).clone();
+
+    state
+        .nodes_mut()
+        .iter_mut()
+        .filter(|n| n.parent == frame.parent && frame.contains(n.position))
+        .for_each(|n| n.position = vec2::sum(&[n.position, offset]));
+    state
+        .subgraphs_mut()
+        .iter_mut()
+        .filter(|s| s.parent == frame.parent && frame.contains(s.position))
+        .for_each(|s| s.position = vec2::sum(&[s.position, offset]));
+
+    let frame = find_frame(state, &frame_id);
+    frame.position = vec2::sum(&[frame.position, offset]);
+
+    layout_changed(state)
+}
+
+fn resize_frame(state: &mut State, frame_id: String, size: [f32; 2]) -> ReduceResult {
+    let frame = find_frame(state, &frame_id);
+    frame.size = [
+        f32::max(size[0], FRAME_MIN_SIZE[0]),
+        f32::max(size[1], FRAME_MIN_SIZE[1]),
+    ];
+    layout_changed(state)
+}
+
+fn remove_frame(state: &mut State, frame_id: String) -> ReduceResult {
+    if !state.frames().iter().any(|f| *f.id() == frame_id) {
+        return ModelUnchanged;
+    }
+
+    state.frames_mut().retain(|f| *f.id() != frame_id);
+
+    if matches!(state.triggered_frame(), Some(id) if *id == frame_id) {
+        state.set_triggered_frame(None);
+    }
+
+    layout_changed(state)
+}
+
+fn set_frame_title(state: &mut State, frame_id: String, title: String) -> ReduceResult {
+    find_frame(state, &frame_id).set_title(title);
+    layout_changed(state)
+}
+
+fn set_frame_color(state: &mut State, frame_id: String, color: [f32; 4]) -> ReduceResult {
+    find_frame(state, &frame_id).color = color;
+    layout_changed(state)
+}
+
+fn set_triggered_frame(state: &mut State, frame_id: String) -> ReduceResult {
+    state.set_triggered_frame(Some(frame_id));
+    ModelUnchanged
+}
+
+fn reset_triggered_frame(state: &mut State) -> ReduceResult {
+    state.set_triggered_frame(None);
+    ModelUnchanged
+}
+
+fn toggle_selection(state: &mut State, id: String) -> ReduceResult {
+    if state.selection().contains(&id) {
+        state.selection_mut().retain(|i| *i != id);
+    } else {
+        state.selection_mut().push(id);
+    }
+    ModelUnchanged
+}
+
+fn reset_selection(state: &mut State) -> ReduceResult {
+    state.set_selection(Vec::new());
+    ModelUnchanged
+}
+
+fn start_selection_rectangle(state: &mut State, position: [f32; 2]) -> ReduceResult {
+    state.set_selection_rectangle(Some([position, position]));
+    ModelUnchanged
+}
+
+fn update_selection_rectangle(state: &mut State, position: [f32; 2]) -> ReduceResult {
+    if let Some([origin, _]) = *state.selection_rectangle() {
+        state.set_selection_rectangle(Some([origin, position]));
+    }
+    ModelUnchanged
+}
+
+fn finish_selection_rectangle(state: &mut State) -> ReduceResult {
+    let [a, b] = if let Some(rectangle) = *state.selection_rectangle() {
+        rectangle
+    } else {
+        return ModelUnchanged;
+    };
+    state.set_selection_rectangle(None);
+
+    let min = [f32::min(a[0], b[0]), f32::min(a[1], b[1])];
+    let max = [f32::max(a[0], b[0]), f32::max(a[1], b[1])];
+    let is_inside = |position: [f32; 2]| {
+        position[0] >= min[0]
+            && position[1] >= min[1]
+            && position[0] <= max[0]
+            && position[1] <= max[1]
+    };
+
+    let level = state.open_subgraph().clone();
+    let selection = state
+        .nodes()
+        .iter()
+        .filter(|n| n.parent == level && is_inside(n.position))
+        .map(|n| n.id().to_owned())
+        .chain(
+            state
+                .subgraphs()
+                .iter()
+                .filter(|s| s.parent == level && is_inside(s.position))
+                .map(|s| s.id().to_owned()),
+        )
+        .collect();
+    state.set_selection(selection);
+
+    ModelUnchanged
+}
+
+fn group_selection(state: &mut State) -> ReduceResult {
+    if state.selection().is_empty() {
+        return ModelUnchanged;
+    }
+
+    let selection = state.selection().clone();
+    state.set_selection(Vec::new());
+
+    if state
+        .group_into_subgraph(&selection, SUBGRAPH_DEFAULT_LABEL.to_owned())
+        .is_some()
+    {
+        ModelChanged
+    } else {
+        ModelUnchanged
+    }
+}
+
+fn ungroup_subgraph(state: &mut State, subgraph_id: String) -> ReduceResult {
+    if !state.subgraphs().iter().any(|s| s.id() == subgraph_id) {
+        return ModelUnchanged;
+    }
+
+    if matches!(state.open_subgraph(), Some(id) if *id == subgraph_id) {
+        state.set_open_subgraph(state.parent_of(&subgraph_id).map(str::to_owned));
+    }
+    state.ungroup_subgraph(&subgraph_id);
+    state.selection_mut().retain(|id| *id != subgraph_id);
+
+    ModelChanged
+}
+
+fn open_subgraph(state: &mut State, subgraph_id: Option<String>) -> ReduceResult {
+    state.set_open_subgraph(subgraph_id);
+    state.set_triggered_node(None);
+    state.set_triggered_pin(None);
+    state.set_triggered_patch(None);
+    state.set_triggered_frame(None);
+    state.set_renamed_node(None);
+    state.set_selection(Vec::new());
+    state.set_selection_rectangle(None);
+    ModelUnchanged
+}
+
+fn move_subgraph(state: &mut State, subgraph_id: String, offset: [f32; 2]) -> ReduceResult {
+    let subgraph = state
+        .subgraphs_mut()
+        .iter_mut()
+        .find(|s| s.id() == subgraph_id)
+        .expect("subgraph_id must match an existing subgraph");
+    subgraph.position = vec2::sum(&[subgraph.position, offset]);
+    ModelUnchanged
+}
+
+fn find_frame<'a>(state: &'a mut State, frame_id: &'_ str) -> &'a mut Frame {
+    state
+        .frames_mut()
+        .iter_mut()
+        .find(|f| f.id() == frame_id)
+        .expect("frame_id must match an existing frame")
+}
+
+fn add_waypoint(state: &mut State, patch: Patch, index: usize, position: [f32; 2]) -> ReduceResult {
+    if !state.patches().contains(&patch) {
+        return ModelUnchanged;
+    }
+
+    let waypoints = state.waypoints_mut().entry(patch).or_default();
+    waypoints.insert(usize::min(index, waypoints.len()), position);
+
+    ModelUnchanged
+}
+
+fn move_waypoint(state: &mut State, patch: Patch, index: usize, offset: [f32; 2]) -> ReduceResult {
+    // In case the waypoint was removed since the action was sent, gracefuly ignore.
+    if let Some(waypoint) = state
+        .waypoints_mut()
+        .get_mut(&patch)
+        .and_then(|w| w.get_mut(index))
+    {
+        *waypoint = vec2::sum(&[*waypoint, offset]);
+    }
+    ModelUnchanged
+}
+
+fn remove_waypoint(state: &mut State, patch: Patch, index: usize) -> ReduceResult {
+    if let Some(waypoints) = state.waypoints_mut().get_mut(&patch) {
+        if index < waypoints.len() {
+            waypoints.remove(index);
+        }
+        if waypoints.is_empty() {
+            state.waypoints_mut().remove(&patch);
+        }
+    }
+    ModelUnchanged
+}
+
+fn set_value(state: &mut State, node_id: String, key: String, value: Value) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        // In case the noded was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+
+    match find_widget(node, &key) {
+        Widget::Button(button) => {
+            let value =
+                value.expect_bool("Given widget is a Button and accepts only values of type bool");
+            if button.active() != value {
+                button.set_active(value);
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::DropDown(drop_down) => {
+            let value = value
+                .expect_string("Given widget is a DropDown and accepts only values of type String");
+            if drop_down.value() != &value {
+                drop_down.set_value(value);
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::TextBox(text_box) => {
+            let value = value
+                .expect_string("Given widget is a TextBox and accepts only values of type String");
+            if text_box.content() != value {
+                text_box.set_content(value);
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Slider(slider) => {
+            let value =
+                value.expect_f32("Given widget is a Slider and accepts only values of type f32");
+            slider.set_value(value);
+            ModelChanged
+        }
+        Widget::RangeSlider(range_slider) => {
+            let value = value.expect_f32_f32(
+                "Given widget is a RangeSlider and accepts only values of type (f32, f32)",
+            );
+            let original_value = range_slider.value();
+            range_slider.set_value(value);
+            if range_slider.value() != original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::XYPad(xy_pad) => {
+            let value = value
+                .expect_point("Given widget is an XYPad and accepts only values of type Point");
+            let original_value = xy_pad.value();
+            xy_pad.set_value(value);
+            if xy_pad.value() != original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Envelope(envelope) => {
+            let original_value = envelope.points_with_curvatures();
+            match value {
+                Value::VecF32F32(points) => envelope.set_points(points),
+                Value::VecF32F32F32(points) if envelope.curved() => {
+                    envelope.set_points_with_curvatures(points)
+                }
+                _ => panic!(
+                    "Given widget is an Envelope and accepts only values of type Vec<(f32, f32)>, or Vec<(f32, f32, f32)> if it is curved"
+                ),
+            }
+            if envelope.points_with_curvatures() != original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Knob(knob) => {
+            let value =
+                value.expect_f32("Given widget is a Knob and accepts only values of type f32");
+            let original_value = knob.value();
+            knob.set_value(value);
+            if (knob.value() - original_value).abs() > f32::EPSILON {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::NumberInput(number_input) => {
+            let value = if number_input.value().is_i32() {
+                Number::I32(value.expect_i32(
+                    "Given widget is an integer NumberInput and accepts only values of type i32",
+                ))
+            } else {
+                Number::F32(value.expect_f32(
+                    "Given widget is a NumberInput and accepts only values of type f32",
+                ))
+            };
+            let original_value = number_input.value();
+            number_input.set_value(value);
+            if number_input.value() != original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Plot(plot) => {
+            let samples = match value {
+                Value::VecF32(samples) => vec![samples],
+                Value::VecVecF32(samples) => samples,
+                _ => panic!(
+                    "Given widget is a Plot and accepts only values of type Vec<f32> or Vec<Vec<f32>>"
+                ),
+            };
+            if plot.samples() != &samples {
+                plot.set_samples(samples);
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Image(image) => {
+            let value = value.expect_rgba8(
+                "Given widget is an Image and accepts only values of type Rgba8",
+            );
+            if image.data().as_ref() != Some(&value) {
+                image.set_data(value);
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        Widget::Canvas(canvas) => match value {
+            Value::VecF32F32(dots) if canvas.dots() != &dots => {
+                canvas.set_dots(dots);
+                ModelChanged
+            }
+            Value::Shapes(shapes) if canvas.shapes() != &shapes => {
+                canvas.set_shapes(shapes);
+                ModelChanged
+            }
+            Value::VecF32F32(_) | Value::Shapes(_) => ModelUnchanged,
+            _ => panic!(
+                "Given widget is a Canvas and accepts only values of type Vec<(f32, f32)> or Vec<Shape>"
+            ),
+        },
+    }
+}
+
+fn canvas_input(
+    state: &mut State,
+    node_id: String,
+    key: String,
+    position: [f32; 2],
+    event: CanvasEvent,
+) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        return ModelUnchanged;
+    };
+
+    assert!(
+        matches!(find_widget(node, &key), Widget::Canvas(canvas) if canvas.interactive()),
+        "Input can be received only by an interactive Canvas"
+    );
+
+    state.set_canvas_input(Some(CanvasInput::new(
+        WidgetAddress::new(node_id, key),
+        position,
+        event,
+    )));
+    ModelChanged
+}
+
+fn update_widget(
+    state: &mut State,
+    node_id: String,
+    key: String,
+    update: WidgetUpdate,
+) -> ReduceResult {
+    let node = if let Some(node) = find_node(state, &node_id) {
+        node
+    } else {
+        // In case the noded was removed since the action was sent, gracefuly ignore.
+        return ModelUnchanged;
+    };
+
+    let widget = if let Some(widget) = node.widgets_mut().iter_mut().find(|w| w.key() == key) {
+        widget
+    } else {
+        return ModelUnchanged;
+    };
+
+    // Updates come from the backend at runtime, invalid ones are ignored
+    // instead of bringing the whole UI down.
+    match (widget, update) {
+        (Widget::Slider(slider), WidgetUpdate::SliderRange { min, max }) => {
+            if !slider.accepts_range(min, max) {
+                return ModelUnchanged;
+            }
+            let original_value = slider.value();
+            slider.set_range(min, max);
+            if slider.value() != original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        (Widget::Slider(slider), WidgetUpdate::SliderFormat { format }) => {
+            slider.set_display_format(format);
+            ModelUnchanged
+        }
+        (Widget::DropDown(drop_down), WidgetUpdate::DropDownItems { items }) => {
+            if items.is_empty() {
+                return ModelUnchanged;
+            }
+            let original_value = drop_down.value().to_owned();
+            drop_down.set_items(items);
+            if drop_down.value() != &original_value {
+                ModelChanged
+            } else {
+                ModelUnchanged
+            }
+        }
+        (Widget::Button(button), WidgetUpdate::Label { label }) => {
+            button.set_label(label);
+            ModelUnchanged
+        }
+        (Widget::TextBox(text_box), WidgetUpdate::ReadOnly { read_only }) => {
+            text_box.set_read_only(read_only);
+            ModelUnchanged
+        }
+        _ => ModelUnchanged,
+    }
+}
+
+fn find_widget<'a>(node: &'a mut Node, widget_key: &'_ str) -> &'a mut Widget {
+    node.widgets_mut()
+        .iter_mut()
+        .find(|w| w.key() == widget_key)
+        .expect("widget_key must match an existing widget")
+}
+
+fn find_node<'a>(state: &'a mut State, node_id: &'_ str) -> Option<&'a mut Node> {
+    state.nodes_mut().iter_mut().find(|n| n.id() == node_id)
+}
+
+fn open_file_dialog(state: &mut State, mode: FileDialogMode) -> ReduceResult {
+    state.file_dialog.buffer = state
+        .file_dialog
+        .recent_file
+        .as_ref()
+        .map(|s| s.to_owned())
+        .unwrap_or_else(|| dirs::home_dir().unwrap().to_str().unwrap().to_owned());
+    state.file_dialog.mode = mode;
+    state.file_dialog.result = Ok(());
+    ModelUnchanged
+}
+
+fn set_file_dialog_buffer(state: &mut State, value: String) -> ReduceResult {
+    state.file_dialog.buffer = value;
+    ModelUnchanged
+}
+
+fn load_file(state: &mut State, path: String) -> ReduceResult {
+    let snapshot_raw = match fs::read_to_string(path.clone()) {
+        Ok(content) => content,
+        Err(err) => {
+            state.file_dialog.result = Err(format!("{}", err));
+            return ModelUnchanged;
+        }
+    };
+
+    let snapshot = match serde_json::from_str(&snapshot_raw) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            state.file_dialog.result = Err(format!("{}", err));
+            return ModelUnchanged;
+        }
+    };
+
+    match state.load_snapshot(snapshot) {
+        Ok(_) => {
+            state.file_dialog.result = Ok(());
+            state.file_dialog.recent_file = Some(path);
+            state.file_dialog.mode = FileDialogMode::Closed;
+            ModelChanged
+        }
+        Err(err) => {
+            state.file_dialog.result = Err(err);
+            ModelUnchanged
+        }
+    }
+}
+
+fn save_file(state: &mut State, path: String) -> ReduceResult {
+    let serialized_state = serde_json::to_string_pretty(&Snapshot::from(&*state))
+        .expect("Failed serializing the state");
+
+    match fs::write(path.clone(), serialized_state) {
+        Ok(_) => {
+            state.file_dialog.result = Ok(());
+            state.file_dialog.recent_file = Some(path);
+            state.file_dialog.mode = FileDialogMode::Closed;
+        }
+        Err(err) => {
+            state.file_dialog.result = Err(format!("{}", err));
+        }
+    }
+
+    ModelUnchanged
+}
+
+fn close_file_dialog(state: &mut State) -> ReduceResult {
+    state.file_dialog.mode = FileDialogMode::Closed;
+    ModelUnchanged
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate tempfile;
+
+    use std::fs;
+
+    use super::*;
+
+    use crate::report::Report;
+    use crate::state::{
+        Button, ButtonActivationMode, Canvas, Direction, DropDown, DropDownItem, Envelope, Image,
+        ImageData, Knob, MouseButton, NumberInput, Pin, Plot, PlotSeries, RangeSlider, Shape,
+        Slider, TextBox, XYPad,
+    };
+
+    #[test]
+    fn scroll() {
+        let mut state = State::default();
+        let original_offset = state.offset;
+
+        assert!(!reduce(&mut state, Action::Scroll { offset: [1.0, 2.0] }).model_changed());
+
+        assert_eq!(state.offset[0], original_offset[0] + 1.0);
+        assert_eq!(state.offset[1], original_offset[1] + 2.0);
+    }
+
+    #[test]
+    fn set_offset() {
+        let mut state = State::default();
+        state.offset = [10.0, 10.0];
+
+        assert!(!reduce(&mut state, Action::SetOffset { offset: [1.0, 2.0] }).model_changed());
+
+        assert_eq!(state.offset, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn add_node_template() {
+        let mut state = State::default();
+
+        assert!(!reduce(
+            &mut state,
+            Action::AddNodeTemplate {
+                node_template: NodeTemplate::new(
+                    "Label".to_owned(),
+                    "class".to_owned(),
+                    true,
+                    vec![],
+                    vec![],
+                ),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.node_templates()[0].class(), "class");
+    }
+
+    #[test]
+    fn ignore_added_node_template_with_duplicated_class() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label 1".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+
+        assert!(!reduce(
+            &mut state,
+            Action::AddNodeTemplate {
+                node_template: NodeTemplate::new(
+                    "Label 2".to_owned(),
+                    "class".to_owned(),
+                    true,
+                    vec![],
+                    vec![],
+                ),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.node_templates().len(), 1);
+        assert_eq!(state.node_templates()[0].label(), "Label 1");
+    }
+
+    #[test]
+    fn remove_node_template_with_its_instances() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "removed".to_owned(),
+            true,
+            vec![Pin::new(
+                "Output".to_owned(),
+                "out".to_owned(),
+                Direction::Output,
+            )],
+            vec![],
+        ));
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "kept".to_owned(),
+            true,
+            vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[1].instantiate([0.0, 0.0]));
+        state
+            .add_patch(
+                PinAddress::new("removed:0".to_owned(), "out".to_owned()),
+                PinAddress::new("kept:0".to_owned(), "in".to_owned()),
+            )
+            .unwrap();
+        state.set_triggered_node(Some("removed:0".to_owned()));
+
+        assert!(reduce(
+            &mut state,
+            Action::RemoveNodeTemplate {
+                class: "removed".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.node_templates().len(), 1);
+        assert_eq!(state.node_templates()[0].class(), "kept");
+        assert_eq!(state.nodes().len(), 1);
+        assert_eq!(state.nodes()[0].id(), "kept:0");
+        assert!(state.patches().is_empty());
+        assert!(state.triggered_node().is_none());
+    }
+
+    #[test]
+    fn remove_node_template_without_instances() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+
+        assert!(!reduce(
+            &mut state,
+            Action::RemoveNodeTemplate {
+                class: "class".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert!(state.node_templates().is_empty());
+    }
+
+    #[test]
+    fn add_node() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+
+        assert!(reduce(
+            &mut state,
+            Action::AddNode {
+                class: "class".to_owned(),
+                position: [100.0, 200.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes()[0].class(), "class");
+        assert_eq!(state.nodes()[0].position, [100.0, 200.0]);
+        assert!(state.triggered_node().is_some());
+        assert_eq!(state.triggered_node().as_ref().unwrap(), "class:0");
+    }
+
+    #[test]
+    fn remove_node() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state
+            .add_patch(
+                PinAddress::new("class:0".to_owned(), "out".to_owned()),
+                PinAddress::new("class:1".to_owned(), "in".to_owned()),
+            )
+            .unwrap();
+
+        assert!(reduce(
+            &mut state,
+            Action::RemoveNode {
+                node_id: "class:1".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes().len(), 1);
+        assert!(state.patches().is_empty());
+    }
+
+    #[test]
+    fn move_node() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        let original_position = state.nodes()[0].position;
+
+        assert!(!reduce(
+            &mut state,
+            Action::MoveNode {
+                node_id: "class:0".to_owned(),
+                offset: [100.0, 200.0],
+            },
+        )
+        .model_changed());
+
+        let updated_position1 = state.nodes()[0].position;
+        assert_eq!(
+            updated_position1,
+            vec2::sum(&[original_position, [100.0, 200.0]])
+        );
+
+        assert!(!reduce(
+            &mut state,
+            Action::MoveNode {
+                node_id: "class:0".to_owned(),
+                offset: [10.0, -10.0],
+            },
+        )
+        .model_changed());
+
+        let updated_position2 = state.nodes()[0].position;
+        assert_eq!(
+            updated_position2,
+            vec2::sum(&[updated_position1, [10.0, -10.0]])
+        );
+    }
+
+    #[test]
+    fn set_node_label() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(reduce(
+            &mut state,
+            Action::SetNodeLabel {
+                node_id: "class:0".to_owned(),
+                label: "Custom".to_owned(),
+            },
+        )
+        .model_changed());
+        assert_eq!(state.nodes()[0].label(), "Custom");
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetNodeLabel {
+                node_id: "class:0".to_owned(),
+                label: "Custom".to_owned(),
+            },
+        )
+        .model_changed());
+    }
+
+    #[test]
+    fn rename_node() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::StartNodeRename {
+                node_id: "class:0".to_owned(),
+            },
+        )
+        .model_changed());
+        assert_eq!(
+            state.renamed_node().as_ref().unwrap().buffer,
+            "Label".to_owned()
+        );
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetNodeRenameBuffer {
+                value: "Custom".to_owned(),
+            },
+        )
+        .model_changed());
+        assert_eq!(
+            state.renamed_node().as_ref().unwrap().buffer,
+            "Custom".to_owned()
+        );
+        assert_eq!(state.nodes()[0].label(), "Label");
+
+        assert!(reduce(
+            &mut state,
+            Action::SetNodeLabel {
+                node_id: "class:0".to_owned(),
+                label: "Custom".to_owned(),
+            },
+        )
+        .model_changed());
+        assert!(state.renamed_node().is_none());
+        assert_eq!(state.nodes()[0].label(), "Custom");
+    }
+
+    #[test]
+    fn cancel_node_rename() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        reduce(
+            &mut state,
+            Action::StartNodeRename {
+                node_id: "class:0".to_owned(),
+            },
+        );
+
+        assert!(!reduce(&mut state, Action::CancelNodeRename).model_changed());
+
+        assert!(state.renamed_node().is_none());
+        assert_eq!(state.nodes()[0].label(), "Label");
+    }
+
+    #[test]
+    fn slider_text_input() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Slider(Slider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                5.0,
+                "%.2f".to_owned(),
+                120.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::StartSliderTextInput {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+            },
+        )
+        .model_changed());
+        assert_eq!(
+            state.slider_text_input(),
+            &Some(WidgetAddress::new("class:0".to_owned(), "key".to_owned()))
+        );
+
+        assert!(!reduce(&mut state, Action::FinishSliderTextInput).model_changed());
+        assert!(state.slider_text_input().is_none());
+    }
+
+    #[test]
+    fn add_frame() {
+        let mut state = State::default();
+
+        assert!(!reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [10.0, 20.0],
+            },
+        )
+        .model_changed());
+        assert!(!reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [30.0, 40.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.frames().len(), 2);
+        assert_eq!(state.frames()[0].id(), "frame:0");
+        assert_eq!(state.frames()[0].position, [10.0, 20.0]);
+        assert_eq!(state.frames()[1].id(), "frame:1");
+    }
+
+    #[test]
+    fn move_frame_with_nodes_inside() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([50.0, 50.0]));
+        state.add_node(state.node_templates()[0].instantiate([500.0, 500.0]));
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+
+        assert!(!reduce(
+            &mut state,
+            Action::MoveFrame {
+                frame_id: "frame:0".to_owned(),
+                offset: [10.0, -10.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.frames()[0].position, [10.0, -10.0]);
+        assert_eq!(state.nodes()[0].position, [60.0, 40.0]);
+        assert_eq!(state.nodes()[1].position, [500.0, 500.0]);
+    }
+
+    #[test]
+    fn add_frame_into_open_subgraph() {
+        let mut state = State::default();
+        state.set_open_subgraph(Some("subgraph:0".to_owned()));
+
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [10.0, 20.0],
+            },
+        );
+
+        assert_eq!(state.frames()[0].parent, Some("subgraph:0".to_owned()));
+    }
+
+    #[test]
+    fn move_frame_only_with_nodes_on_its_level() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([50.0, 50.0]));
+        state.add_node(state.node_templates()[0].instantiate([60.0, 60.0]));
+        state.group_into_subgraph(&["class:1".to_owned()], "Label".to_owned());
+        state.subgraphs_mut()[0].position = [500.0, 500.0];
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+
+        reduce(
+            &mut state,
+            Action::MoveFrame {
+                frame_id: "frame:0".to_owned(),
+                offset: [10.0, -10.0],
+            },
+        );
+
+        assert_eq!(state.nodes()[0].position, [60.0, 40.0]);
+        assert_eq!(state.nodes()[1].position, [60.0, 60.0]);
+        assert_eq!(state.subgraphs()[0].position, [500.0, 500.0]);
+    }
+
+    #[test]
+    fn move_frame_inside_subgraph() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([50.0, 50.0]));
+        state.add_node(state.node_templates()[0].instantiate([60.0, 60.0]));
+        state.group_into_subgraph(&["class:1".to_owned()], "Label".to_owned());
+        reduce(
+            &mut state,
+            Action::OpenSubgraph {
+                subgraph_id: Some("subgraph:0".to_owned()),
+            },
+        );
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+
+        reduce(
+            &mut state,
+            Action::MoveFrame {
+                frame_id: "frame:0".to_owned(),
+                offset: [10.0, -10.0],
+            },
+        );
+
+        assert_eq!(state.nodes()[0].position, [50.0, 50.0]);
+        assert_eq!(state.nodes()[1].position, [70.0, 50.0]);
+    }
+
+    #[test]
+    fn resize_frame_keeping_minimal_size() {
+        let mut state = State::default();
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+
+        reduce(
+            &mut state,
+            Action::ResizeFrame {
+                frame_id: "frame:0".to_owned(),
+                size: [400.0, 300.0],
+            },
+        );
+        assert_eq!(state.frames()[0].size, [400.0, 300.0]);
+
+        reduce(
+            &mut state,
+            Action::ResizeFrame {
+                frame_id: "frame:0".to_owned(),
+                size: [0.0, 0.0],
+            },
+        );
+        assert_eq!(state.frames()[0].size, FRAME_MIN_SIZE);
+    }
+
+    #[test]
+    fn edit_frame() {
+        let mut state = State::default();
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+
+        reduce(
+            &mut state,
+            Action::SetFrameTitle {
+                frame_id: "frame:0".to_owned(),
+                title: "Title".to_owned(),
+            },
+        );
+        reduce(
+            &mut state,
+            Action::SetFrameColor {
+                frame_id: "frame:0".to_owned(),
+                color: [1.0, 0.0, 0.0, 1.0],
+            },
+        );
+
+        assert_eq!(state.frames()[0].title(), "Title");
+        assert_eq!(state.frames()[0].color, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn remove_triggered_frame() {
+        let mut state = State::default();
+        reduce(
+            &mut state,
+            Action::AddFrame {
+                position: [0.0, 0.0],
+            },
+        );
+        reduce(
+            &mut state,
+            Action::SetTriggeredFrame {
+                frame_id: "frame:0".to_owned(),
+            },
+        );
+
+        assert!(!reduce(
+            &mut state,
+            Action::RemoveFrame {
+                frame_id: "frame:0".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert!(state.frames().is_empty());
+        assert!(state.triggered_frame().is_none());
+    }
+
+    fn initialize_state_with_nodes(count: usize) -> State {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        for i in 0..count {
+            state.add_node(state.node_templates()[0].instantiate([i as f32 * 100.0, 0.0]));
+        }
+        state
+    }
+
+    #[test]
+    fn toggle_selection() {
+        let mut state = initialize_state_with_nodes(2);
+
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:0".to_owned(),
+            },
+        );
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:1".to_owned(),
+            },
+        );
+        assert_eq!(
+            *state.selection(),
+            vec!["class:0".to_owned(), "class:1".to_owned()]
+        );
+
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:0".to_owned(),
+            },
+        );
+        assert_eq!(*state.selection(), vec!["class:1".to_owned()]);
+
+        reduce(&mut state, Action::ResetSelection);
+        assert!(state.selection().is_empty());
+    }
+
+    #[test]
+    fn select_nodes_with_rectangle() {
+        let mut state = initialize_state_with_nodes(3);
+
+        reduce(
+            &mut state,
+            Action::StartSelectionRectangle {
+                position: [150.0, 10.0],
+            },
+        );
+        reduce(
+            &mut state,
+            Action::UpdateSelectionRectangle {
+                position: [-10.0, -10.0],
+            },
+        );
+        reduce(&mut state, Action::FinishSelectionRectangle);
+
+        assert_eq!(
+            *state.selection(),
+            vec!["class:0".to_owned(), "class:1".to_owned()]
+        );
+        assert!(state.selection_rectangle().is_none());
+    }
+
+    #[test]
+    fn group_selection_into_subgraph_and_ungroup_it() {
+        let mut state = initialize_state_with_nodes(2);
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:1".to_owned(),
+            },
+        );
+
+        assert!(reduce(&mut state, Action::GroupSelection).model_changed());
+        assert_eq!(state.subgraphs().len(), 1);
+        assert_eq!(state.nodes()[1].parent, Some("subgraph:0".to_owned()));
+        assert!(state.selection().is_empty());
+
+        reduce(
+            &mut state,
+            Action::OpenSubgraph {
+                subgraph_id: Some("subgraph:0".to_owned()),
+            },
+        );
+
+        assert!(reduce(
+            &mut state,
+            Action::UngroupSubgraph {
+                subgraph_id: "subgraph:0".to_owned(),
+            },
+        )
+        .model_changed());
+        assert!(state.subgraphs().is_empty());
+        assert_eq!(state.nodes()[1].parent, None);
+        assert!(state.open_subgraph().is_none());
+    }
+
+    #[test]
+    fn remove_subgraph_with_its_last_node() {
+        let mut state = initialize_state_with_nodes(2);
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:1".to_owned(),
+            },
+        );
+        reduce(&mut state, Action::GroupSelection);
+
+        reduce(
+            &mut state,
+            Action::RemoveNode {
+                node_id: "class:1".to_owned(),
+            },
+        );
+
+        assert!(state.subgraphs().is_empty());
+    }
+
+    #[test]
+    fn ignore_grouping_of_removed_nodes() {
+        let mut state = initialize_state_with_nodes(2);
+        reduce(
+            &mut state,
+            Action::ToggleSelection {
+                id: "class:1".to_owned(),
+            },
+        );
+        state.nodes_mut().retain(|n| n.id() != "class:1");
+
+        assert!(!reduce(&mut state, Action::GroupSelection).model_changed());
+        assert!(state.subgraphs().is_empty());
+        assert!(state.selection().is_empty());
+    }
+
+    #[test]
+    fn ignore_grouping_of_empty_selection() {
+        let mut state = initialize_state_with_nodes(1);
+
+        assert!(!reduce(&mut state, Action::GroupSelection).model_changed());
+        assert!(state.subgraphs().is_empty());
+    }
+
+    #[test]
+    fn open_subgraph_resetting_triggered_items() {
+        let mut state = initialize_state_with_nodes(1);
+        reduce(
+            &mut state,
+            Action::SetTriggeredNode {
+                node_id: "class:0".to_owned(),
+            },
+        );
+
+        reduce(
+            &mut state,
+            Action::OpenSubgraph {
+                subgraph_id: Some("subgraph:0".to_owned()),
+            },
+        );
+
+        assert_eq!(*state.open_subgraph(), Some("subgraph:0".to_owned()));
+        assert!(state.triggered_node().is_none());
+    }
+
+    #[test]
+    fn trigger_node() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetTriggeredNode {
+                node_id: "class:0".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert!(state.triggered_node().is_some());
+        assert_eq!(state.triggered_node().as_ref().unwrap(), "class:0");
+
+        assert!(!reduce(&mut state, Action::ResetTriggeredNode).model_changed());
+
+        assert!(state.triggered_node().is_none());
+    }
+
+    #[test]
+    fn move_triggered_node_forward() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        assert_eq!(state.nodes()[0].id(), "class:0");
+        assert_eq!(state.nodes()[1].id(), "class:1");
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetTriggeredNode {
+                node_id: "class:0".to_owned(),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes()[0].id(), "class:1");
+        assert_eq!(state.nodes()[1].id(), "class:0");
+    }
+
+    #[test]
+    fn trigger_patch() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state
+            .add_patch(
+                PinAddress::new("class:0".to_owned(), "out".to_owned()),
+                PinAddress::new("class:1".to_owned(), "in".to_owned()),
+            )
+            .unwrap();
+
+        let patch = Patch::new(
+            PinAddress::new("class:0".to_owned(), "out".to_owned()),
+            PinAddress::new("class:1".to_owned(), "in".to_owned()),
+        );
+        assert!(!reduce(
+            &mut state,
+            Action::SetTriggeredPatch {
+                patch: patch.clone(),
+            },
+        )
+        .model_changed());
+
+        assert!(state.triggered_patch().is_some());
+        assert_eq!(state.triggered_patch().as_ref().unwrap(), &patch);
+
+        assert!(!reduce(&mut state, Action::ResetTriggeredPatch).model_changed());
+
+        assert!(state.triggered_patch().is_none());
+    }
+
+    #[test]
+    fn trigger_pin() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetTriggeredPin {
+                pin_address: PinAddress::new("class:0".to_owned(), "in".to_owned()),
+            },
+        )
+        .model_changed());
+
+        assert!(state.triggered_pin().is_some());
+        assert_eq!(state.triggered_pin().as_ref().unwrap().node_id(), "class:0");
+        assert_eq!(state.triggered_pin().as_ref().unwrap().pin_class(), "in");
+
+        assert!(!reduce(&mut state, Action::ResetTriggeredPin).model_changed());
+
+        assert!(state.triggered_pin().is_none());
+    }
+
+    #[test]
+    fn create_patch_when_second_pin_is_triggered() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetTriggeredPin {
+                pin_address: PinAddress::new("class:0".to_owned(), "out".to_owned()),
+            },
+        )
+        .model_changed());
+
+        assert!(reduce(
+            &mut state,
+            Action::SetTriggeredPin {
+                pin_address: PinAddress::new("class:1".to_owned(), "in".to_owned()),
+            },
+        )
+        .model_changed());
+
+        let patch = state.patches().iter().next().unwrap();
+        assert_eq!(
+            *patch,
+            Patch::new(
+                PinAddress::new("class:0".to_owned(), "out".to_owned()),
+                PinAddress::new("class:1".to_owned(), "in".to_owned())
+            )
+        );
+
+        assert!(state.triggered_pin().is_none());
+    }
+
+    #[test]
+    fn remove_patch() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state
+            .add_patch(
+                PinAddress::new("class:0".to_owned(), "out".to_owned()),
+                PinAddress::new("class:1".to_owned(), "in".to_owned()),
+            )
+            .unwrap();
+
+        assert!(reduce(
+            &mut state,
+            Action::RemovePatch {
+                patch: Patch::new(
+                    PinAddress::new("class:0".to_owned(), "out".to_owned()),
+                    PinAddress::new("class:1".to_owned(), "in".to_owned()),
+                ),
+            },
+        )
+        .model_changed());
+
+        assert!(state.patches().is_empty());
+    }
+
+    fn initialize_state_with_patch() -> (State, Patch) {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![
+                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+            ],
+            vec![],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        let patch = Patch::new(
+            PinAddress::new("class:0".to_owned(), "out".to_owned()),
+            PinAddress::new("class:1".to_owned(), "in".to_owned()),
+        );
+        state
+            .add_patch(patch.source().clone(), patch.destination().clone())
+            .unwrap();
+        (state, patch)
+    }
+
+    #[test]
+    fn add_waypoint() {
+        let (mut state, patch) = initialize_state_with_patch();
+
+        reduce(
+            &mut state,
+            Action::AddWaypoint {
+                patch: patch.clone(),
+                index: 0,
+                position: [10.0, 10.0],
+            },
+        );
+        assert!(!reduce(
+            &mut state,
+            Action::AddWaypoint {
+                patch: patch.clone(),
+                index: 0,
+                position: [5.0, 5.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.waypoints()[&patch], vec![[5.0, 5.0], [10.0, 10.0]]);
+    }
+
+    #[test]
+    fn move_waypoint() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state
+            .waypoints_mut()
+            .insert(patch.clone(), vec![[10.0, 10.0]]);
+
+        reduce(
+            &mut state,
+            Action::MoveWaypoint {
+                patch: patch.clone(),
+                index: 0,
+                offset: [1.0, 2.0],
+            },
+        );
+
+        assert_eq!(state.waypoints()[&patch], vec![[11.0, 12.0]]);
+    }
+
+    #[test]
+    fn ignore_move_of_removed_waypoint() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state
+            .waypoints_mut()
+            .insert(patch.clone(), vec![[10.0, 10.0]]);
+
+        reduce(
+            &mut state,
+            Action::MoveWaypoint {
+                patch: patch.clone(),
+                index: 1,
+                offset: [1.0, 2.0],
+            },
+        );
+
+        assert_eq!(state.waypoints()[&patch], vec![[10.0, 10.0]]);
+    }
+
+    #[test]
+    fn remove_waypoint() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state
+            .waypoints_mut()
+            .insert(patch.clone(), vec![[10.0, 10.0], [20.0, 20.0]]);
+
+        reduce(
+            &mut state,
+            Action::RemoveWaypoint {
+                patch: patch.clone(),
+                index: 0,
+            },
+        );
+        assert_eq!(state.waypoints()[&patch], vec![[20.0, 20.0]]);
+
+        reduce(
+            &mut state,
+            Action::RemoveWaypoint {
+                patch: patch.clone(),
+                index: 0,
+            },
+        );
+        assert!(state.waypoints().is_empty());
+    }
+
+    #[test]
+    fn remove_patch_with_waypoints() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state
+            .waypoints_mut()
+            .insert(patch.clone(), vec![[10.0, 10.0]]);
+
+        reduce(&mut state, Action::RemovePatch { patch });
+
+        assert!(state.waypoints().is_empty());
+    }
+
+    #[test]
+    fn insert_node_into_patch() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.set_drop_target_patch(Some(patch.clone()));
+
+        assert!(reduce(
+            &mut state,
+            Action::InsertNodeIntoPatch {
+                node_id: "class:2".to_owned(),
+                patch: patch.clone(),
+            },
+        )
+        .model_changed());
+
+        assert!(state.drop_target_patch().is_none());
+        assert!(!state.patches().contains(&patch));
+        assert_eq!(state.patches().len(), 2);
+    }
+
+    #[test]
+    fn insert_node_into_removed_patch() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.set_drop_target_patch(Some(patch.clone()));
+        reduce(
+            &mut state,
+            Action::RemovePatch {
+                patch: patch.clone(),
+            },
+        );
+
+        assert!(!reduce(
+            &mut state,
+            Action::InsertNodeIntoPatch {
+                node_id: "class:2".to_owned(),
+                patch,
+            },
+        )
+        .model_changed());
+
+        assert!(state.drop_target_patch().is_none());
+        assert!(state.patches().is_empty());
+    }
+
+    #[test]
+    fn clear_drop_target_when_drag_is_cancelled() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        reduce(
+            &mut state,
+            Action::SetTriggeredNode {
+                node_id: "class:2".to_owned(),
+            },
+        );
+        reduce(
+            &mut state,
+            Action::SetDropTargetPatch { patch: Some(patch) },
+        );
+
+        reduce(&mut state, Action::ResetTriggeredNode);
+
+        assert!(state.drop_target_patch().is_none());
+    }
+
+    #[test]
+    fn insert_node_into_patch_connected_to_it() {
+        let (mut state, patch) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::InsertNodeIntoPatch {
+                node_id: "class:0".to_owned(),
+                patch: patch.clone(),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.patches().len(), 1);
+        assert!(state.patches().contains(&patch));
+    }
+
+    #[test]
+    fn move_node_with_snap_to_grid() {
+        let (mut state, _) = initialize_state_with_patch();
+        reduce(&mut state, Action::SetSnapToGrid { snap: true });
+
+        for _ in 0..3 {
+            reduce(
+                &mut state,
+                Action::MoveNode {
+                    node_id: "class:0".to_owned(),
+                    offset: [Grid::SPACING / 4.0, 0.0],
+                },
+            );
+        }
+        assert_eq!(state.nodes()[0].position, [Grid::SPACING, 0.0]);
+
+        reduce(&mut state, Action::FinishNodeDrag);
+        assert!(state.drag_positions().is_empty());
+    }
+
+    #[test]
+    fn snap_each_dragged_node_relative_to_its_origin() {
+        let (mut state, _) = initialize_state_with_patch();
+        state.nodes_mut()[1].position = [Grid::SPACING * 5.0, 0.0];
+        reduce(&mut state, Action::SetSnapToGrid { snap: true });
+        let move_node = |state: &mut State, node_id: &str| {
+            reduce(
+                state,
+                Action::MoveNode {
+                    node_id: node_id.to_owned(),
+                    offset: [Grid::SPACING / 4.0, 0.0],
+                },
+            );
+        };
+
+        move_node(&mut state, "class:0");
+        move_node(&mut state, "class:0");
+        move_node(&mut state, "class:1");
+        move_node(&mut state, "class:0");
+
+        assert_eq!(state.nodes()[0].position, [Grid::SPACING, 0.0]);
+        assert_eq!(state.nodes()[1].position, [Grid::SPACING * 5.0, 0.0]);
+    }
+
+    #[test]
+    fn arrange_selection() {
+        let (mut state, _) = initialize_state_with_patch();
+        state.nodes_mut()[1].position = [100.0, 100.0];
+        state.set_selection(vec!["class:0".to_owned(), "class:1".to_owned()]);
+
+        reduce(
+            &mut state,
+            Action::ArrangeSelection {
+                arrangement: Arrangement::AlignTop,
+            },
+        );
+
+        assert_eq!(state.nodes()[1].position, [100.0, 0.0]);
+    }
+
+    #[test]
+    fn set_bypass() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(reduce(
+            &mut state,
+            Action::SetBypass {
+                node_id: "class:0".to_owned(),
+                bypassed: true,
+            },
+        )
+        .model_changed());
+        assert!(state.nodes()[0].bypassed);
+        assert_eq!(state.patches().len(), 1);
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetBypass {
+                node_id: "class:0".to_owned(),
+                bypassed: true,
+            },
+        )
+        .model_changed());
+    }
+
+    #[test]
+    fn resize_node() {
+        let mut state = State::default();
+        state.add_node_template(
+            NodeTemplate::new("Label".to_owned(), "class".to_owned(), true, vec![], vec![])
+                .with_resizable(true),
+        );
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(!reduce(
+            &mut state,
+            Action::ResizeNode {
+                node_id: "class:0".to_owned(),
+                size: [300.0, 200.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes()[0].custom_size, Some([300.0, 200.0]));
+    }
+
+    #[test]
+    fn ignore_resize_of_fixed_node() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::ResizeNode {
+                node_id: "class:0".to_owned(),
+                size: [300.0, 200.0],
+            },
+        )
+        .model_changed());
+
+        assert!(state.nodes()[0].custom_size.is_none());
+    }
+
+    #[test]
+    fn ignore_resize_of_removed_node() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::ResizeNode {
+                node_id: "missing:0".to_owned(),
+                size: [300.0, 200.0],
+            },
+        )
+        .model_changed());
+    }
+
+    #[test]
+    fn set_node_collapsed() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetNodeCollapsed {
+                node_id: "class:0".to_owned(),
+                collapsed: true,
+            },
+        )
+        .model_changed());
+
+        assert!(state.nodes()[0].collapsed);
+        assert!(!state.nodes()[1].collapsed);
+    }
+
+    #[test]
+    fn set_node_size() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::SetNodeSize {
+                node_id: "class:0".to_owned(),
+                size: [100.0, 50.0],
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes()[0].size, [100.0, 50.0]);
+    }
+
+    #[test]
+    fn auto_layout() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        reduce(&mut state, Action::AutoLayout { root_node_id: None });
+
+        assert_eq!(state.nodes()[0].position[1], state.nodes()[1].position[1]);
+        assert!(state.nodes()[0].position[0] < state.nodes()[1].position[0]);
+    }
+
+    #[test]
+    fn report_layout_after_auto_layout() {
+        let (mut state, _) = initialize_state_with_patch();
+        state.report_layout = true;
+
+        assert!(reduce(&mut state, Action::AutoLayout { root_node_id: None }).model_changed());
+
+        let layout = Report::from(&state).layout.unwrap();
+        assert_eq!(layout.nodes[0].position, state.nodes()[0].position);
+        assert_eq!(layout.nodes[1].position, state.nodes()[1].position);
+        assert_ne!(layout.nodes[0].position, layout.nodes[1].position);
+    }
+
+    #[test]
+    fn report_layout_after_moving_node() {
+        let (mut state, _) = initialize_state_with_patch();
+        state.report_layout = true;
+        let node_id = state.nodes()[0].id().to_owned();
+
+        assert!(reduce(
+            &mut state,
+            Action::MoveNode {
+                node_id,
+                offset: [100.0, 200.0],
+            },
+        )
+        .model_changed());
+
+        let layout = Report::from(&state).layout.unwrap();
+        assert_eq!(layout.nodes[0].position, [100.0, 200.0]);
+    }
+
+    #[test]
+    fn auto_layout_downstream_of_nonexistent_node() {
+        let (mut state, _) = initialize_state_with_patch();
+
+        assert!(!reduce(
+            &mut state,
+            Action::AutoLayout {
+                root_node_id: Some("does_not_exist".to_owned()),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(state.nodes()[0].position, state.nodes()[1].position);
+    }
+
+    #[test]
+    fn remove_node_with_waypoints() {
+        let (mut state, patch) = initialize_state_with_patch();
+        state.waypoints_mut().insert(patch, vec![[10.0, 10.0]]);
+
+        reduce(
+            &mut state,
+            Action::RemoveNode {
+                node_id: "class:0".to_owned(),
+            },
+        );
+
+        assert!(state.waypoints().is_empty());
+    }
+
+    fn initialize_state_with_variadic_pins() -> State {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "source".to_owned(),
+            true,
+            vec![Pin::new(
+                "Output".to_owned(),
+                "out".to_owned(),
+                Direction::Output,
+            )],
+            vec![],
+        ));
+        state.add_node_template(
+            NodeTemplate::new(
+                "Label".to_owned(),
+                "mixer".to_owned(),
+                true,
+                vec![Pin::new(
+                    "Output".to_owned(),
+                    "out".to_owned(),
+                    Direction::Output,
+                )],
+                vec![],
+            )
+            .with_variadic_pins(vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )]),
+        );
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state.add_node(state.node_templates()[1].instantiate([0.0, 0.0]));
+        state
+    }
+
+    fn pin_classes(state: &State, node_id: &str) -> Vec<String> {
+        state
+            .nodes()
+            .iter()
+            .find(|n| n.id() == node_id)
+            .unwrap()
+            .pins()
+            .iter()
+            .map(|p| p.class().to_string())
+            .collect()
+    }
+
+    fn connect(state: &mut State, source: PinAddress, destination: PinAddress) {
+        reduce(
+            state,
+            Action::SetTriggeredPin {
+                pin_address: source,
+            },
+        );
+        reduce(
+            state,
+            Action::SetTriggeredPin {
+                pin_address: destination,
+            },
+        );
+    }
+
+    #[test]
+    fn append_variadic_pin_when_the_last_one_is_connected() {
+        let mut state = initialize_state_with_variadic_pins();
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in1"]);
+
+        connect(
+            &mut state,
+            PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+        );
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in1", "in2"]);
+
+        connect(
+            &mut state,
+            PinAddress::new("source:1".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in2".to_owned()),
+        );
+        assert_eq!(
+            pin_classes(&state, "mixer:0"),
+            vec!["out", "in1", "in2", "in3"]
+        );
+    }
+
+    #[test]
+    fn remove_redundant_variadic_pins_when_disconnected() {
+        let mut state = initialize_state_with_variadic_pins();
+        connect(
+            &mut state,
+            PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+        );
+        connect(
+            &mut state,
+            PinAddress::new("source:1".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in2".to_owned()),
+        );
+
+        reduce(
+            &mut state,
+            Action::RemovePatch {
+                patch: Patch::new(
+                    PinAddress::new("source:0".to_owned(), "out".to_owned()),
+                    PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+                ),
+            },
+        );
+        assert_eq!(
+            pin_classes(&state, "mixer:0"),
+            vec!["out", "in1", "in2", "in3"]
+        );
+
+        reduce(
+            &mut state,
+            Action::RemoveNode {
+                node_id: "source:1".to_owned(),
+            },
+        );
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in1"]);
+    }
+
+    #[test]
+    fn number_new_variadic_pin_after_the_highest_one() {
+        let mut state = initialize_state_with_variadic_pins();
+        connect(
+            &mut state,
+            PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+        );
+        connect(
+            &mut state,
+            PinAddress::new("source:1".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in2".to_owned()),
+        );
+        reduce(
+            &mut state,
+            Action::RemovePatch {
+                patch: Patch::new(
+                    PinAddress::new("source:0".to_owned(), "out".to_owned()),
+                    PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+                ),
+            },
+        );
+        assert_eq!(
+            pin_classes(&state, "mixer:0"),
+            vec!["out", "in1", "in2", "in3"]
+        );
+
+        reduce(
+            &mut state,
+            Action::RemovePin {
+                pin_address: PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+            },
+        );
+        reduce(
+            &mut state,
+            Action::RemovePin {
+                pin_address: PinAddress::new("mixer:0".to_owned(), "in3".to_owned()),
+            },
+        );
+
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in2", "in3"]);
+    }
+
+    #[test]
+    fn add_pin() {
+        let mut state = initialize_state_with_variadic_pins();
+
+        assert!(reduce(
+            &mut state,
+            Action::AddPin {
+                node_id: "source:0".to_owned(),
+                pin: Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+            },
+        )
+        .model_changed());
+
+        assert_eq!(pin_classes(&state, "source:0"), vec!["out", "in"]);
+    }
+
+    #[test]
+    fn ignore_addition_of_pin_with_taken_class() {
+        let mut state = initialize_state_with_variadic_pins();
+
+        for class in ["out", "in1", "in5"].iter() {
+            assert!(!reduce(
+                &mut state,
+                Action::AddPin {
+                    node_id: "mixer:0".to_owned(),
+                    pin: Pin::new("Pin".to_owned(), class.to_string(), Direction::Input),
+                },
+            )
+            .model_changed());
+        }
+
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in1"]);
+    }
+
+    #[test]
+    fn remove_pin_with_its_patches() {
+        let mut state = initialize_state_with_variadic_pins();
+        connect(
+            &mut state,
+            PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            PinAddress::new("mixer:0".to_owned(), "in1".to_owned()),
+        );
+
+        assert!(reduce(
+            &mut state,
+            Action::RemovePin {
+                pin_address: PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            },
+        )
+        .model_changed());
+
+        assert!(pin_classes(&state, "source:0").is_empty());
+        assert!(state.patches().is_empty());
+        assert!(state.triggered_patch().is_none());
+        assert_eq!(pin_classes(&state, "mixer:0"), vec!["out", "in1"]);
+    }
+
+    #[test]
+    fn remove_unconnected_pin() {
+        let mut state = initialize_state_with_variadic_pins();
+
+        assert!(!reduce(
+            &mut state,
+            Action::RemovePin {
+                pin_address: PinAddress::new("source:0".to_owned(), "out".to_owned()),
+            },
+        )
+        .model_changed());
+
+        assert!(pin_classes(&state, "source:0").is_empty());
+    }
+
+    #[test]
+    fn set_text_box_content() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::TextBox(TextBox::new(
+                "key".to_owned(),
+                100,
+                [100.0, 100.0],
+                false,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::String("hello world".to_owned()),
+            },
+        )
+        .model_changed());
+
+        if let Widget::TextBox(text_box) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(text_box.content(), "hello world");
+        } else {
+            panic!("invalid widget type");
+        }
+    }
+
+    #[test]
+    fn set_button() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Button(Button::new(
+                "Button".to_owned(),
+                "key".to_owned(),
+                ButtonActivationMode::OnClick,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::Bool(true),
+            },
+        )
+        .model_changed());
+
+        if let Widget::Button(button) = &state.nodes()[0].widgets()[0] {
+            assert!(button.active());
+        } else {
+            panic!("invalid widget type");
+        }
+
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::Bool(false),
+            },
+        )
+        .model_changed());
+
+        if let Widget::Button(button) = &state.nodes()[0].widgets()[0] {
+            assert!(!button.active());
+        } else {
+            panic!("invalid widget type");
+        }
+    }
+
+    #[test]
+    fn set_slider() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Slider(Slider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                5.0,
+                "%.2f".to_owned(),
+                120.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::F32(6.0),
+            },
+        )
+        .model_changed());
+
+        if let Widget::Slider(slider) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(slider.value(), 6.0);
+        } else {
+            panic!("invalid widget type");
         }
     }
 
-    ModelUnchanged
-}
+    #[test]
+    fn set_range_slider() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::RangeSlider(RangeSlider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                (2.0, 8.0),
+                "%.1f".to_owned(),
+                150.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-fn close_file_dialog(state: &mut State) -> ReduceResult {
-    state.file_dialog.mode = FileDialogMode::Closed;
-    ModelUnchanged
-}
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::F32F32((3.0, 6.0)),
+            },
+        )
+        .model_changed());
 
-#[cfg(test)]
-mod tests {
-    extern crate tempfile;
+        if let Widget::RangeSlider(range_slider) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(range_slider.value(), (3.0, 6.0));
+        } else {
+            panic!("invalid widget type");
+        }
+    }
 
-    use std::fs;
+    #[test]
+    fn set_xy_pad() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::XYPad(XYPad::new(
+                "key".to_owned(),
+                (-1.0, 1.0),
+                (-1.0, 1.0),
+                (0.0, 0.0),
+                100.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-    use super::*;
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::Point((0.5, -0.25)),
+            },
+        )
+        .model_changed());
 
-    use crate::state::{
-        Button, ButtonActivationMode, Canvas, Direction, DropDown, DropDownItem, NodeTemplate, Pin,
-        Slider, TextBox,
-    };
+        if let Widget::XYPad(xy_pad) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(xy_pad.value(), (0.5, -0.25));
+        } else {
+            panic!("invalid widget type");
+        }
+    }
 
     #[test]
-    fn scroll() {
+    fn set_envelope() {
         let mut state = State::default();
-        let original_offset = state.offset;
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Envelope(Envelope::new(
+                "key".to_owned(),
+                (0.0, 1.0),
+                (0.0, 1.0),
+                vec![(0.0, 0.0), (1.0, 1.0)],
+                true,
+                [200.0, 100.0],
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-        assert!(!reduce(&mut state, Action::Scroll { offset: [1.0, 2.0] }).model_changed());
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::VecF32F32F32(vec![(0.0, 0.0, 0.5), (0.5, 1.0, 0.0), (1.0, 0.0, 0.0)]),
+            },
+        )
+        .model_changed());
+        assert!(reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::VecF32F32(vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]),
+            },
+        )
+        .model_changed());
 
-        assert_eq!(state.offset[0], original_offset[0] + 1.0);
-        assert_eq!(state.offset[1], original_offset[1] + 2.0);
+        if let Widget::Envelope(envelope) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(envelope.points(), &vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]);
+            assert_eq!(envelope.curvatures(), &vec![0.0, 0.0]);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn add_node() {
+    fn set_knob() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![],
+            vec![Widget::Knob(Knob::new(
+                "key".to_owned(),
+                0.0,
+                1.0,
+                0.5,
+                "%.2f".to_owned(),
+                40.0,
+            ))],
         ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
         assert!(reduce(
             &mut state,
-            Action::AddNode {
-                class: "class".to_owned(),
-                position: [100.0, 200.0],
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::F32(2.0),
             },
         )
         .model_changed());
 
-        assert_eq!(state.nodes()[0].class(), "class");
-        assert_eq!(state.nodes()[0].position, [100.0, 200.0]);
-        assert!(state.triggered_node().is_some());
-        assert_eq!(state.triggered_node().as_ref().unwrap(), "class:0");
+        if let Widget::Knob(knob) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(knob.value(), 1.0);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn remove_node() {
+    fn set_number_input() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![
-                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
-                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
-            ],
             vec![],
+            vec![Widget::NumberInput(NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                Some(Number::I32(0)),
+                Some(Number::I32(10)),
+                1.0,
+                "%d".to_owned(),
+                120.0,
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state
-            .add_patch(
-                PinAddress::new("class:0".to_owned(), "out".to_owned()),
-                PinAddress::new("class:1".to_owned(), "in".to_owned()),
-            )
-            .unwrap();
 
         assert!(reduce(
             &mut state,
-            Action::RemoveNode {
-                node_id: "class:1".to_owned(),
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::I32(7),
+            },
+        )
+        .model_changed());
+        assert!(!reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::I32(7),
             },
         )
         .model_changed());
 
-        assert_eq!(state.nodes().len(), 1);
-        assert!(state.patches().is_empty());
+        if let Widget::NumberInput(number_input) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(number_input.value(), Number::I32(7));
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn move_node() {
+    #[should_panic(
+        expected = "Given widget is an integer NumberInput and accepts only values of type i32"
+    )]
+    fn panic_on_set_number_input_with_invalid_type() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
+            vec![Widget::NumberInput(NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                None,
+                None,
+                1.0,
+                "%d".to_owned(),
+                120.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+        reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::F32(7.0),
+            },
+        );
+    }
+
+    #[test]
+    fn set_dropdown() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
             vec![],
+            vec![Widget::DropDown(DropDown::new(
+                "key".to_owned(),
+                vec![
+                    DropDownItem::new("Label 1".to_owned(), "value1".to_owned()),
+                    DropDownItem::new("Label 2".to_owned(), "value2".to_owned()),
+                ],
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        let original_position = state.nodes()[0].position;
 
-        assert!(!reduce(
+        assert!(reduce(
             &mut state,
-            Action::MoveNode {
+            Action::SetValue {
                 node_id: "class:0".to_owned(),
-                offset: [100.0, 200.0],
+                key: "key".to_owned(),
+                value: Value::String("value2".to_owned()),
             },
         )
         .model_changed());
 
-        let updated_position1 = state.nodes()[0].position;
-        assert_eq!(
-            updated_position1,
-            vec2::sum(&[original_position, [100.0, 200.0]])
-        );
+        if let Widget::DropDown(dropdown) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(dropdown.value(), "value2");
+        } else {
+            panic!("invalid widget type");
+        }
+    }
+
+    #[test]
+    fn update_slider_range() {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Slider(Slider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                5.0,
+                "%.2f".to_owned(),
+                120.0,
+            ))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
         assert!(!reduce(
             &mut state,
-            Action::MoveNode {
+            Action::UpdateWidget {
                 node_id: "class:0".to_owned(),
-                offset: [10.0, -10.0],
+                key: "key".to_owned(),
+                update: WidgetUpdate::SliderRange {
+                    min: 0.0,
+                    max: 20.0
+                },
             },
         )
         .model_changed());
 
-        let updated_position2 = state.nodes()[0].position;
-        assert_eq!(
-            updated_position2,
-            vec2::sum(&[updated_position1, [10.0, -10.0]])
-        );
+        assert!(reduce(
+            &mut state,
+            Action::UpdateWidget {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::SliderRange { min: 0.0, max: 2.0 },
+            },
+        )
+        .model_changed());
+
+        if let Widget::Slider(slider) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(slider.max(), 2.0);
+            assert_eq!(slider.value(), 2.0);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn trigger_node() {
+    fn update_dropdown_items() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![Pin::new(
-                "Input".to_owned(),
-                "in".to_owned(),
-                Direction::Input,
-            )],
             vec![],
+            vec![Widget::DropDown(DropDown::new(
+                "key".to_owned(),
+                vec![
+                    DropDownItem::new("Label 1".to_owned(), "value1".to_owned()),
+                    DropDownItem::new("Label 2".to_owned(), "value2".to_owned()),
+                ],
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-        assert!(!reduce(
+        assert!(reduce(
             &mut state,
-            Action::SetTriggeredNode {
+            Action::UpdateWidget {
                 node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::DropDownItems {
+                    items: vec![DropDownItem::new("Label 3".to_owned(), "value3".to_owned())],
+                },
             },
         )
         .model_changed());
 
-        assert!(state.triggered_node().is_some());
-        assert_eq!(state.triggered_node().as_ref().unwrap(), "class:0");
-
-        assert!(!reduce(&mut state, Action::ResetTriggeredNode).model_changed());
-
-        assert!(state.triggered_node().is_none());
+        if let Widget::DropDown(dropdown) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(dropdown.value(), "value3");
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn move_triggered_node_forward() {
+    fn update_button_label() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![],
+            vec![Widget::Button(Button::new(
+                "Button".to_owned(),
+                "key".to_owned(),
+                ButtonActivationMode::OnClick,
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        assert_eq!(state.nodes()[0].id(), "class:0");
-        assert_eq!(state.nodes()[1].id(), "class:1");
 
         assert!(!reduce(
             &mut state,
-            Action::SetTriggeredNode {
+            Action::UpdateWidget {
                 node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::Label {
+                    label: "Renamed".to_owned()
+                },
             },
         )
         .model_changed());
 
-        assert_eq!(state.nodes()[0].id(), "class:1");
-        assert_eq!(state.nodes()[1].id(), "class:0");
+        if let Widget::Button(button) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(button.label(), "Renamed");
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn trigger_patch() {
+    fn ignore_invalid_slider_range() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![
-                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
-                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
-            ],
             vec![],
+            vec![Widget::Slider(Slider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                5.0,
+                "%.2f".to_owned(),
+                120.0,
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state
-            .add_patch(
-                PinAddress::new("class:0".to_owned(), "out".to_owned()),
-                PinAddress::new("class:1".to_owned(), "in".to_owned()),
-            )
-            .unwrap();
 
-        let patch = Patch::new(
-            PinAddress::new("class:0".to_owned(), "out".to_owned()),
-            PinAddress::new("class:1".to_owned(), "in".to_owned()),
-        );
         assert!(!reduce(
             &mut state,
-            Action::SetTriggeredPatch {
-                patch: patch.clone(),
+            Action::UpdateWidget {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::SliderRange {
+                    min: 10.0,
+                    max: 0.0
+                },
             },
         )
         .model_changed());
 
-        assert!(state.triggered_patch().is_some());
-        assert_eq!(state.triggered_patch().as_ref().unwrap(), &patch);
-
-        assert!(!reduce(&mut state, Action::ResetTriggeredPatch).model_changed());
-
-        assert!(state.triggered_patch().is_none());
+        if let Widget::Slider(slider) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(slider.min(), 0.0);
+            assert_eq!(slider.max(), 10.0);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn trigger_pin() {
+    fn ignore_empty_dropdown_items() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![Pin::new(
-                "Input".to_owned(),
-                "in".to_owned(),
-                Direction::Input,
-            )],
             vec![],
+            vec![Widget::DropDown(DropDown::new(
+                "key".to_owned(),
+                vec![DropDownItem::new("Label 1".to_owned(), "value1".to_owned())],
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
         assert!(!reduce(
             &mut state,
-            Action::SetTriggeredPin {
-                pin_address: PinAddress::new("class:0".to_owned(), "in".to_owned()),
+            Action::UpdateWidget {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::DropDownItems { items: vec![] },
             },
         )
         .model_changed());
 
-        assert!(state.triggered_pin().is_some());
-        assert_eq!(state.triggered_pin().as_ref().unwrap().node_id(), "class:0");
-        assert_eq!(state.triggered_pin().as_ref().unwrap().pin_class(), "in");
-
-        assert!(!reduce(&mut state, Action::ResetTriggeredPin).model_changed());
-
-        assert!(state.triggered_pin().is_none());
+        if let Widget::DropDown(dropdown) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(dropdown.items().len(), 1);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn create_patch_when_second_pin_is_triggered() {
+    fn ignore_update_not_matching_widget() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![
-                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
-                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
-            ],
             vec![],
+            vec![Widget::Canvas(Canvas::new(
+                "key".to_owned(),
+                [100.0, 100.0],
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
         assert!(!reduce(
             &mut state,
-            Action::SetTriggeredPin {
-                pin_address: PinAddress::new("class:0".to_owned(), "out".to_owned()),
+            Action::UpdateWidget {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                update: WidgetUpdate::ReadOnly { read_only: true },
             },
         )
         .model_changed());
-
-        assert!(reduce(
+        assert!(!reduce(
             &mut state,
-            Action::SetTriggeredPin {
-                pin_address: PinAddress::new("class:1".to_owned(), "in".to_owned()),
+            Action::UpdateWidget {
+                node_id: "class:0".to_owned(),
+                key: "missing".to_owned(),
+                update: WidgetUpdate::ReadOnly { read_only: true },
             },
         )
         .model_changed());
-
-        let patch = state.patches().iter().next().unwrap();
-        assert_eq!(
-            *patch,
-            Patch::new(
-                PinAddress::new("class:0".to_owned(), "out".to_owned()),
-                PinAddress::new("class:1".to_owned(), "in".to_owned())
-            )
-        );
-
-        assert!(state.triggered_pin().is_none());
     }
 
     #[test]
-    fn remove_patch() {
+    fn set_canvas_value() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
-            vec![
-                Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
-                Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
-            ],
             vec![],
+            vec![Widget::Canvas(Canvas::new(
+                "key".to_owned(),
+                [100.0, 100.0],
+            ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
-        state
-            .add_patch(
-                PinAddress::new("class:0".to_owned(), "out".to_owned()),
-                PinAddress::new("class:1".to_owned(), "in".to_owned()),
-            )
-            .unwrap();
 
         assert!(reduce(
             &mut state,
-            Action::RemovePatch {
-                patch: Patch::new(
-                    PinAddress::new("class:0".to_owned(), "out".to_owned()),
-                    PinAddress::new("class:1".to_owned(), "in".to_owned()),
-                ),
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::VecF32F32(vec![(1.0, 2.0)]),
             },
         )
         .model_changed());
 
-        assert!(state.patches().is_empty());
+        if let Widget::Canvas(canvas) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(canvas.dots(), &[(1.0, 2.0)]);
+        } else {
+            panic!("invalid widget type");
+        }
     }
 
     #[test]
-    fn set_text_box_content() {
+    fn set_image_value() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![Widget::TextBox(TextBox::new(
-                "key".to_owned(),
-                100,
-                [100.0, 100.0],
-                false,
-            ))],
+            vec![Widget::Image(Image::new("key".to_owned(), [100.0, 100.0]))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-        assert!(reduce(
-            &mut state,
-            Action::SetValue {
-                node_id: "class:0".to_owned(),
-                key: "key".to_owned(),
-                value: Value::String("hello world".to_owned()),
-            },
-        )
-        .model_changed());
-
-        if let Widget::TextBox(text_box) = &state.nodes()[0].widgets()[0] {
-            assert_eq!(text_box.content(), "hello world");
+        let set_image = || Action::SetValue {
+            node_id: "class:0".to_owned(),
+            key: "key".to_owned(),
+            value: Value::Rgba8(ImageData::new(1, 1, vec![255, 0, 0, 255])),
+        };
+        assert!(reduce(&mut state, set_image()).model_changed());
+        assert!(!reduce(&mut state, set_image()).model_changed());
+
+        if let Widget::Image(image) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(image.data().as_ref().unwrap().data(), &vec![255, 0, 0, 255]);
+            assert_eq!(image.revision(), 1);
         } else {
             panic!("invalid widget type");
         }
     }
 
     #[test]
-    fn set_button() {
+    fn set_canvas_shapes() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![Widget::Button(Button::new(
-                "Button".to_owned(),
+            vec![Widget::Canvas(Canvas::new(
                 "key".to_owned(),
-                ButtonActivationMode::OnClick,
+                [100.0, 100.0],
             ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        let shapes = vec![Shape::Line {
+            from: [0.0, 0.0],
+            to: [10.0, 10.0],
+            color: [1.0, 1.0, 1.0, 1.0],
+            thickness: 1.0,
+        }];
 
         assert!(reduce(
             &mut state,
             Action::SetValue {
                 node_id: "class:0".to_owned(),
                 key: "key".to_owned(),
-                value: Value::Bool(true),
+                value: Value::Shapes(shapes.clone()),
             },
         )
         .model_changed());
-
-        if let Widget::Button(button) = &state.nodes()[0].widgets()[0] {
-            assert!(button.active());
-        } else {
-            panic!("invalid widget type");
-        }
-
-        assert!(reduce(
+        assert!(!reduce(
             &mut state,
             Action::SetValue {
                 node_id: "class:0".to_owned(),
                 key: "key".to_owned(),
-                value: Value::Bool(false),
+                value: Value::Shapes(shapes.clone()),
             },
         )
         .model_changed());
 
-        if let Widget::Button(button) = &state.nodes()[0].widgets()[0] {
-            assert!(!button.active());
+        if let Widget::Canvas(canvas) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(canvas.shapes(), &shapes);
+            assert!(canvas.dots().is_empty());
         } else {
             panic!("invalid widget type");
         }
     }
 
     #[test]
-    fn set_slider() {
+    fn set_plot_value() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![Widget::Slider(Slider::new(
+            vec![Widget::Plot(Plot::new(
                 "key".to_owned(),
-                0.0,
-                10.0,
-                5.0,
-                "%.2f".to_owned(),
-                120.0,
+                (-1.0, 1.0),
+                vec![PlotSeries::new("".to_owned(), [1.0, 1.0, 1.0, 1.0])],
+                [100.0, 100.0],
             ))],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
@@ -735,55 +3553,69 @@ mod tests {
             Action::SetValue {
                 node_id: "class:0".to_owned(),
                 key: "key".to_owned(),
-                value: Value::F32(6.0),
+                value: Value::VecF32(vec![0.0, 0.5]),
+            },
+        )
+        .model_changed());
+        assert!(!reduce(
+            &mut state,
+            Action::SetValue {
+                node_id: "class:0".to_owned(),
+                key: "key".to_owned(),
+                value: Value::VecVecF32(vec![vec![0.0, 0.5]]),
             },
         )
         .model_changed());
 
-        if let Widget::Slider(slider) = &state.nodes()[0].widgets()[0] {
-            assert_eq!(slider.value(), 6.0);
+        if let Widget::Plot(plot) = &state.nodes()[0].widgets()[0] {
+            assert_eq!(plot.samples(), &vec![vec![0.0, 0.5]]);
         } else {
             panic!("invalid widget type");
         }
     }
 
     #[test]
-    fn set_dropdown() {
+    fn receive_canvas_input() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
             "class".to_owned(),
             true,
             vec![],
-            vec![Widget::DropDown(DropDown::new(
-                "key".to_owned(),
-                vec![
-                    DropDownItem::new("Label 1".to_owned(), "value1".to_owned()),
-                    DropDownItem::new("Label 2".to_owned(), "value2".to_owned()),
-                ],
-            ))],
+            vec![Widget::Canvas(
+                Canvas::new("key".to_owned(), [100.0, 100.0]).with_interactive(true),
+            )],
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
         assert!(reduce(
             &mut state,
-            Action::SetValue {
+            Action::CanvasInput {
                 node_id: "class:0".to_owned(),
                 key: "key".to_owned(),
-                value: Value::String("value2".to_owned()),
+                position: [10.0, 20.0],
+                event: CanvasEvent::Press(MouseButton::Left),
             },
         )
         .model_changed());
 
-        if let Widget::DropDown(dropdown) = &state.nodes()[0].widgets()[0] {
-            assert_eq!(dropdown.value(), "value2");
-        } else {
-            panic!("invalid widget type");
-        }
+        assert_eq!(
+            state.canvas_input(),
+            &Some(CanvasInput::new(
+                WidgetAddress::new("class:0".to_owned(), "key".to_owned()),
+                [10.0, 20.0],
+                CanvasEvent::Press(MouseButton::Left),
+            ))
+        );
+
+        reduce(&mut state, Action::ResetTriggeredNode);
+
+        assert_eq!(state.canvas_input(), &None);
     }
 
     #[test]
-    fn set_canvas_value() {
+    #[should_panic(expected = "Input can be received only by an interactive Canvas")]
+    fn reject_input_of_non_interactive_canvas() {
         let mut state = State::default();
         state.add_node_template(NodeTemplate::new(
             "Label".to_owned(),
@@ -797,21 +3629,15 @@ mod tests {
         ));
         state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
 
-        assert!(reduce(
+        reduce(
             &mut state,
-            Action::SetValue {
+            Action::CanvasInput {
                 node_id: "class:0".to_owned(),
                 key: "key".to_owned(),
-                value: Value::VecF32F32(vec![(1.0, 2.0)]),
+                position: [10.0, 20.0],
+                event: CanvasEvent::Scroll(1.0),
             },
-        )
-        .model_changed());
-
-        if let Widget::Canvas(canvas) = &state.nodes()[0].widgets()[0] {
-            assert_eq!(canvas.dots(), &[(1.0, 2.0)]);
-        } else {
-            panic!("invalid widget type");
-        }
+        );
     }
 
     #[test]
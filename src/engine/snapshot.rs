@@ -6,7 +6,7 @@ use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 
-use crate::engine::state::{Node, NodeTemplate, Patch, State};
+use crate::engine::state::{Frame, Node, NodeTemplate, Patch, State, Subgraph};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
 pub struct Snapshot {
@@ -14,21 +14,50 @@ pub struct Snapshot {
     pub node_templates: Vec<NodeTemplate>,
     pub nodes: Vec<Node>,
     pub patches: HashSet<Patch>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frames: Vec<Frame>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subgraphs: Vec<Subgraph>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waypoints: Vec<PatchWaypoints>,
+}
+
+/// Reroute points a patch is drawn through.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PatchWaypoints {
+    pub patch: Patch,
+    pub points: Vec<[f32; 2]>,
 }
 
 impl From<&State> for Snapshot {
     fn from(state: &State) -> Self {
+        // Waypoints are sorted so the same graph is always saved the same way.
+        let mut waypoints: Vec<PatchWaypoints> = state
+            .waypoints()
+            .iter()
+            .map(|(patch, points)| PatchWaypoints {
+                patch: patch.clone(),
+                points: points.clone(),
+            })
+            .collect();
+        waypoints.sort_by(|a, b| a.patch.cmp(&b.patch));
+
         Self {
             offset: state.offset,
             node_templates: state.node_templates().clone(),
             nodes: state.nodes().clone(),
             patches: state.patches().clone(),
+            frames: state.frames().clone(),
+            subgraphs: state.subgraphs().clone(),
+            waypoints,
         }
     }
 }
 
 impl State {
     pub fn load_snapshot(&mut self, snapshot: Snapshot) -> Result<(), String> {
+        validate_nesting(&snapshot)?;
+
         for template in snapshot.node_templates.iter() {
             let state_template = self
                 .node_templates()
@@ -43,20 +72,77 @@ impl State {
         self.set_triggered_node(None);
         self.set_triggered_pin(None);
         self.set_triggered_patch(None);
+        self.set_drop_target_patch(None);
+        self.set_renamed_node(None);
+        self.set_slider_text_input(None);
+        self.set_triggered_frame(None);
+        self.set_open_subgraph(None);
+        self.set_selection(Vec::new());
+        self.set_selection_rectangle(None);
+        self.drag_positions_mut().clear();
 
         self.set_nodes(snapshot.nodes);
         self.set_patches(snapshot.patches);
+        self.set_frames(snapshot.frames);
+        self.set_subgraphs(snapshot.subgraphs);
+        self.set_waypoints(
+            snapshot
+                .waypoints
+                .into_iter()
+                .map(|w| (w.patch, w.points))
+                .collect(),
+        );
+        self.drop_stale_waypoints();
 
         Ok(())
     }
 }
 
+/// Make sure that every parent references an existing subgraph and that no
+/// subgraph is nested in itself.
+fn validate_nesting(snapshot: &Snapshot) -> Result<(), String> {
+    let find_subgraph = |id: &str| snapshot.subgraphs.iter().find(|s| s.id() == id);
+
+    let parents = snapshot
+        .nodes
+        .iter()
+        .map(|n| &n.parent)
+        .chain(snapshot.subgraphs.iter().map(|s| &s.parent))
+        .chain(snapshot.frames.iter().map(|f| &f.parent));
+    for parent in parents.filter_map(Option::as_deref) {
+        if find_subgraph(parent).is_none() {
+            return Err("Cannot load an item nested in an unknown subgraph".to_owned());
+        }
+    }
+
+    for subgraph in snapshot.subgraphs.iter() {
+        let mut current = subgraph.parent.as_deref();
+        let mut depth = 0;
+        while let Some(parent) = current {
+            // A path longer than the number of subgraphs must contain a cycle.
+            depth += 1;
+            if depth > snapshot.subgraphs.len() {
+                return Err("Cannot load subgraphs nested in themselves".to_owned());
+            }
+            current = find_subgraph(parent).and_then(|s| s.parent.as_deref());
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     extern crate serde_json;
 
+    use std::collections::HashMap;
+
     use super::*;
-    use crate::engine::state::{Button, ButtonActivationMode, Direction, Pin, PinAddress, Widget};
+    use crate::engine::state::{
+        Button, ButtonActivationMode, Direction, Number, NumberInput, Pin, PinAddress, Widget,
+        WidgetAddress,
+    };
+    use crate::model as m;
 
     fn initialize_state() -> State {
         let mut state = State::default();
@@ -111,6 +197,9 @@ mod tests {
             node_templates: vec![template],
             nodes: vec![node1, node2],
             patches: vec![patch].into_iter().collect(),
+            frames: vec![],
+            subgraphs: vec![],
+            waypoints: vec![],
         }
     }
 
@@ -257,6 +346,234 @@ mod tests {
         );
     }
 
+    #[test]
+    fn save_and_load_frames() {
+        let mut state = initialize_state();
+        state.add_frame(Frame::new(
+            "frame:0".to_owned(),
+            "Title".to_owned(),
+            [10.0, 20.0],
+            [100.0, 50.0],
+            [1.0, 0.0, 0.0, 0.5],
+        ));
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = initialize_state();
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert_eq!(loaded_state.frames(), state.frames());
+    }
+
+    #[test]
+    fn save_and_load_collapsed_node() {
+        let mut state = initialize_state();
+        state.nodes_mut()[0].collapsed = true;
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = initialize_state();
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert!(loaded_state.nodes()[0].collapsed);
+        assert!(!loaded_state.nodes()[1].collapsed);
+    }
+
+    #[test]
+    fn save_and_load_custom_node_size() {
+        let mut state = initialize_state();
+        state.nodes_mut()[0].custom_size = Some([400.0, 300.0]);
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = initialize_state();
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert_eq!(loaded_state.nodes()[0].custom_size, Some([400.0, 300.0]));
+        assert_eq!(loaded_state.nodes()[1].custom_size, None);
+    }
+
+    #[test]
+    fn save_and_load_integer_number_input() {
+        let new_state = || {
+            let mut state = State::default();
+            state.add_node_template(NodeTemplate::new(
+                "Node".to_owned(),
+                "node".to_owned(),
+                true,
+                vec![],
+                vec![Widget::NumberInput(NumberInput::new(
+                    "steps".to_owned(),
+                    Number::I32(8),
+                    Some(Number::I32(1)),
+                    None,
+                    0.1,
+                    "%d".to_owned(),
+                    100.0,
+                ))],
+            ));
+            state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+            state
+        };
+        let mut state = new_state();
+        if let Widget::NumberInput(number_input) = &mut state.nodes_mut()[0].widgets_mut()[0] {
+            number_input.set_value(Number::I32(16));
+        }
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = new_state();
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert_eq!(loaded_state.nodes(), state.nodes());
+        assert_eq!(
+            m::Value::from(&loaded_state.nodes()[0].widgets()[0]),
+            m::Value::I32(16)
+        );
+    }
+
+    #[test]
+    fn reset_transient_state_on_load() {
+        let mut state = initialize_state();
+        state
+            .drag_positions_mut()
+            .insert("node:0".to_owned(), [10.0, 20.0]);
+        state.set_slider_text_input(Some(WidgetAddress::new(
+            "node:0".to_owned(),
+            "key".to_owned(),
+        )));
+
+        state.load_snapshot(initialize_snapshot()).unwrap();
+
+        assert!(state.drag_positions().is_empty());
+        assert!(state.slider_text_input().is_none());
+    }
+
+    #[test]
+    fn save_and_load_waypoints() {
+        let mut state = initialize_state();
+        let patch = state.patches().iter().next().unwrap().clone();
+        state
+            .waypoints_mut()
+            .insert(patch.clone(), vec![[10.0, 20.0], [30.0, 40.0]]);
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = initialize_state();
+        loaded_state.set_waypoints(HashMap::new());
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            loaded_state.waypoints()[&patch],
+            vec![[10.0, 20.0], [30.0, 40.0]]
+        );
+    }
+
+    #[test]
+    fn save_waypoints_sorted_by_patch() {
+        let mut state = initialize_state();
+        let patches: Vec<Patch> = (0..10)
+            .map(|i| {
+                Patch::new(
+                    PinAddress::new(format!("node:{}", i), "out1".to_owned()),
+                    PinAddress::new("node:0".to_owned(), "in1".to_owned()),
+                )
+            })
+            .collect();
+        for patch in patches.iter().rev() {
+            state
+                .waypoints_mut()
+                .insert(patch.clone(), vec![[0.0, 0.0]]);
+        }
+
+        let snapshot = Snapshot::from(&state);
+
+        let saved_patches: Vec<Patch> = snapshot.waypoints.into_iter().map(|w| w.patch).collect();
+        assert_eq!(saved_patches, patches);
+    }
+
+    #[test]
+    fn save_and_load_subgraphs() {
+        let mut state = initialize_state();
+        state.group_into_subgraph(&["node:0".to_owned()], "Outer".to_owned());
+        state.set_open_subgraph(Some("subgraph:0".to_owned()));
+        state.group_into_subgraph(&["node:0".to_owned()], "Inner".to_owned());
+        state.set_open_subgraph(None);
+        let serialized = serde_json::to_string(&Snapshot::from(&state)).unwrap();
+
+        let mut loaded_state = initialize_state();
+        loaded_state
+            .load_snapshot(serde_json::from_str(&serialized).unwrap())
+            .unwrap();
+
+        assert_eq!(loaded_state.subgraphs(), state.subgraphs());
+        assert_eq!(loaded_state.nodes(), state.nodes());
+        assert_eq!(
+            loaded_state.nodes()[0].parent,
+            Some("subgraph:1".to_owned())
+        );
+        assert_eq!(
+            loaded_state.subgraphs()[1].parent,
+            Some("subgraph:0".to_owned())
+        );
+    }
+
+    #[test]
+    fn reject_node_nested_in_unknown_subgraph() {
+        let mut snapshot = Snapshot::from(&initialize_state());
+        snapshot.nodes[0].parent = Some("subgraph:0".to_owned());
+
+        let mut state = initialize_state();
+
+        assert!(state.load_snapshot(snapshot).is_err());
+    }
+
+    #[test]
+    fn reject_frame_nested_in_unknown_subgraph() {
+        let mut frame = Frame::new(
+            "frame:0".to_owned(),
+            "Title".to_owned(),
+            [10.0, 20.0],
+            [100.0, 50.0],
+            [1.0, 0.0, 0.0, 0.5],
+        );
+        frame.parent = Some("subgraph:0".to_owned());
+        let mut snapshot = Snapshot::from(&initialize_state());
+        snapshot.frames.push(frame);
+
+        let mut state = initialize_state();
+
+        assert!(state.load_snapshot(snapshot).is_err());
+    }
+
+    #[test]
+    fn reject_cyclic_subgraphs() {
+        let mut snapshot = Snapshot::from(&initialize_state());
+        snapshot.subgraphs = vec![
+            Subgraph::new(
+                "subgraph:0".to_owned(),
+                "Label".to_owned(),
+                Some("subgraph:1".to_owned()),
+                [0.0, 0.0],
+            ),
+            Subgraph::new(
+                "subgraph:1".to_owned(),
+                "Label".to_owned(),
+                Some("subgraph:0".to_owned()),
+                [0.0, 0.0],
+            ),
+        ];
+
+        let mut state = initialize_state();
+
+        assert!(state.load_snapshot(snapshot).is_err());
+    }
+
     #[test]
     fn deserialize_snapshot() {
         assert_eq!(
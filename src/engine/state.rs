@@ -8,7 +8,7 @@ extern crate serde;
 
 use std::cell::RefCell;
 use std::clone::Clone;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::From;
 
 use imgui::ImString;
@@ -35,8 +35,42 @@ pub struct State {
     patches: HashSet<Patch>,
     #[getset(get = "pub", set = "pub")]
     triggered_patch: Option<Patch>,
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    waypoints: HashMap<Patch, Vec<[f32; 2]>>,
+    #[getset(get = "pub", set = "pub")]
+    drop_target_patch: Option<Patch>,
+
+    #[getset(get = "pub", set = "pub")]
+    renamed_node: Option<NodeRename>,
+    #[getset(get = "pub", set = "pub")]
+    slider_text_input: Option<WidgetAddress>,
+    #[getset(get = "pub", set = "pub")]
+    canvas_input: Option<CanvasInput>,
+
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    frames: Vec<Frame>,
+    #[getset(get = "pub", set = "pub")]
+    triggered_frame: Option<String>,
+
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    subgraphs: Vec<Subgraph>,
+    #[getset(get = "pub", set = "pub")]
+    open_subgraph: Option<String>,
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    selection: Vec<String>,
+    #[getset(get = "pub", set = "pub")]
+    selection_rectangle: Option<[[f32; 2]; 2]>,
+
+    pub grid: Grid,
+    #[getset(get = "pub", set = "pub")]
+    minimap_visible: bool,
+    /// Unsnapped positions of nodes being dragged, by their ids.
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    drag_positions: HashMap<String, [f32; 2]>,
 
     pub file_dialog: FileDialog,
+
+    pub report_layout: bool,
 }
 
 impl From<c::Config> for State {
@@ -46,6 +80,7 @@ impl From<c::Config> for State {
             .node_templates
             .into_iter()
             .for_each(|t| state.add_node_template(NodeTemplate::from(t)));
+        state.report_layout = config.report_layout;
         state
     }
 }
@@ -59,6 +94,8 @@ impl From<c::NodeTemplate> for NodeTemplate {
             config.pins.into_iter().map(Pin::from).collect(),
             config.widgets.into_iter().map(Widget::from).collect(),
         )
+        .with_variadic_pins(config.variadic_pins.into_iter().map(Pin::from).collect())
+        .with_resizable(config.resizable)
     }
 }
 
@@ -97,7 +134,61 @@ impl From<c::Widget> for Widget {
                 default,
                 format,
                 width,
-            } => Widget::Slider(Slider::new(key, min, max, default, format, width)),
+                scale,
+                unit,
+            } => Widget::Slider(
+                Slider::new(key, min, max, default, format, width)
+                    .with_scale(Scale::from(scale))
+                    .with_unit(unit.unwrap_or_default()),
+            ),
+            c::Widget::RangeSlider {
+                key,
+                min,
+                max,
+                default,
+                format,
+                width,
+            } => Widget::RangeSlider(RangeSlider::new(key, min, max, default, format, width)),
+            c::Widget::XYPad {
+                key,
+                x_range,
+                y_range,
+                default,
+                size,
+            } => Widget::XYPad(XYPad::new(key, x_range, y_range, default, size)),
+            c::Widget::Envelope {
+                key,
+                x_range,
+                y_range,
+                default,
+                curved,
+                size,
+            } => Widget::Envelope(Envelope::new(key, x_range, y_range, default, curved, size)),
+            c::Widget::Knob {
+                key,
+                min,
+                max,
+                default,
+                format,
+                size,
+            } => Widget::Knob(Knob::new(key, min, max, default, format, size)),
+            c::Widget::NumberInput {
+                key,
+                default,
+                min,
+                max,
+                step,
+                format,
+                width,
+            } => Widget::NumberInput(NumberInput::new(
+                key,
+                Number::from(default),
+                min.map(Number::from),
+                max.map(Number::from),
+                step,
+                format,
+                width,
+            )),
             c::Widget::Trigger { key, label } => {
                 Widget::Button(Button::new(label, key, ButtonActivationMode::OnHold))
             }
@@ -106,21 +197,182 @@ impl From<c::Widget> for Widget {
             }
             c::Widget::DropDown { key, items } => Widget::DropDown(DropDown::new(
                 key,
-                items
-                    .into_iter()
-                    .map(|i| DropDownItem::new(i.label, i.value))
-                    .collect(),
+                items.into_iter().map(DropDownItem::from).collect(),
             )),
-            c::Widget::Canvas { key, size } => Widget::Canvas(Canvas::new(key, size)),
+            c::Widget::Plot {
+                key,
+                y_range,
+                series,
+                axes,
+                grid,
+                size,
+            } => Widget::Plot(
+                Plot::new(
+                    key,
+                    y_range,
+                    series.into_iter().map(PlotSeries::from).collect(),
+                    size,
+                )
+                .with_axes(axes)
+                .with_grid(grid),
+            ),
+            c::Widget::Image { key, size } => Widget::Image(Image::new(key, size)),
+            c::Widget::Canvas {
+                key,
+                size,
+                interactive,
+            } => Widget::Canvas(Canvas::new(key, size).with_interactive(interactive)),
+        }
+    }
+}
+
+impl From<c::PlotSeries> for PlotSeries {
+    fn from(config: c::PlotSeries) -> Self {
+        Self::new(config.label, config.color)
+    }
+}
+
+impl From<c::Scale> for Scale {
+    fn from(config: c::Scale) -> Self {
+        match config {
+            c::Scale::Linear => Scale::Linear,
+            c::Scale::Logarithmic => Scale::Logarithmic,
+            c::Scale::Exponential { curve } => Scale::Exponential { curve },
+            c::Scale::Steps { step } => Scale::Steps { step },
+        }
+    }
+}
+
+impl From<c::Number> for Number {
+    fn from(config: c::Number) -> Self {
+        match config {
+            c::Number::F32(value) => Number::F32(value),
+            c::Number::I32(value) => Number::I32(value),
         }
     }
 }
 
+impl From<c::DropDownItem> for DropDownItem {
+    fn from(config: c::DropDownItem) -> Self {
+        Self::new(config.label, config.value)
+    }
+}
+
 impl From<&State> for r::Report {
     fn from(state: &State) -> Self {
         Self {
             nodes: state.nodes.iter().map(m::Node::from).collect(),
             patches: state.patches.iter().map(m::Patch::from).collect(),
+            subgraphs: state
+                .subgraphs
+                .iter()
+                .map(|s| m::Subgraph::from((state, s)))
+                .collect(),
+            layout: if state.report_layout {
+                Some(r::Layout::from(state))
+            } else {
+                None
+            },
+            canvas_input: state.canvas_input.as_ref().map(m::CanvasInput::from),
+        }
+    }
+}
+
+impl From<&CanvasInput> for m::CanvasInput {
+    fn from(state: &CanvasInput) -> Self {
+        Self {
+            node_id: state.address.node_id.clone(),
+            key: state.address.key.clone(),
+            position: state.position,
+            event: match state.event {
+                CanvasEvent::Press(button) => m::CanvasEvent::Press(button.into()),
+                CanvasEvent::Drag(button) => m::CanvasEvent::Drag(button.into()),
+                CanvasEvent::Release(button) => m::CanvasEvent::Release(button.into()),
+                CanvasEvent::Scroll(delta) => m::CanvasEvent::Scroll(delta),
+            },
+        }
+    }
+}
+
+impl From<MouseButton> for m::MouseButton {
+    fn from(state: MouseButton) -> Self {
+        match state {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+        }
+    }
+}
+
+impl From<&State> for r::Layout {
+    fn from(state: &State) -> Self {
+        Self {
+            offset: state.offset,
+            nodes: state.nodes.iter().map(m::NodeLayout::from).collect(),
+            frames: state.frames.iter().map(m::Frame::from).collect(),
+        }
+    }
+}
+
+impl From<(&State, &Subgraph)> for m::Subgraph {
+    fn from((state, subgraph): (&State, &Subgraph)) -> Self {
+        let pins = state.subgraph_pins(subgraph.id());
+        let addresses = |direction| {
+            pins.iter()
+                .filter(|p| p.direction() == direction)
+                .map(|p| m::PinAddress::from(p.address()))
+                .collect()
+        };
+        Self {
+            id: subgraph.id().to_string(),
+            label: subgraph.label().to_string(),
+            parent: subgraph.parent.clone(),
+            nodes: state
+                .nodes
+                .iter()
+                .filter(|n| n.parent.as_deref() == Some(subgraph.id()))
+                .map(|n| n.id().to_string())
+                .collect(),
+            inputs: addresses(Direction::Input),
+            outputs: addresses(Direction::Output),
+        }
+    }
+}
+
+impl From<&Frame> for m::Frame {
+    fn from(state: &Frame) -> Self {
+        Self {
+            id: state.id().to_string(),
+            title: state.title().to_string(),
+            position: state.position,
+            size: state.size,
+            color: state.color,
+            parent: state.parent.clone(),
+        }
+    }
+}
+
+impl From<&Node> for m::NodeLayout {
+    fn from(state: &Node) -> Self {
+        Self {
+            id: state.id().to_string(),
+            label: state.label().to_string(),
+            display_heading: state.display_heading,
+            position: state.position,
+            pins: state.pins.iter().map(c::Pin::from).collect(),
+        }
+    }
+}
+
+impl From<&Pin> for c::Pin {
+    fn from(state: &Pin) -> Self {
+        Self {
+            label: state.label().to_string(),
+            class: state.class().to_string(),
+            direction: match state.direction() {
+                Direction::Input => c::Direction::Input,
+                Direction::Output => c::Direction::Output,
+            },
         }
     }
 }
@@ -130,6 +382,8 @@ impl From<&Node> for m::Node {
         Self {
             id: state.id().to_string(),
             class: state.class().to_string(),
+            label: state.label().to_string(),
+            bypassed: state.bypassed,
             data: state
                 .widgets
                 .iter()
@@ -145,7 +399,20 @@ impl From<&Widget> for m::Value {
             Widget::DropDown(dropdown) => Self::String(dropdown.value().to_string()),
             Widget::TextBox(text_box) => Self::String(text_box.content().to_string()),
             Widget::Slider(slider) => Self::F32(slider.value()),
+            Widget::RangeSlider(range_slider) => Self::F32F32(range_slider.value()),
+            Widget::XYPad(xy_pad) => Self::Point(xy_pad.value()),
+            Widget::Envelope(envelope) if envelope.curved() => {
+                Self::VecF32F32F32(envelope.points_with_curvatures())
+            }
+            Widget::Envelope(envelope) => Self::VecF32F32(envelope.points().clone()),
+            Widget::Knob(knob) => Self::F32(knob.value()),
+            Widget::NumberInput(number_input) => match number_input.value() {
+                Number::F32(value) => Self::F32(value),
+                Number::I32(value) => Self::I32(value),
+            },
             Widget::Button(button) => Self::Bool(button.active()),
+            Widget::Plot(_) => Self::Unavailable,
+            Widget::Image(_) => Self::Unavailable,
             Widget::Canvas(_) => Self::Unavailable,
         }
     }
@@ -154,14 +421,17 @@ impl From<&Widget> for m::Value {
 impl From<&Patch> for m::Patch {
     fn from(state: &Patch) -> Self {
         Self {
-            source: m::PinAddress {
-                node_id: state.source.node_id.clone(),
-                pin_class: state.source.pin_class.clone(),
-            },
-            destination: m::PinAddress {
-                node_id: state.destination.node_id.clone(),
-                pin_class: state.destination.pin_class.clone(),
-            },
+            source: m::PinAddress::from(&state.source),
+            destination: m::PinAddress::from(&state.destination),
+        }
+    }
+}
+
+impl From<&PinAddress> for m::PinAddress {
+    fn from(state: &PinAddress) -> Self {
+        Self {
+            node_id: state.node_id.clone(),
+            pin_class: state.pin_class.clone(),
         }
     }
 }
@@ -175,6 +445,10 @@ pub struct NodeTemplate {
     #[getset(get = "pub")]
     id_counter: RefCell<usize>,
     pins: Vec<Pin>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variadic_pins: Vec<Pin>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    resizable: bool,
     #[getset(get = "pub")]
     widgets: Vec<Widget>,
 }
@@ -191,6 +465,18 @@ impl State {
 
         self.node_templates.push(node_template);
     }
+
+    pub fn has_node_template(&self, class: &str) -> bool {
+        self.node_templates.iter().any(|nt| nt.class() == class)
+    }
+
+    pub fn remove_node_template(&mut self, class: &str) -> Option<NodeTemplate> {
+        let index = self
+            .node_templates
+            .iter()
+            .position(|nt| nt.class() == class)?;
+        Some(self.node_templates.remove(index))
+    }
 }
 
 impl NodeTemplate {
@@ -218,7 +504,14 @@ impl NodeTemplate {
                     Widget::Button(button) => button.key(),
                     Widget::TextBox(widget) => widget.key(),
                     Widget::Slider(widget) => widget.key(),
+                    Widget::RangeSlider(widget) => widget.key(),
+                    Widget::XYPad(widget) => widget.key(),
+                    Widget::Envelope(widget) => widget.key(),
+                    Widget::Knob(widget) => widget.key(),
+                    Widget::NumberInput(widget) => widget.key(),
                     Widget::DropDown(widget) => widget.key(),
+                    Widget::Plot(widget) => widget.key(),
+                    Widget::Image(widget) => widget.key(),
                     Widget::Canvas(widget) => widget.key(),
                 };
                 assert!(keys.insert(key), "Each widget must have its unique key");
@@ -231,20 +524,55 @@ impl NodeTemplate {
             display_heading,
             id_counter: RefCell::new(0),
             pins,
+            variadic_pins: Vec::new(),
+            resizable: false,
             widgets,
         }
     }
 
+    pub fn with_variadic_pins(mut self, variadic_pins: Vec<Pin>) -> Self {
+        {
+            let mut classes = HashSet::new();
+            variadic_pins.iter().for_each(|v| {
+                assert!(
+                    classes.insert(v.class()),
+                    "Each variadic pin must have its unique class"
+                );
+                assert!(
+                    !self.pins.iter().any(|p| v.is_variadic_class_of(p.class())),
+                    "Variadic pin must not collide with other pins"
+                );
+            });
+        }
+
+        self.variadic_pins = variadic_pins;
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
     pub fn instantiate(&self, position: [f32; 2]) -> Node {
         let id = ImStringWrapper::from(format!("{}:{}", self.class(), self.id_counter.borrow()));
         *self.id_counter.borrow_mut() += 1;
+        let mut pins = self.pins.clone();
+        pins.extend(self.variadic_pins.iter().map(|v| v.variadic_instance(1)));
         Node {
             id,
             label: self.label.clone(),
             class: self.class.clone(),
             display_heading: self.display_heading,
             position,
-            pins: self.pins.clone(),
+            size: [0.0, 0.0],
+            collapsed: false,
+            bypassed: false,
+            parent: None,
+            pins,
+            variadic_pins: self.variadic_pins.clone(),
+            resizable: self.resizable,
+            custom_size: None,
             widgets: self.widgets.clone(),
         }
     }
@@ -269,8 +597,32 @@ pub struct Node {
 
     pub position: [f32; 2],
 
+    /// Size of the node as measured when it was last drawn. It is not known
+    /// before the node is drawn for the first time.
+    #[serde(skip)]
+    pub size: [f32; 2],
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub collapsed: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bypassed: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
     #[getset(get = "pub", get_mut = "pub")]
     pins: Vec<Pin>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub")]
+    variadic_pins: Vec<Pin>,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub resizable: bool,
+    /// Size set by the user when resizing the node. The node is never smaller
+    /// than needed to fit its content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_size: Option<[f32; 2]>,
 
     #[getset(get = "pub", get_mut = "pub")]
     widgets: Vec<Widget>,
@@ -303,6 +655,32 @@ impl Node {
     pub fn label_im(&self) -> &ImString {
         self.label.im_str()
     }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = ImStringWrapper::from(label);
+    }
+
+    /// Append the pin to the node. The pin is not added if its class is
+    /// already taken or if it could collide with an instance of a variadic pin.
+    /// Returns whether the pin was added.
+    pub fn add_pin(&mut self, pin: Pin) -> bool {
+        if self.pins.iter().any(|p| p.class() == pin.class())
+            || self
+                .variadic_pins
+                .iter()
+                .any(|v| v.is_variadic_class_of(pin.class()))
+        {
+            return false;
+        }
+
+        self.pins.push(pin);
+        true
+    }
+
+    pub fn remove_pin(&mut self, class: &str) -> Option<Pin> {
+        let index = self.pins.iter().position(|p| p.class() == class)?;
+        Some(self.pins.remove(index))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Copy, Debug)]
@@ -318,12 +696,74 @@ pub struct Pin {
     class: String,
     #[getset(get_copy = "pub")]
     direction: Direction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    variadic_class: Option<String>,
 }
 
 impl State {
     pub fn triggered_pin_take(&mut self) -> Option<PinAddress> {
         self.triggered_pin.take()
     }
+
+    /// Make sure that every variadic pin has exactly one unconnected instance
+    /// following its last connected instance.
+    pub fn update_variadic_pins(&mut self) {
+        let patches = &self.patches;
+        for node in self.nodes.iter_mut() {
+            let node_id = node.id().to_string();
+            let is_connected = |class: &str| {
+                patches.iter().any(|p| {
+                    (*p.source().node_id() == node_id && *p.source().pin_class() == class)
+                        || (*p.destination().node_id() == node_id
+                            && *p.destination().pin_class() == class)
+                })
+            };
+
+            for variadic_pin in node.variadic_pins.iter() {
+                let instances: Vec<String> = node
+                    .pins
+                    .iter()
+                    .filter(|p| p.variadic_class.as_deref() == Some(variadic_pin.class()))
+                    .map(|p| p.class().to_string())
+                    .collect();
+                let required = instances
+                    .iter()
+                    .rposition(|class| is_connected(class))
+                    .map_or(1, |last_connected| last_connected + 2);
+
+                if instances.len() > required {
+                    let redundant = &instances[required..];
+                    node.pins.retain(|p| !redundant.contains(p.class()));
+                }
+
+                // Instances may have been removed from the middle, so the new
+                // ones continue from the highest number in use.
+                let mut number = instances
+                    .iter()
+                    .filter_map(|class| variadic_pin.variadic_number_of(class))
+                    .max()
+                    .unwrap_or(0);
+                for _ in instances.len()..required {
+                    number += 1;
+                    while node
+                        .pins
+                        .iter()
+                        .any(|p| variadic_pin.variadic_number_of(p.class()) == Some(number))
+                    {
+                        number += 1;
+                    }
+                    let position = node
+                        .pins
+                        .iter()
+                        .rposition(|p| p.variadic_class.as_deref() == Some(variadic_pin.class()))
+                        .map_or(node.pins.len(), |last_instance| last_instance + 1);
+                    node.pins
+                        .insert(position, variadic_pin.variadic_instance(number));
+                }
+            }
+        }
+    }
 }
 
 impl Pin {
@@ -332,9 +772,27 @@ impl Pin {
             class,
             label: ImStringWrapper::from(label),
             direction,
+            variadic_class: None,
+        }
+    }
+
+    pub fn variadic_instance(&self, number: usize) -> Self {
+        Self {
+            class: format!("{}{}", self.class, number),
+            label: ImStringWrapper::from(format!("{} {}", self.label(), number)),
+            direction: self.direction,
+            variadic_class: Some(self.class.clone()),
         }
     }
 
+    fn is_variadic_class_of(&self, class: &str) -> bool {
+        self.variadic_number_of(class).is_some()
+    }
+
+    fn variadic_number_of(&self, class: &str) -> Option<usize> {
+        class.strip_prefix(self.class.as_str())?.parse().ok()
+    }
+
     pub fn label(&self) -> &str {
         self.label.im_str().to_str()
     }
@@ -344,7 +802,7 @@ impl Pin {
     }
 }
 
-#[derive(Serialize, Deserialize, Getters, Clone, Hash, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Getters, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct PinAddress {
     #[getset(get = "pub")]
     node_id: String,
@@ -363,7 +821,14 @@ pub enum Widget {
     Button(Button),
     TextBox(TextBox),
     Slider(Slider),
+    RangeSlider(RangeSlider),
+    XYPad(XYPad),
+    Envelope(Envelope),
+    Knob(Knob),
+    NumberInput(NumberInput),
     DropDown(DropDown),
+    Plot(Plot),
+    Image(Image),
     Canvas(Canvas),
 }
 
@@ -373,7 +838,14 @@ impl Widget {
             Widget::Button(button) => button.key(),
             Widget::TextBox(text_box) => text_box.key(),
             Widget::Slider(slider) => slider.key(),
+            Widget::RangeSlider(range_slider) => range_slider.key(),
+            Widget::XYPad(xy_pad) => xy_pad.key(),
+            Widget::Envelope(envelope) => envelope.key(),
+            Widget::Knob(knob) => knob.key(),
+            Widget::NumberInput(number_input) => number_input.key(),
             Widget::DropDown(drop_down) => drop_down.key(),
+            Widget::Plot(plot) => plot.key(),
+            Widget::Image(image) => image.key(),
             Widget::Canvas(canvas) => canvas.key(),
         }
     }
@@ -390,10 +862,38 @@ impl Widget {
         matches!(self, Widget::Slider(_))
     }
 
+    pub fn is_range_slider(&self) -> bool {
+        matches!(self, Widget::RangeSlider(_))
+    }
+
+    pub fn is_xy_pad(&self) -> bool {
+        matches!(self, Widget::XYPad(_))
+    }
+
+    pub fn is_envelope(&self) -> bool {
+        matches!(self, Widget::Envelope(_))
+    }
+
+    pub fn is_knob(&self) -> bool {
+        matches!(self, Widget::Knob(_))
+    }
+
+    pub fn is_number_input(&self) -> bool {
+        matches!(self, Widget::NumberInput(_))
+    }
+
     pub fn is_dropdown(&self) -> bool {
         matches!(self, Widget::DropDown(_))
     }
 
+    pub fn is_plot(&self) -> bool {
+        matches!(self, Widget::Plot(_))
+    }
+
+    pub fn is_image(&self) -> bool {
+        matches!(self, Widget::Image(_))
+    }
+
     pub fn is_canvas(&self) -> bool {
         matches!(self, Widget::Canvas(_))
     }
@@ -433,6 +933,10 @@ impl Button {
     pub fn label_im(&self) -> &ImString {
         self.label.im_str()
     }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = ImStringWrapper::from(label);
+    }
 }
 
 #[derive(
@@ -446,7 +950,7 @@ pub struct TextBox {
     #[getset(get_copy = "pub")]
     size: [f32; 2],
     content: ImStringWrapper,
-    #[getset(get_copy = "pub")]
+    #[getset(get_copy = "pub", set = "pub")]
     read_only: bool,
 }
 
@@ -484,9 +988,50 @@ pub struct Slider {
     max: f32,
     #[getset(get_copy = "pub")]
     value: f32,
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    default: f32,
     display_format: ImStringWrapper,
     #[getset(get_copy = "pub")]
     width: f32,
+    #[serde(default, skip_serializing_if = "Scale::is_linear")]
+    #[getset(get_copy = "pub")]
+    scale: Scale,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    #[getset(get = "pub")]
+    unit: String,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug, Default)]
+pub enum Scale {
+    #[default]
+    Linear,
+    Logarithmic,
+    Exponential {
+        curve: f32,
+    },
+    Steps {
+        step: f32,
+    },
+}
+
+impl Scale {
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Scale::Linear)
+    }
+
+    fn assert_fits(self, min: f32) {
+        match self {
+            Scale::Linear => (),
+            Scale::Logarithmic => {
+                assert!(min > 0.0, "Logarithmic scale requires positive limits")
+            }
+            Scale::Exponential { curve } => {
+                assert!(curve > 0.0, "Curve of exponential scale must be positive")
+            }
+            Scale::Steps { step } => assert!(step > 0.0, "Step must be positive"),
+        }
+    }
 }
 
 impl Slider {
@@ -508,12 +1053,36 @@ impl Slider {
             min,
             max,
             value,
+            default: value,
             display_format: ImStringWrapper::from(display_format),
             width,
+            scale: Scale::Linear,
+            unit: String::new(),
         }
     }
 
+    pub fn with_scale(mut self, scale: Scale) -> Self {
+        scale.assert_fits(self.min);
+        self.scale = scale;
+        self.set_value(self.value);
+        self
+    }
+
+    pub fn with_unit(mut self, unit: String) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Set the value, keeping it within limits and snapping it to the closest
+    /// step if the scale is stepped.
     pub fn set_value(&mut self, value: f32) {
+        let value = match self.scale {
+            Scale::Steps { step } => {
+                let last_step = ((self.max - self.min) / step).floor();
+                self.min + f32::min(((value - self.min) / step).round(), last_step) * step
+            }
+            _ => value,
+        };
         if value < self.min {
             self.value = self.min;
         } else if value > self.max {
@@ -523,32 +1092,385 @@ impl Slider {
         }
     }
 
+    /// Whether the given limits are valid for this slider.
+    pub fn accepts_range(&self, min: f32, max: f32) -> bool {
+        min < max && !(self.scale == Scale::Logarithmic && min <= 0.0)
+    }
+
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        assert!(min < max, "Lower limit must be below the upper limit");
+        self.scale.assert_fits(min);
+        self.min = min;
+        self.max = max;
+        self.set_value(self.value);
+    }
+
     pub fn display_format(&self) -> &str {
         self.display_format.im_str().to_str()
     }
 
+    pub fn set_display_format(&mut self, display_format: String) {
+        self.display_format = ImStringWrapper::from(display_format);
+    }
+
     pub fn display_format_im(&self) -> &ImString {
         &self.display_format.im_str()
     }
 }
 
 #[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
-pub struct DropDown {
+pub struct RangeSlider {
     #[getset(get = "pub")]
     key: String,
-    #[getset(get = "pub")]
-    value: String,
-    #[getset(get = "pub")]
-    items: Vec<DropDownItem>,
+    #[getset(get_copy = "pub")]
+    min: f32,
+    #[getset(get_copy = "pub")]
+    max: f32,
+    #[getset(get_copy = "pub")]
+    value: (f32, f32),
+    display_format: ImStringWrapper,
+    #[getset(get_copy = "pub")]
+    width: f32,
 }
 
-impl DropDown {
-    pub fn new(key: String, items: Vec<DropDownItem>) -> Self {
-        assert!(!items.is_empty(), "items must not be empty");
+impl RangeSlider {
+    pub fn new(
+        key: String,
+        min: f32,
+        max: f32,
+        value: (f32, f32),
+        display_format: String,
+        width: f32,
+    ) -> Self {
+        assert!(min < max, "Lower limit must be below the upper limit");
+        assert!(
+            min <= value.0 && value.1 <= max,
+            "Value must be within min and max"
+        );
+        assert!(
+            value.0 <= value.1,
+            "Lower end of the range must not be above the upper end"
+        );
         Self {
             key,
-            value: items[0].value.clone(),
-            items,
+            min,
+            max,
+            value,
+            display_format: ImStringWrapper::from(display_format),
+            width,
+        }
+    }
+
+    /// Set both ends of the range, keeping them within limits. If the ends
+    /// cross, the one that moved is stopped at the other.
+    pub fn set_value(&mut self, value: (f32, f32)) {
+        let clamp = |v: f32| f32::min(f32::max(v, self.min), self.max);
+        let (mut low, mut high) = (clamp(value.0), clamp(value.1));
+        if low > high {
+            if (low - self.value.0).abs() > f32::EPSILON {
+                low = high;
+            } else {
+                high = low;
+            }
+        }
+        self.value = (low, high);
+    }
+
+    pub fn display_format(&self) -> &str {
+        self.display_format.im_str().to_str()
+    }
+
+    pub fn display_format_im(&self) -> &ImString {
+        self.display_format.im_str()
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct XYPad {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    x_range: (f32, f32),
+    #[getset(get_copy = "pub")]
+    y_range: (f32, f32),
+    #[getset(get_copy = "pub")]
+    value: (f32, f32),
+    #[getset(get_copy = "pub")]
+    size: f32,
+}
+
+impl XYPad {
+    pub fn new(
+        key: String,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+        value: (f32, f32),
+        size: f32,
+    ) -> Self {
+        assert!(
+            x_range.0 < x_range.1 && y_range.0 < y_range.1,
+            "Lower limit must be below the upper limit"
+        );
+        assert!(
+            x_range.0 <= value.0
+                && value.0 <= x_range.1
+                && y_range.0 <= value.1
+                && value.1 <= y_range.1,
+            "Value must be within min and max"
+        );
+        Self {
+            key,
+            x_range,
+            y_range,
+            value,
+            size,
+        }
+    }
+
+    pub fn set_value(&mut self, value: (f32, f32)) {
+        self.value = (
+            value.0.clamp(self.x_range.0, self.x_range.1),
+            value.1.clamp(self.y_range.0, self.y_range.1),
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct Envelope {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    x_range: (f32, f32),
+    #[getset(get_copy = "pub")]
+    y_range: (f32, f32),
+    #[getset(get = "pub")]
+    points: Vec<(f32, f32)>,
+    /// Curvature of each segment, one less than there are points.
+    #[getset(get = "pub")]
+    curvatures: Vec<f32>,
+    #[getset(get_copy = "pub")]
+    curved: bool,
+    #[getset(get_copy = "pub")]
+    size: [f32; 2],
+}
+
+impl Envelope {
+    pub fn new(
+        key: String,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+        points: Vec<(f32, f32)>,
+        curved: bool,
+        size: [f32; 2],
+    ) -> Self {
+        assert!(
+            x_range.0 < x_range.1 && y_range.0 < y_range.1,
+            "Lower limit must be below the upper limit"
+        );
+        let mut envelope = Self {
+            key,
+            x_range,
+            y_range,
+            points: Vec::new(),
+            curvatures: Vec::new(),
+            curved,
+            size,
+        };
+        envelope.set_points(points);
+        envelope
+    }
+
+    /// Set breakpoints, resetting all segments to be linear.
+    pub fn set_points(&mut self, points: Vec<(f32, f32)>) {
+        self.set_points_with_curvatures(points.into_iter().map(|(x, y)| (x, y, 0.0)).collect());
+    }
+
+    /// Set breakpoints together with curvatures of segments starting at them.
+    /// Points are kept within limits and ordered by `x`, points with
+    /// non-finite coordinates are dropped.
+    pub fn set_points_with_curvatures(&mut self, points: Vec<(f32, f32, f32)>) {
+        let mut points: Vec<_> = points
+            .into_iter()
+            .filter(|(x, y, _)| x.is_finite() && y.is_finite())
+            .map(|(x, y, c)| {
+                (
+                    x.clamp(self.x_range.0, self.x_range.1),
+                    y.clamp(self.y_range.0, self.y_range.1),
+                    c.clamp(-1.0, 1.0),
+                )
+            })
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let segments = points.len().saturating_sub(1);
+        self.points = points.iter().map(|(x, y, _)| (*x, *y)).collect();
+        self.curvatures = points.iter().take(segments).map(|(_, _, c)| *c).collect();
+    }
+
+    pub fn points_with_curvatures(&self) -> Vec<(f32, f32, f32)> {
+        self.points
+            .iter()
+            .zip(self.curvatures.iter().chain(std::iter::once(&0.0)))
+            .map(|((x, y), c)| (*x, *y, *c))
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct Knob {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    min: f32,
+    #[getset(get_copy = "pub")]
+    max: f32,
+    #[getset(get_copy = "pub")]
+    value: f32,
+    display_format: ImStringWrapper,
+    #[getset(get_copy = "pub")]
+    size: f32,
+}
+
+impl Knob {
+    pub fn new(
+        key: String,
+        min: f32,
+        max: f32,
+        value: f32,
+        display_format: String,
+        size: f32,
+    ) -> Self {
+        assert!(min < max, "Lower limit must be below the upper limit");
+        assert!(
+            min <= value && value <= max,
+            "Value must be within min and max"
+        );
+        Self {
+            key,
+            min,
+            max,
+            value,
+            display_format: ImStringWrapper::from(display_format),
+            size,
+        }
+    }
+
+    pub fn set_value(&mut self, value: f32) {
+        self.value = f32::min(f32::max(value, self.min), self.max);
+    }
+
+    pub fn display_format(&self) -> &str {
+        self.display_format.im_str().to_str()
+    }
+
+    pub fn display_format_im(&self) -> &ImString {
+        self.display_format.im_str()
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum Number {
+    F32(f32),
+    I32(i32),
+}
+
+impl Number {
+    pub fn is_i32(self) -> bool {
+        matches!(self, Number::I32(_))
+    }
+
+    fn is_same_type(self, other: Number) -> bool {
+        self.is_i32() == other.is_i32()
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct NumberInput {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    value: Number,
+    #[getset(get_copy = "pub")]
+    min: Option<Number>,
+    #[getset(get_copy = "pub")]
+    max: Option<Number>,
+    #[getset(get_copy = "pub")]
+    step: f32,
+    display_format: ImStringWrapper,
+    #[getset(get_copy = "pub")]
+    width: f32,
+}
+
+impl NumberInput {
+    pub fn new(
+        key: String,
+        value: Number,
+        min: Option<Number>,
+        max: Option<Number>,
+        step: f32,
+        display_format: String,
+        width: f32,
+    ) -> Self {
+        assert!(
+            min.iter().chain(max.iter()).all(|l| l.is_same_type(value)),
+            "Limits must be of the same type as the value"
+        );
+        if let (Some(min), Some(max)) = (min, max) {
+            assert!(min < max, "Lower limit must be below the upper limit");
+        }
+        assert!(
+            min.iter().all(|&min| min <= value) && max.iter().all(|&max| value <= max),
+            "Value must be within min and max"
+        );
+        Self {
+            key,
+            value,
+            min,
+            max,
+            step,
+            display_format: ImStringWrapper::from(display_format),
+            width,
+        }
+    }
+
+    pub fn set_value(&mut self, value: Number) {
+        assert!(
+            value.is_same_type(self.value),
+            "Value must be of the same type as the current one"
+        );
+        self.value = match (self.min, self.max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        };
+    }
+
+    pub fn display_format(&self) -> &str {
+        self.display_format.im_str().to_str()
+    }
+
+    pub fn display_format_im(&self) -> &ImString {
+        self.display_format.im_str()
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct DropDown {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get = "pub")]
+    value: String,
+    #[getset(get = "pub")]
+    items: Vec<DropDownItem>,
+}
+
+impl DropDown {
+    pub fn new(key: String, items: Vec<DropDownItem>) -> Self {
+        assert!(!items.is_empty(), "items must not be empty");
+        Self {
+            key,
+            value: items[0].value.clone(),
+            items,
         }
     }
 
@@ -559,6 +1481,14 @@ impl DropDown {
         );
         self.value = value;
     }
+
+    pub fn set_items(&mut self, items: Vec<DropDownItem>) {
+        assert!(!items.is_empty(), "items must not be empty");
+        if !items.iter().any(|i| i.value == self.value) {
+            self.value = items[0].value.clone();
+        }
+        self.items = items;
+    }
 }
 
 #[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
@@ -575,6 +1505,150 @@ impl DropDownItem {
     }
 }
 
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct Plot {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    y_range: (f32, f32),
+    #[getset(get = "pub")]
+    series: Vec<PlotSeries>,
+    #[getset(get_copy = "pub")]
+    axes: bool,
+    #[getset(get_copy = "pub")]
+    grid: Option<[u32; 2]>,
+    #[getset(get_copy = "pub")]
+    size: [f32; 2],
+    /// List of samples for each of the series.
+    #[getset(get = "pub")]
+    samples: Vec<Vec<f32>>,
+}
+
+impl Plot {
+    pub fn new(key: String, y_range: (f32, f32), series: Vec<PlotSeries>, size: [f32; 2]) -> Self {
+        assert!(
+            y_range.0 < y_range.1,
+            "Lower limit must be below the upper limit"
+        );
+        assert!(!series.is_empty(), "Plot must have at least one series");
+        let samples = vec![Vec::new(); series.len()];
+        Self {
+            key,
+            y_range,
+            series,
+            axes: false,
+            grid: None,
+            size,
+            samples,
+        }
+    }
+
+    pub fn with_axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    pub fn with_grid(mut self, grid: Option<[u32; 2]>) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    pub fn set_samples(&mut self, samples: Vec<Vec<f32>>) {
+        assert_eq!(
+            samples.len(),
+            self.series.len(),
+            "Plot requires a list of samples for each of its series"
+        );
+        self.samples = samples;
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct PlotSeries {
+    #[getset(get = "pub")]
+    label: String,
+    #[getset(get_copy = "pub")]
+    color: [f32; 4],
+}
+
+impl PlotSeries {
+    pub fn new(label: String, color: [f32; 4]) -> Self {
+        Self { label, color }
+    }
+}
+
+#[derive(Serialize, Deserialize, Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct Image {
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get_copy = "pub")]
+    size: [f32; 2],
+    // Images are fed by the backend while running, they are not saved.
+    #[serde(skip)]
+    #[getset(get = "pub")]
+    data: Option<ImageData>,
+    /// Incremented on every change of the data, so it is cheap to tell
+    /// whether the image needs to be uploaded again.
+    #[serde(skip)]
+    #[getset(get_copy = "pub")]
+    revision: u64,
+}
+
+impl Image {
+    pub fn new(key: String, size: [f32; 2]) -> Self {
+        Self {
+            key,
+            size,
+            data: None,
+            revision: 0,
+        }
+    }
+
+    pub fn set_data(&mut self, data: ImageData) {
+        self.data = Some(data);
+        self.revision += 1;
+    }
+}
+
+#[derive(Getters, CopyGetters, Clone, PartialEq)]
+pub struct ImageData {
+    #[getset(get_copy = "pub")]
+    width: u32,
+    #[getset(get_copy = "pub")]
+    height: u32,
+    #[getset(get = "pub")]
+    data: Vec<u8>,
+}
+
+impl ImageData {
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Image must be at least one pixel wide and high"
+        );
+        assert_eq!(
+            data.len(),
+            width as usize * height as usize * 4,
+            "Image data must have 4 bytes per each pixel"
+        );
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+// Printing all the pixels would make debug output unreadable.
+impl std::fmt::Debug for ImageData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageData")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Getters, CopyGetters, Setters, Clone, PartialEq, Debug)]
 pub struct Canvas {
     #[getset(get = "pub")]
@@ -583,6 +1657,12 @@ pub struct Canvas {
     size: [f32; 2],
     #[getset(get = "pub", set = "pub")]
     dots: Vec<(f32, f32)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub", set = "pub")]
+    shapes: Vec<Shape>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[getset(get_copy = "pub")]
+    interactive: bool,
 }
 
 impl Canvas {
@@ -591,11 +1671,122 @@ impl Canvas {
             key,
             size,
             dots: Vec::new(),
+            shapes: Vec::new(),
+            interactive: false,
+        }
+    }
+
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Shape {
+    Line {
+        from: [f32; 2],
+        to: [f32; 2],
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Polyline {
+        points: Vec<[f32; 2]>,
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Rect {
+        min: [f32; 2],
+        max: [f32; 2],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+        color: [f32; 4],
+        filled: bool,
+    },
+    Triangle {
+        points: [[f32; 2]; 3],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Text {
+        position: [f32; 2],
+        color: [f32; 4],
+        text: String,
+    },
+}
+
+impl From<m::Shape> for Shape {
+    fn from(model: m::Shape) -> Self {
+        match model {
+            m::Shape::Line {
+                from,
+                to,
+                color,
+                thickness,
+            } => Shape::Line {
+                from,
+                to,
+                color,
+                thickness,
+            },
+            m::Shape::Polyline {
+                points,
+                color,
+                thickness,
+            } => Shape::Polyline {
+                points,
+                color,
+                thickness,
+            },
+            m::Shape::Rect {
+                min,
+                max,
+                color,
+                filled,
+            } => Shape::Rect {
+                min,
+                max,
+                color,
+                filled,
+            },
+            m::Shape::Circle {
+                center,
+                radius,
+                color,
+                filled,
+            } => Shape::Circle {
+                center,
+                radius,
+                color,
+                filled,
+            },
+            m::Shape::Triangle {
+                points,
+                color,
+                filled,
+            } => Shape::Triangle {
+                points,
+                color,
+                filled,
+            },
+            m::Shape::Text {
+                position,
+                color,
+                text,
+            } => Shape::Text {
+                position,
+                color,
+                text,
+            },
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Getters, Hash, PartialEq, Eq, Clone, Debug)]
+#[derive(Serialize, Deserialize, Getters, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub struct Patch {
     #[getset(get = "pub")]
     source: PinAddress,
@@ -630,6 +1821,63 @@ impl State {
 
         Ok(patch)
     }
+
+    /// Split the given patch into two, leading from its source to the input of
+    /// the node and from the output of the node to its destination. Only nodes
+    /// with a single input and a single output pin can be inserted. Both of the
+    /// new patches go through the validation of `add_patch`; if any of them is
+    /// refused, the state is left untouched.
+    pub fn insert_node_into_patch(&mut self, node_id: &str, patch: &Patch) -> Result<(), String> {
+        if !self.patches.contains(patch) {
+            return Err("Patch does not exist".to_owned());
+        }
+
+        let node = must_find_node(self.nodes(), node_id);
+        let inputs: Vec<_> = node
+            .pins()
+            .iter()
+            .filter(|p| p.direction() == Direction::Input)
+            .collect();
+        let outputs: Vec<_> = node
+            .pins()
+            .iter()
+            .filter(|p| p.direction() == Direction::Output)
+            .collect();
+        let (input, output) = match (inputs.as_slice(), outputs.as_slice()) {
+            ([input], [output]) => (
+                PinAddress::new(node_id.to_owned(), input.class().to_owned()),
+                PinAddress::new(node_id.to_owned(), output.class().to_owned()),
+            ),
+            _ => {
+                return Err(
+                    "Only nodes with a single input and output can be inserted into a patch"
+                        .to_owned(),
+                )
+            }
+        };
+
+        let original_patches = self.patches.clone();
+        let result = self
+            .add_patch(patch.source().clone(), input)
+            .and_then(|_| self.add_patch(output, patch.destination().clone()));
+        if let Err(err) = result {
+            self.patches = original_patches;
+            return Err(err);
+        }
+
+        self.patches.remove(patch);
+        self.drop_stale_waypoints();
+
+        Ok(())
+    }
+}
+
+impl State {
+    /// Remove waypoints of patches which no longer exist.
+    pub fn drop_stale_waypoints(&mut self) {
+        let patches = &self.patches;
+        self.waypoints.retain(|p, _| patches.contains(p));
+    }
 }
 
 fn must_find_node<'a>(nodes: &'a [Node], id: &str) -> &'a Node {
@@ -654,6 +1902,434 @@ impl Patch {
     }
 }
 
+#[derive(Serialize, Deserialize, Getters, Clone, PartialEq, Debug)]
+pub struct Frame {
+    #[getset(get = "pub")]
+    id: String,
+    title: ImStringWrapper,
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    pub color: [f32; 4],
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+impl Frame {
+    pub fn new(
+        id: String,
+        title: String,
+        position: [f32; 2],
+        size: [f32; 2],
+        color: [f32; 4],
+    ) -> Self {
+        Self {
+            id,
+            title: ImStringWrapper::from(title),
+            position,
+            size,
+            color,
+            parent: None,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        self.title.im_str().to_str()
+    }
+
+    pub fn title_im(&self) -> &ImString {
+        self.title.im_str()
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = ImStringWrapper::from(title);
+    }
+
+    pub fn contains(&self, point: [f32; 2]) -> bool {
+        point[0] >= self.position[0]
+            && point[1] >= self.position[1]
+            && point[0] <= self.position[0] + self.size[0]
+            && point[1] <= self.position[1] + self.size[1]
+    }
+}
+
+impl State {
+    pub fn add_frame(&mut self, frame: Frame) {
+        assert!(
+            !self.frames.iter().any(|f| f.id() == frame.id()),
+            "Each Frame within a state must have its unique id"
+        );
+
+        self.frames.push(frame);
+    }
+
+    pub fn next_frame_id(&self) -> String {
+        (0..)
+            .map(|i| format!("frame:{}", i))
+            .find(|id| !self.frames.iter().any(|f| f.id() == id))
+            .unwrap()
+    }
+}
+
+/// Subgraph groups nodes and other subgraphs. Its members reference it through
+/// their `parent` field. Pins of a subgraph are not stored, they are derived
+/// from patches crossing its boundary.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Subgraph {
+    id: ImStringWrapper,
+    label: ImStringWrapper,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub position: [f32; 2],
+}
+
+impl Subgraph {
+    pub fn new(id: String, label: String, parent: Option<String>, position: [f32; 2]) -> Self {
+        Self {
+            id: ImStringWrapper::from(id),
+            label: ImStringWrapper::from(label),
+            parent,
+            position,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        self.id.im_str().to_str()
+    }
+
+    pub fn id_im(&self) -> &ImString {
+        self.id.im_str()
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.im_str().to_str()
+    }
+
+    pub fn label_im(&self) -> &ImString {
+        self.label.im_str()
+    }
+}
+
+/// Pin exposed on a subgraph. It represents a pin of an inner node which is
+/// patched to a node outside of the subgraph.
+#[derive(Getters, CopyGetters, Clone, PartialEq, Debug)]
+pub struct SubgraphPin {
+    #[getset(get = "pub")]
+    address: PinAddress,
+    #[getset(get = "pub")]
+    label: ImString,
+    #[getset(get_copy = "pub")]
+    direction: Direction,
+}
+
+impl State {
+    pub fn add_subgraph(&mut self, subgraph: Subgraph) {
+        assert!(
+            !self.subgraphs.iter().any(|s| s.id() == subgraph.id()),
+            "Each Subgraph within a state must have its unique id"
+        );
+
+        self.subgraphs.push(subgraph);
+    }
+
+    pub fn next_subgraph_id(&self) -> String {
+        (0..)
+            .map(|i| format!("subgraph:{}", i))
+            // Subgraphs share the namespace with nodes, a template may be
+            // called "subgraph" too.
+            .find(|id| {
+                !self.subgraphs.iter().any(|s| s.id() == id)
+                    && !self.nodes.iter().any(|n| n.id() == id)
+            })
+            .unwrap()
+    }
+
+    /// Find the subgraph containing node or subgraph of the given id.
+    pub fn parent_of(&self, id: &str) -> Option<&str> {
+        if let Some(node) = self.nodes.iter().find(|n| n.id() == id) {
+            node.parent.as_deref()
+        } else if let Some(subgraph) = self.subgraphs.iter().find(|s| s.id() == id) {
+            subgraph.parent.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Whether the node or subgraph of the given id is nested, directly or
+    /// indirectly, in the given subgraph.
+    pub fn is_inside(&self, id: &str, subgraph_id: &str) -> bool {
+        let mut current = self.parent_of(id);
+        while let Some(parent) = current {
+            if parent == subgraph_id {
+                return true;
+            }
+            current = self.parent_of(parent);
+        }
+        false
+    }
+
+    /// Find the node or subgraph representing the given item on the given level
+    /// of nesting. That is the item itself if it is placed directly on the
+    /// level, or the subgraph on the level which contains the item. `None` is
+    /// returned if the item is not nested within the level.
+    pub fn representative_on_level(&self, id: &str, level: Option<&str>) -> Option<String> {
+        let mut current = id;
+        loop {
+            let parent = self.parent_of(current);
+            if parent == level {
+                return Some(current.to_owned());
+            }
+            current = parent?;
+        }
+    }
+
+    /// List subgraphs leading from the root to the given subgraph, including
+    /// it.
+    pub fn subgraph_path(&self, subgraph_id: &str) -> Vec<&Subgraph> {
+        let mut path = Vec::new();
+        let mut current = Some(subgraph_id);
+        while let Some(id) = current {
+            let subgraph = self
+                .subgraphs
+                .iter()
+                .find(|s| s.id() == id)
+                .expect("Subgraph path must reference existing subgraphs");
+            path.push(subgraph);
+            current = subgraph.parent.as_deref();
+        }
+        path.reverse();
+        path
+    }
+
+    pub fn subgraph_pins(&self, subgraph_id: &str) -> Vec<SubgraphPin> {
+        let mut addresses: Vec<(Direction, &PinAddress)> = self
+            .patches
+            .iter()
+            .filter_map(|p| {
+                let source_inside = self.is_inside(p.source().node_id(), subgraph_id);
+                let destination_inside = self.is_inside(p.destination().node_id(), subgraph_id);
+                if destination_inside && !source_inside {
+                    Some((Direction::Input, p.destination()))
+                } else if source_inside && !destination_inside {
+                    Some((Direction::Output, p.source()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        addresses.sort_by(|a, b| {
+            let key_a = (a.0 == Direction::Output, a.1.node_id(), a.1.pin_class());
+            let key_b = (b.0 == Direction::Output, b.1.node_id(), b.1.pin_class());
+            key_a.cmp(&key_b)
+        });
+        addresses.dedup();
+
+        addresses
+            .into_iter()
+            .map(|(direction, address)| {
+                let node = must_find_node(&self.nodes, address.node_id());
+                let pin = must_find_pin(node.pins(), address.pin_class());
+                SubgraphPin {
+                    address: address.clone(),
+                    label: ImString::from(format!("{} {}", node.label(), pin.label())),
+                    direction,
+                }
+            })
+            .collect()
+    }
+
+    /// Move given nodes and subgraphs into a new subgraph placed on the
+    /// currently open level. Returns the id of the new subgraph, or `None` if
+    /// none of the given items is available on the level.
+    pub fn group_into_subgraph(&mut self, ids: &[String], label: String) -> Option<String> {
+        let level = self.open_subgraph.clone();
+        let members_positions: Vec<[f32; 2]> = self
+            .nodes
+            .iter()
+            .filter(|n| ids.iter().any(|id| id == n.id()) && n.parent == level)
+            .map(|n| n.position)
+            .chain(
+                self.subgraphs
+                    .iter()
+                    .filter(|s| ids.iter().any(|id| id == s.id()) && s.parent == level)
+                    .map(|s| s.position),
+            )
+            .collect();
+        if members_positions.is_empty() {
+            return None;
+        }
+        let position = [
+            members_positions
+                .iter()
+                .map(|p| p[0])
+                .fold(f32::INFINITY, f32::min),
+            members_positions
+                .iter()
+                .map(|p| p[1])
+                .fold(f32::INFINITY, f32::min),
+        ];
+
+        // Frames enclosing only the grouped members follow them.
+        let others_positions: Vec<[f32; 2]> = self
+            .nodes
+            .iter()
+            .filter(|n| !ids.iter().any(|id| id == n.id()) && n.parent == level)
+            .map(|n| n.position)
+            .chain(
+                self.subgraphs
+                    .iter()
+                    .filter(|s| !ids.iter().any(|id| id == s.id()) && s.parent == level)
+                    .map(|s| s.position),
+            )
+            .collect();
+        let frames_ids: Vec<String> = self
+            .frames
+            .iter()
+            .filter(|f| f.parent == level)
+            .filter(|f| {
+                members_positions.iter().any(|p| f.contains(*p))
+                    && !others_positions.iter().any(|p| f.contains(*p))
+            })
+            .map(|f| f.id().clone())
+            .collect();
+
+        let id = self.next_subgraph_id();
+        self.add_subgraph(Subgraph::new(id.clone(), label, level.clone(), position));
+
+        self.nodes
+            .iter_mut()
+            .filter(|n| ids.iter().any(|i| i == n.id()) && n.parent == level)
+            .for_each(|n| n.parent = Some(id.clone()));
+        self.subgraphs
+            .iter_mut()
+            .filter(|s| ids.iter().any(|i| i == s.id()) && s.parent == level)
+            .for_each(|s| s.parent = Some(id.clone()));
+
+        self.frames
+            .iter_mut()
+            .filter(|f| frames_ids.contains(f.id()))
+            .for_each(|f| f.parent = Some(id.clone()));
+
+        Some(id)
+    }
+
+    /// Remove the subgraph, moving all its members and frames to its parent.
+    pub fn ungroup_subgraph(&mut self, subgraph_id: &str) {
+        let parent = if let Some(subgraph) = self.subgraphs.iter().find(|s| s.id() == subgraph_id) {
+            subgraph.parent.clone()
+        } else {
+            return;
+        };
+
+        self.subgraphs.retain(|s| s.id() != subgraph_id);
+        self.nodes
+            .iter_mut()
+            .filter(|n| n.parent.as_deref() == Some(subgraph_id))
+            .for_each(|n| n.parent = parent.clone());
+        self.subgraphs
+            .iter_mut()
+            .filter(|s| s.parent.as_deref() == Some(subgraph_id))
+            .for_each(|s| s.parent = parent.clone());
+        self.frames
+            .iter_mut()
+            .filter(|f| f.parent.as_deref() == Some(subgraph_id))
+            .for_each(|f| f.parent = parent.clone());
+    }
+
+    /// Remove subgraphs left without any members, together with frames drawn
+    /// inside them. If the open subgraph gets removed, its parent is opened
+    /// instead.
+    pub fn drop_empty_subgraphs(&mut self) {
+        loop {
+            let empty = self
+                .subgraphs
+                .iter()
+                .find(|s| {
+                    let is_member = |parent: &Option<String>| parent.as_deref() == Some(s.id());
+                    !self.nodes.iter().any(|n| is_member(&n.parent))
+                        && !self.subgraphs.iter().any(|c| is_member(&c.parent))
+                })
+                .map(|s| (s.id().to_owned(), s.parent.clone()));
+            // Removal of a subgraph may leave its parent empty too.
+            let (subgraph_id, parent) = match empty {
+                Some(empty) => empty,
+                None => break,
+            };
+
+            if self.open_subgraph.as_deref() == Some(subgraph_id.as_str()) {
+                self.open_subgraph = parent;
+            }
+            self.selection.retain(|id| *id != subgraph_id);
+            self.subgraphs.retain(|s| s.id() != subgraph_id);
+
+            self.frames
+                .retain(|f| f.parent.as_ref() != Some(&subgraph_id));
+            if matches!(&self.triggered_frame, Some(id) if !self.frames.iter().any(|f| f.id() == id))
+            {
+                self.triggered_frame = None;
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct NodeRename {
+    pub node_id: String,
+    pub buffer: String,
+}
+
+impl NodeRename {
+    pub fn new(node_id: String, buffer: String) -> Self {
+        Self { node_id, buffer }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WidgetAddress {
+    pub node_id: String,
+    pub key: String,
+}
+
+impl WidgetAddress {
+    pub fn new(node_id: String, key: String) -> Self {
+        Self { node_id, key }
+    }
+}
+
+/// Mouse input received by an interactive canvas, kept only until it gets
+/// reported.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CanvasInput {
+    pub address: WidgetAddress,
+    pub position: [f32; 2],
+    pub event: CanvasEvent,
+}
+
+impl CanvasInput {
+    pub fn new(address: WidgetAddress, position: [f32; 2], event: CanvasEvent) -> Self {
+        Self {
+            address,
+            position,
+            event,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CanvasEvent {
+    Press(MouseButton),
+    Drag(MouseButton),
+    Release(MouseButton),
+    Scroll(f32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct FileDialog {
     pub buffer: String,
@@ -686,6 +2362,26 @@ impl Default for FileDialog {
     }
 }
 
+/// Grid drawn on the background of the canvas. When snapping is enabled,
+/// dragged nodes are aligned to its intersections.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct Grid {
+    pub visible: bool,
+    pub snap: bool,
+}
+
+impl Grid {
+    pub const SPACING: f32 = 20.0;
+
+    /// Round the given position to the nearest intersection of the grid.
+    pub fn snapped(position: [f32; 2]) -> [f32; 2] {
+        [
+            (position[0] / Self::SPACING).round() * Self::SPACING,
+            (position[1] / Self::SPACING).round() * Self::SPACING,
+        ]
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 #[serde(from = "String")]
 #[serde(into = "String")]
@@ -756,6 +2452,31 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn remove_node_template() {
+            let mut state = State::default();
+            state.add_node_template(NodeTemplate::new(
+                "Label".to_owned(),
+                "class".to_owned(),
+                true,
+                vec![],
+                vec![],
+            ));
+
+            let removed = state.remove_node_template("class");
+
+            assert_eq!(removed.unwrap().class(), "class");
+            assert!(state.node_templates().is_empty());
+            assert!(!state.has_node_template("class"));
+        }
+
+        #[test]
+        fn remove_nonexistent_node_template() {
+            let mut state = State::default();
+
+            assert!(state.remove_node_template("class").is_none());
+        }
+
         #[test]
         fn add_nodes() {
             let mut state = State::default();
@@ -839,6 +2560,53 @@ mod tests {
             );
         }
 
+        #[test]
+        fn build_node_with_variadic_pins() {
+            let node_template = NodeTemplate::new(
+                "Label".to_owned(),
+                "class1".to_owned(),
+                true,
+                vec![Pin::new(
+                    "Output".to_owned(),
+                    "out".to_owned(),
+                    Direction::Output,
+                )],
+                vec![],
+            )
+            .with_variadic_pins(vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )]);
+
+            let node = node_template.instantiate([0.0, 0.0]);
+
+            assert_eq!(node.pins().len(), 2);
+            assert_eq!(pin_label(node.pins(), "in1"), "Input 1");
+            assert_eq!(node.pins()[1].variadic_class().as_deref(), Some("in"));
+        }
+
+        #[test]
+        #[should_panic(expected = "Variadic pin must not collide with other pins")]
+        fn panic_on_variadic_pins_colliding_with_pins() {
+            let _node_template = NodeTemplate::new(
+                "Label".to_owned(),
+                "class1".to_owned(),
+                true,
+                vec![Pin::new(
+                    "Input 2".to_owned(),
+                    "in2".to_owned(),
+                    Direction::Input,
+                )],
+                vec![],
+            )
+            .with_variadic_pins(vec![Pin::new(
+                "Input".to_owned(),
+                "in".to_owned(),
+                Direction::Input,
+            )]);
+        }
+
         #[test]
         #[should_panic(expected = "Each widget must have its unique key")]
         fn panic_on_duplicated_widgets() {
@@ -897,16 +2665,29 @@ mod tests {
         }
 
         #[test]
-        fn turn_off() {
+        fn turn_off() {
+            let mut button = Button::new(
+                "Button".to_owned(),
+                "key".to_owned(),
+                ButtonActivationMode::OnClick,
+            );
+
+            button.set_active(false);
+
+            assert!(!button.active());
+        }
+
+        #[test]
+        fn change_label() {
             let mut button = Button::new(
                 "Button".to_owned(),
                 "key".to_owned(),
                 ButtonActivationMode::OnClick,
             );
 
-            button.set_active(false);
+            button.set_label("Renamed".to_owned());
 
-            assert!(!button.active());
+            assert_eq!(button.label(), "Renamed");
         }
     }
 
@@ -993,6 +2774,408 @@ mod tests {
 
             assert_eq!(slider.value(), 10.0);
         }
+
+        #[test]
+        fn set_range_clamping_value() {
+            let mut slider =
+                Slider::new("key".to_owned(), 0.0, 10.0, 5.0, "%.2f".to_owned(), 120.0);
+
+            slider.set_range(6.0, 20.0);
+
+            assert_eq!(slider.min(), 6.0);
+            assert_eq!(slider.max(), 20.0);
+            assert_eq!(slider.value(), 6.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Lower limit must be below the upper limit")]
+        fn panic_on_set_reversed_range() {
+            let mut slider =
+                Slider::new("key".to_owned(), 0.0, 10.0, 5.0, "%.2f".to_owned(), 120.0);
+
+            slider.set_range(10.0, 0.0);
+        }
+
+        #[test]
+        fn set_value_snapping_to_steps() {
+            let mut slider =
+                Slider::new("key".to_owned(), 1.0, 10.0, 5.0, "%.2f".to_owned(), 120.0)
+                    .with_scale(Scale::Steps { step: 2.0 });
+
+            slider.set_value(6.2);
+            assert_eq!(slider.value(), 7.0);
+
+            slider.set_value(10.0);
+            assert_eq!(slider.value(), 9.0);
+
+            assert_eq!(slider.default(), 5.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Logarithmic scale requires positive limits")]
+        fn panic_on_logarithmic_scale_with_non_positive_limit() {
+            let _slider = Slider::new("key".to_owned(), 0.0, 10.0, 5.0, "%.2f".to_owned(), 120.0)
+                .with_scale(Scale::Logarithmic);
+        }
+
+        #[test]
+        #[should_panic(expected = "Logarithmic scale requires positive limits")]
+        fn panic_on_set_non_positive_range_of_logarithmic_scale() {
+            let mut slider = Slider::new(
+                "key".to_owned(),
+                20.0,
+                20000.0,
+                440.0,
+                "%.2f".to_owned(),
+                120.0,
+            )
+            .with_scale(Scale::Logarithmic);
+
+            slider.set_range(0.0, 100.0);
+        }
+    }
+
+    mod range_slider {
+        use super::*;
+
+        fn new_range_slider() -> RangeSlider {
+            RangeSlider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                (2.0, 8.0),
+                "%.1f".to_owned(),
+                150.0,
+            )
+        }
+
+        #[test]
+        fn initialize() {
+            let range_slider = new_range_slider();
+
+            assert_eq!(range_slider.key(), "key");
+            assert_eq!(range_slider.min(), 0.0);
+            assert_eq!(range_slider.max(), 10.0);
+            assert_eq!(range_slider.value(), (2.0, 8.0));
+        }
+
+        #[test]
+        fn report_value_as_pair() {
+            let widget = Widget::RangeSlider(new_range_slider());
+
+            assert_eq!(m::Value::from(&widget), m::Value::F32F32((2.0, 8.0)));
+        }
+
+        #[test]
+        #[should_panic(expected = "Lower end of the range must not be above the upper end")]
+        fn panic_on_initialize_with_reversed_value() {
+            let _range_slider = RangeSlider::new(
+                "key".to_owned(),
+                0.0,
+                10.0,
+                (8.0, 2.0),
+                "%.1f".to_owned(),
+                150.0,
+            );
+        }
+
+        #[test]
+        fn set_value_clamped_by_limits() {
+            let mut range_slider = new_range_slider();
+
+            range_slider.set_value((-5.0, 20.0));
+
+            assert_eq!(range_slider.value(), (0.0, 10.0));
+        }
+
+        #[test]
+        fn stop_moved_lower_end_at_upper_end() {
+            let mut range_slider = new_range_slider();
+
+            range_slider.set_value((9.0, 8.0));
+
+            assert_eq!(range_slider.value(), (8.0, 8.0));
+        }
+
+        #[test]
+        fn stop_moved_upper_end_at_lower_end() {
+            let mut range_slider = new_range_slider();
+
+            range_slider.set_value((2.0, 1.0));
+
+            assert_eq!(range_slider.value(), (2.0, 2.0));
+        }
+    }
+
+    mod xy_pad {
+        use super::*;
+
+        #[test]
+        fn initialize() {
+            let xy_pad = XYPad::new(
+                "key".to_owned(),
+                (-1.0, 1.0),
+                (0.0, 10.0),
+                (0.0, 5.0),
+                100.0,
+            );
+
+            assert_eq!(xy_pad.key(), "key");
+            assert_eq!(xy_pad.x_range(), (-1.0, 1.0));
+            assert_eq!(xy_pad.y_range(), (0.0, 10.0));
+            assert_eq!(xy_pad.value(), (0.0, 5.0));
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must be within min and max")]
+        fn panic_on_initialize_with_value_out_of_limits() {
+            let _xy_pad = XYPad::new(
+                "key".to_owned(),
+                (-1.0, 1.0),
+                (0.0, 10.0),
+                (0.0, 20.0),
+                100.0,
+            );
+        }
+
+        #[test]
+        fn set_value_clamped_by_limits() {
+            let mut xy_pad = XYPad::new(
+                "key".to_owned(),
+                (-1.0, 1.0),
+                (0.0, 10.0),
+                (0.0, 5.0),
+                100.0,
+            );
+
+            xy_pad.set_value((0.5, 2.0));
+            assert_eq!(xy_pad.value(), (0.5, 2.0));
+
+            xy_pad.set_value((-3.0, 20.0));
+            assert_eq!(xy_pad.value(), (-1.0, 10.0));
+        }
+    }
+
+    mod envelope {
+        use super::*;
+
+        fn new_envelope(curved: bool) -> Envelope {
+            Envelope::new(
+                "key".to_owned(),
+                (0.0, 1.0),
+                (0.0, 1.0),
+                vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)],
+                curved,
+                [200.0, 100.0],
+            )
+        }
+
+        #[test]
+        fn initialize() {
+            let envelope = new_envelope(false);
+
+            assert_eq!(envelope.key(), "key");
+            assert_eq!(envelope.points(), &vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]);
+            assert_eq!(envelope.curvatures(), &vec![0.0, 0.0]);
+        }
+
+        #[test]
+        fn set_points_ordered_and_clamped() {
+            let mut envelope = new_envelope(false);
+
+            envelope.set_points(vec![(0.7, 2.0), (-1.0, 0.5), (0.3, 0.2)]);
+
+            assert_eq!(envelope.points(), &vec![(0.0, 0.5), (0.3, 0.2), (0.7, 1.0)]);
+        }
+
+        #[test]
+        fn set_points_drops_non_finite() {
+            let mut envelope = new_envelope(false);
+
+            envelope.set_points(vec![(0.7, 0.1), (f32::NAN, 0.5), (0.3, f32::INFINITY)]);
+
+            assert_eq!(envelope.points(), &vec![(0.7, 0.1)]);
+        }
+
+        #[test]
+        fn set_points_with_curvatures() {
+            let mut envelope = new_envelope(true);
+
+            envelope.set_points_with_curvatures(vec![(1.0, 0.0, 0.0), (0.0, 1.0, -3.0)]);
+
+            assert_eq!(envelope.points(), &vec![(0.0, 1.0), (1.0, 0.0)]);
+            assert_eq!(envelope.curvatures(), &vec![-1.0]);
+            assert_eq!(
+                envelope.points_with_curvatures(),
+                vec![(0.0, 1.0, -1.0), (1.0, 0.0, 0.0)]
+            );
+        }
+
+        #[test]
+        fn report_value_according_to_curvature() {
+            assert_eq!(
+                m::Value::from(&Widget::Envelope(new_envelope(false))),
+                m::Value::VecF32F32(vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)])
+            );
+            assert_eq!(
+                m::Value::from(&Widget::Envelope(new_envelope(true))),
+                m::Value::VecF32F32F32(vec![(0.0, 0.0, 0.0), (0.5, 1.0, 0.0), (1.0, 0.0, 0.0)])
+            );
+        }
+    }
+
+    mod knob {
+        use super::*;
+
+        #[test]
+        fn initialize() {
+            let knob = Knob::new("key".to_owned(), 0.0, 1.0, 0.5, "%.2f".to_owned(), 40.0);
+
+            assert_eq!(knob.key(), "key");
+            assert_eq!(knob.min(), 0.0);
+            assert_eq!(knob.max(), 1.0);
+            assert_eq!(knob.value(), 0.5);
+            assert_eq!(knob.size(), 40.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must be within min and max")]
+        fn panic_on_initialize_with_value_out_of_limits() {
+            let _knob = Knob::new("key".to_owned(), 0.0, 1.0, 2.0, "%.2f".to_owned(), 40.0);
+        }
+
+        #[test]
+        fn set_value_clamped_by_limits() {
+            let mut knob = Knob::new("key".to_owned(), 0.0, 1.0, 0.5, "%.2f".to_owned(), 40.0);
+
+            knob.set_value(0.7);
+            assert_eq!(knob.value(), 0.7);
+
+            knob.set_value(-1.0);
+            assert_eq!(knob.value(), 0.0);
+
+            knob.set_value(3.0);
+            assert_eq!(knob.value(), 1.0);
+        }
+    }
+
+    mod number_input {
+        use super::*;
+
+        #[test]
+        fn initialize() {
+            let number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                Some(Number::I32(0)),
+                None,
+                0.1,
+                "%d".to_owned(),
+                120.0,
+            );
+
+            assert_eq!(number_input.key(), "key");
+            assert_eq!(number_input.value(), Number::I32(5));
+            assert_eq!(number_input.min(), Some(Number::I32(0)));
+            assert_eq!(number_input.max(), None);
+        }
+
+        #[test]
+        #[should_panic(expected = "Limits must be of the same type as the value")]
+        fn panic_on_initialize_with_mixed_types() {
+            let _number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                Some(Number::F32(0.0)),
+                None,
+                0.1,
+                "%d".to_owned(),
+                120.0,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Lower limit must be below the upper limit")]
+        fn panic_on_initialize_with_reversed_limits() {
+            let _number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::F32(5.0),
+                Some(Number::F32(10.0)),
+                Some(Number::F32(0.0)),
+                0.1,
+                "%.2f".to_owned(),
+                120.0,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must be within min and max")]
+        fn panic_on_initialize_with_value_out_of_limits() {
+            let _number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::F32(-5.0),
+                Some(Number::F32(0.0)),
+                None,
+                0.1,
+                "%.2f".to_owned(),
+                120.0,
+            );
+        }
+
+        #[test]
+        fn set_value_clamped_by_limits() {
+            let mut number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                Some(Number::I32(0)),
+                Some(Number::I32(10)),
+                1.0,
+                "%d".to_owned(),
+                120.0,
+            );
+
+            number_input.set_value(Number::I32(3));
+            assert_eq!(number_input.value(), Number::I32(3));
+
+            number_input.set_value(Number::I32(-3));
+            assert_eq!(number_input.value(), Number::I32(0));
+
+            number_input.set_value(Number::I32(30));
+            assert_eq!(number_input.value(), Number::I32(10));
+        }
+
+        #[test]
+        fn set_value_without_limits() {
+            let mut number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::F32(5.0),
+                None,
+                None,
+                0.1,
+                "%.2f".to_owned(),
+                120.0,
+            );
+
+            number_input.set_value(Number::F32(-1000.0));
+
+            assert_eq!(number_input.value(), Number::F32(-1000.0));
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must be of the same type as the current one")]
+        fn panic_on_set_value_of_different_type() {
+            let mut number_input = NumberInput::new(
+                "key".to_owned(),
+                Number::I32(5),
+                None,
+                None,
+                1.0,
+                "%d".to_owned(),
+                120.0,
+            );
+
+            number_input.set_value(Number::F32(3.0));
+        }
     }
 
     mod drop_down {
@@ -1056,7 +3239,121 @@ mod tests {
                 ],
             );
 
-            drop_down.set_value("non_existent_value".to_owned());
+            drop_down.set_value("non_existent_value".to_owned());
+        }
+
+        #[test]
+        fn set_items_keeping_available_value() {
+            let mut drop_down = DropDown::new(
+                "key".to_owned(),
+                vec![
+                    DropDownItem::new("Item 1".to_owned(), "value1".to_owned()),
+                    DropDownItem::new("Item 2".to_owned(), "value2".to_owned()),
+                ],
+            );
+            drop_down.set_value("value2".to_owned());
+
+            drop_down.set_items(vec![
+                DropDownItem::new("Item 2".to_owned(), "value2".to_owned()),
+                DropDownItem::new("Item 3".to_owned(), "value3".to_owned()),
+            ]);
+
+            assert_eq!(drop_down.value(), "value2");
+            assert_eq!(drop_down.items().len(), 2);
+        }
+
+        #[test]
+        fn set_items_resetting_unavailable_value() {
+            let mut drop_down = DropDown::new(
+                "key".to_owned(),
+                vec![
+                    DropDownItem::new("Item 1".to_owned(), "value1".to_owned()),
+                    DropDownItem::new("Item 2".to_owned(), "value2".to_owned()),
+                ],
+            );
+
+            drop_down.set_items(vec![DropDownItem::new(
+                "Item 3".to_owned(),
+                "value3".to_owned(),
+            )]);
+
+            assert_eq!(drop_down.value(), "value3");
+        }
+    }
+
+    mod plot {
+        use super::*;
+
+        fn new_plot() -> Plot {
+            Plot::new(
+                "key".to_owned(),
+                (-1.0, 1.0),
+                vec![
+                    PlotSeries::new("A".to_owned(), [1.0, 0.0, 0.0, 1.0]),
+                    PlotSeries::new("B".to_owned(), [0.0, 1.0, 0.0, 1.0]),
+                ],
+                [300.0, 100.0],
+            )
+        }
+
+        #[test]
+        fn initialize() {
+            let plot = new_plot();
+
+            assert_eq!(plot.key(), "key");
+            assert_eq!(plot.samples(), &vec![Vec::<f32>::new(), Vec::new()]);
+        }
+
+        #[test]
+        #[should_panic(expected = "Plot must have at least one series")]
+        fn initialize_without_series() {
+            Plot::new("key".to_owned(), (-1.0, 1.0), vec![], [300.0, 100.0]);
+        }
+
+        #[test]
+        fn set_samples() {
+            let mut plot = new_plot();
+
+            plot.set_samples(vec![vec![0.0, 1.0], vec![0.5]]);
+
+            assert_eq!(plot.samples(), &vec![vec![0.0, 1.0], vec![0.5]]);
+        }
+
+        #[test]
+        #[should_panic(expected = "Plot requires a list of samples for each of its series")]
+        fn set_samples_of_missing_series() {
+            let mut plot = new_plot();
+
+            plot.set_samples(vec![vec![0.0, 1.0]]);
+        }
+    }
+
+    mod image {
+        use super::*;
+
+        #[test]
+        fn initialize() {
+            let image = Image::new("key".to_owned(), [100.0, 100.0]);
+
+            assert_eq!(image.key(), "key");
+            assert!(image.data().is_none());
+            assert_eq!(image.revision(), 0);
+        }
+
+        #[test]
+        fn set_data() {
+            let mut image = Image::new("key".to_owned(), [100.0, 100.0]);
+
+            image.set_data(ImageData::new(1, 2, vec![0; 8]));
+
+            assert_eq!(image.data().as_ref().unwrap().height(), 2);
+            assert_eq!(image.revision(), 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "Image data must have 4 bytes per each pixel")]
+        fn initialize_data_of_invalid_length() {
+            ImageData::new(2, 2, vec![0; 8]);
         }
     }
 
@@ -1069,6 +3366,25 @@ mod tests {
 
             assert_eq!(canvas.key(), "key");
             assert!(canvas.dots().is_empty());
+            assert!(canvas.shapes().is_empty());
+        }
+
+        #[test]
+        fn convert_shape_from_model() {
+            assert_eq!(
+                Shape::from(m::Shape::Circle {
+                    center: [10.0, 20.0],
+                    radius: 5.0,
+                    color: [1.0, 0.0, 0.0, 1.0],
+                    filled: true,
+                }),
+                Shape::Circle {
+                    center: [10.0, 20.0],
+                    radius: 5.0,
+                    color: [1.0, 0.0, 0.0, 1.0],
+                    filled: true,
+                }
+            );
         }
     }
 
@@ -1218,6 +3534,528 @@ mod tests {
                 Err(err) => assert_eq!(err, "Patch cannot connect pins of the same direction"),
             }
         }
+
+        #[test]
+        fn insert_node_into_patch() {
+            let mut state = initialize_state();
+            state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+            let patch = state
+                .add_patch(
+                    PinAddress::new("node:0".to_owned(), "out1".to_owned()),
+                    PinAddress::new("node:1".to_owned(), "in1".to_owned()),
+                )
+                .unwrap();
+            state
+                .waypoints_mut()
+                .insert(patch.clone(), vec![[0.0, 0.0]]);
+
+            state.insert_node_into_patch("node:2", &patch).unwrap();
+
+            assert_eq!(state.patches().len(), 2);
+            assert!(state.patches().contains(&Patch::new(
+                PinAddress::new("node:0".to_owned(), "out1".to_owned()),
+                PinAddress::new("node:2".to_owned(), "in1".to_owned()),
+            )));
+            assert!(state.patches().contains(&Patch::new(
+                PinAddress::new("node:2".to_owned(), "out1".to_owned()),
+                PinAddress::new("node:1".to_owned(), "in1".to_owned()),
+            )));
+            assert!(state.waypoints().is_empty());
+        }
+
+        #[test]
+        fn fail_on_insert_node_into_its_own_patch() {
+            let mut state = initialize_state();
+            let patch = state
+                .add_patch(
+                    PinAddress::new("node:0".to_owned(), "out1".to_owned()),
+                    PinAddress::new("node:1".to_owned(), "in1".to_owned()),
+                )
+                .unwrap();
+
+            match state.insert_node_into_patch("node:1", &patch) {
+                Ok(_) => panic!("Operation should fail"),
+                Err(err) => assert_eq!(err, "Patch cannot loop between pins of a single node"),
+            }
+            assert_eq!(state.patches().len(), 1);
+            assert!(state.patches().contains(&patch));
+        }
+
+        #[test]
+        fn fail_on_insert_node_with_multiple_inputs_into_patch() {
+            let mut state = initialize_state();
+            state.add_node_template(NodeTemplate::new(
+                "Label".to_owned(),
+                "mixer".to_owned(),
+                true,
+                vec![
+                    Pin::new("Input 1".to_owned(), "in1".to_owned(), Direction::Input),
+                    Pin::new("Input 2".to_owned(), "in2".to_owned(), Direction::Input),
+                    Pin::new("Output 1".to_owned(), "out1".to_owned(), Direction::Output),
+                ],
+                vec![],
+            ));
+            state.add_node(state.node_templates()[1].instantiate([0.0, 0.0]));
+            let patch = state
+                .add_patch(
+                    PinAddress::new("node:0".to_owned(), "out1".to_owned()),
+                    PinAddress::new("node:1".to_owned(), "in1".to_owned()),
+                )
+                .unwrap();
+
+            assert!(state.insert_node_into_patch("mixer:0", &patch).is_err());
+            assert_eq!(state.patches().len(), 1);
+        }
+    }
+
+    mod report {
+        use super::*;
+
+        fn initialize_state() -> State {
+            let mut state = State::default();
+
+            state.add_node_template(NodeTemplate::new(
+                "Label".to_owned(),
+                "node".to_owned(),
+                true,
+                vec![
+                    Pin::new("Input 1".to_owned(), "in1".to_owned(), Direction::Input),
+                    Pin::new("Output 1".to_owned(), "out1".to_owned(), Direction::Output),
+                ],
+                vec![],
+            ));
+
+            state.add_node(state.node_templates()[0].instantiate([10.0, 20.0]));
+            state.offset = [1.0, 2.0];
+
+            state
+        }
+
+        #[test]
+        fn omit_layout_by_default() {
+            let state = initialize_state();
+
+            let report = r::Report::from(&state);
+
+            assert_eq!(report.nodes.len(), 1);
+            assert!(report.layout.is_none());
+        }
+
+        #[test]
+        fn report_canvas_input() {
+            let mut state = initialize_state();
+            state.set_canvas_input(Some(CanvasInput::new(
+                WidgetAddress::new("node:0".to_owned(), "canvas".to_owned()),
+                [10.0, 20.0],
+                CanvasEvent::Press(MouseButton::Right),
+            )));
+
+            let report = r::Report::from(&state);
+
+            assert_eq!(
+                report.canvas_input,
+                Some(m::CanvasInput {
+                    node_id: "node:0".to_owned(),
+                    key: "canvas".to_owned(),
+                    position: [10.0, 20.0],
+                    event: m::CanvasEvent::Press(m::MouseButton::Right),
+                })
+            );
+        }
+
+        #[test]
+        fn report_bypassed_node() {
+            let mut state = initialize_state();
+            state.nodes_mut()[0].bypassed = true;
+
+            let report = r::Report::from(&state);
+
+            assert!(report.nodes[0].bypassed);
+        }
+
+        #[test]
+        fn include_layout_when_requested() {
+            let mut state = initialize_state();
+            state.report_layout = true;
+
+            let report = r::Report::from(&state);
+
+            let layout = report.layout.unwrap();
+            assert_eq!(layout.offset, [1.0, 2.0]);
+            assert_eq!(
+                layout.nodes,
+                vec![m::NodeLayout {
+                    id: "node:0".to_owned(),
+                    label: "Label".to_owned(),
+                    display_heading: true,
+                    position: [10.0, 20.0],
+                    pins: vec![
+                        c::Pin {
+                            label: "Input 1".to_owned(),
+                            class: "in1".to_owned(),
+                            direction: c::Direction::Input,
+                        },
+                        c::Pin {
+                            label: "Output 1".to_owned(),
+                            class: "out1".to_owned(),
+                            direction: c::Direction::Output,
+                        },
+                    ],
+                }]
+            );
+        }
+
+        #[test]
+        fn report_subgraphs_and_flattened_view() {
+            let mut state = initialize_state();
+            state.add_node(state.node_templates()[0].instantiate([10.0, 20.0]));
+            state
+                .add_patch(
+                    PinAddress::new("node:0".to_owned(), "out1".to_owned()),
+                    PinAddress::new("node:1".to_owned(), "in1".to_owned()),
+                )
+                .unwrap();
+            state.group_into_subgraph(&["node:1".to_owned()], "Subgraph".to_owned());
+
+            let report = r::Report::from(&state);
+
+            assert_eq!(
+                report.subgraphs,
+                vec![m::Subgraph {
+                    id: "subgraph:0".to_owned(),
+                    label: "Subgraph".to_owned(),
+                    parent: None,
+                    nodes: vec!["node:1".to_owned()],
+                    inputs: vec![m::PinAddress {
+                        node_id: "node:1".to_owned(),
+                        pin_class: "in1".to_owned(),
+                    }],
+                    outputs: vec![],
+                }]
+            );
+
+            let flattened = report.flatten();
+
+            assert!(flattened.subgraphs.is_empty());
+            assert_eq!(flattened.nodes[0].id, "node:0");
+            assert_eq!(flattened.nodes[1].id, "subgraph:0/node:1");
+            assert_eq!(
+                flattened.patches[0].destination.node_id,
+                "subgraph:0/node:1"
+            );
+        }
+
+        #[test]
+        fn flatten_layout() {
+            let mut state = initialize_state();
+            state.report_layout = true;
+            state.add_node(state.node_templates()[0].instantiate([10.0, 20.0]));
+            state.group_into_subgraph(&["node:1".to_owned()], "Subgraph".to_owned());
+
+            let flattened = r::Report::from(&state).flatten();
+
+            let layout = flattened.layout.unwrap();
+            assert_eq!(layout.nodes[0].id, "node:0");
+            assert_eq!(layout.nodes[1].id, "subgraph:0/node:1");
+        }
+
+        #[test]
+        fn include_frames_in_layout() {
+            let mut state = initialize_state();
+            state.report_layout = true;
+            state.add_frame(Frame::new(
+                "frame:0".to_owned(),
+                "Title".to_owned(),
+                [0.0, 0.0],
+                [100.0, 50.0],
+                [1.0, 1.0, 1.0, 1.0],
+            ));
+
+            let report = r::Report::from(&state);
+
+            assert_eq!(
+                report.layout.unwrap().frames,
+                vec![m::Frame {
+                    id: "frame:0".to_owned(),
+                    title: "Title".to_owned(),
+                    position: [0.0, 0.0],
+                    size: [100.0, 50.0],
+                    color: [1.0, 1.0, 1.0, 1.0],
+                    parent: None,
+                }]
+            );
+        }
+    }
+
+    mod subgraph {
+        use super::*;
+
+        // Three nodes chained node:0 -> node:1 -> node:2, where node:1 is
+        // nested in subgraph:1, which is nested in subgraph:0.
+        fn initialize_state() -> State {
+            let mut state = State::default();
+            state.add_node_template(NodeTemplate::new(
+                "Label".to_owned(),
+                "node".to_owned(),
+                true,
+                vec![
+                    Pin::new("Input".to_owned(), "in".to_owned(), Direction::Input),
+                    Pin::new("Output".to_owned(), "out".to_owned(), Direction::Output),
+                ],
+                vec![],
+            ));
+            for i in 0..3 {
+                state.add_node(state.node_templates()[0].instantiate([i as f32 * 100.0, 0.0]));
+            }
+            state
+                .add_patch(
+                    PinAddress::new("node:0".to_owned(), "out".to_owned()),
+                    PinAddress::new("node:1".to_owned(), "in".to_owned()),
+                )
+                .unwrap();
+            state
+                .add_patch(
+                    PinAddress::new("node:1".to_owned(), "out".to_owned()),
+                    PinAddress::new("node:2".to_owned(), "in".to_owned()),
+                )
+                .unwrap();
+            state.group_into_subgraph(&["node:1".to_owned()], "Outer".to_owned());
+            state.set_open_subgraph(Some("subgraph:0".to_owned()));
+            state.group_into_subgraph(&["node:1".to_owned()], "Inner".to_owned());
+            state.set_open_subgraph(None);
+            state
+        }
+
+        #[test]
+        fn group_nodes_into_subgraph() {
+            let state = initialize_state();
+
+            assert_eq!(state.subgraphs().len(), 2);
+            assert_eq!(state.subgraphs()[0].parent, None);
+            assert_eq!(state.subgraphs()[0].position, [100.0, 0.0]);
+            assert_eq!(state.subgraphs()[1].parent, Some("subgraph:0".to_owned()));
+            assert_eq!(state.nodes()[1].parent, Some("subgraph:1".to_owned()));
+            assert_eq!(state.nodes()[0].parent, None);
+        }
+
+        #[test]
+        fn subgraph_id_does_not_collide_with_node_id() {
+            let mut state = State::default();
+            state.add_node_template(NodeTemplate::new(
+                "Label".to_owned(),
+                "subgraph".to_owned(),
+                true,
+                vec![],
+                vec![],
+            ));
+            state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+
+            assert_eq!(state.next_subgraph_id(), "subgraph:1");
+        }
+
+        #[test]
+        fn check_nesting() {
+            let state = initialize_state();
+
+            assert!(state.is_inside("node:1", "subgraph:0"));
+            assert!(state.is_inside("node:1", "subgraph:1"));
+            assert!(state.is_inside("subgraph:1", "subgraph:0"));
+            assert!(!state.is_inside("node:0", "subgraph:0"));
+        }
+
+        #[test]
+        fn find_representative_on_level() {
+            let state = initialize_state();
+
+            assert_eq!(
+                state.representative_on_level("node:1", None),
+                Some("subgraph:0".to_owned())
+            );
+            assert_eq!(
+                state.representative_on_level("node:1", Some("subgraph:0")),
+                Some("subgraph:1".to_owned())
+            );
+            assert_eq!(
+                state.representative_on_level("node:0", None),
+                Some("node:0".to_owned())
+            );
+            assert_eq!(
+                state.representative_on_level("node:0", Some("subgraph:0")),
+                None
+            );
+        }
+
+        #[test]
+        fn list_path_to_subgraph() {
+            let state = initialize_state();
+
+            let path: Vec<&str> = state
+                .subgraph_path("subgraph:1")
+                .iter()
+                .map(|s| s.id())
+                .collect();
+
+            assert_eq!(path, vec!["subgraph:0", "subgraph:1"]);
+        }
+
+        #[test]
+        fn expose_pins_crossing_boundary() {
+            let state = initialize_state();
+
+            let pins = state.subgraph_pins("subgraph:0");
+
+            assert_eq!(pins.len(), 2);
+            assert_eq!(
+                *pins[0].address(),
+                PinAddress::new("node:1".to_owned(), "in".to_owned())
+            );
+            assert_eq!(pins[0].direction(), Direction::Input);
+            assert_eq!(pins[0].label().to_str(), "Label Input");
+            assert_eq!(
+                *pins[1].address(),
+                PinAddress::new("node:1".to_owned(), "out".to_owned())
+            );
+            assert_eq!(pins[1].direction(), Direction::Output);
+        }
+
+        #[test]
+        fn ignore_grouping_of_unavailable_items() {
+            let mut state = initialize_state();
+
+            assert_eq!(
+                state.group_into_subgraph(&["node:1".to_owned()], "Label".to_owned()),
+                None
+            );
+            assert_eq!(
+                state.group_into_subgraph(&["missing".to_owned()], "Label".to_owned()),
+                None
+            );
+            assert_eq!(state.subgraphs().len(), 2);
+        }
+
+        #[test]
+        fn drop_empty_subgraphs_with_their_empty_parents() {
+            let mut state = initialize_state();
+            state.set_open_subgraph(Some("subgraph:1".to_owned()));
+            state.nodes_mut().retain(|n| n.id() != "node:1");
+
+            state.drop_empty_subgraphs();
+
+            assert!(state.subgraphs().is_empty());
+            assert!(state.open_subgraph().is_none());
+        }
+
+        #[test]
+        fn drop_frames_of_empty_subgraphs() {
+            let mut state = initialize_state();
+            let mut frame = Frame::new(
+                "frame:0".to_owned(),
+                "Title".to_owned(),
+                [0.0, 0.0],
+                [100.0, 50.0],
+                [1.0, 1.0, 1.0, 1.0],
+            );
+            frame.parent = Some("subgraph:1".to_owned());
+            state.add_frame(frame);
+            state.set_triggered_frame(Some("frame:0".to_owned()));
+            state.nodes_mut().retain(|n| n.id() != "node:1");
+
+            state.drop_empty_subgraphs();
+
+            assert!(state.frames().is_empty());
+            assert!(state.triggered_frame().is_none());
+        }
+
+        #[test]
+        fn ungroup_subgraph_into_its_parent() {
+            let mut state = initialize_state();
+
+            state.ungroup_subgraph("subgraph:1");
+
+            assert_eq!(state.subgraphs().len(), 1);
+            assert_eq!(state.nodes()[1].parent, Some("subgraph:0".to_owned()));
+        }
+
+        #[test]
+        fn ungroup_frames_into_parent() {
+            let mut state = initialize_state();
+            let mut frame = Frame::new(
+                "frame:0".to_owned(),
+                "Title".to_owned(),
+                [0.0, 0.0],
+                [100.0, 50.0],
+                [1.0, 1.0, 1.0, 1.0],
+            );
+            frame.parent = Some("subgraph:1".to_owned());
+            state.add_frame(frame);
+
+            state.ungroup_subgraph("subgraph:1");
+
+            assert_eq!(state.frames()[0].parent, Some("subgraph:0".to_owned()));
+        }
+
+        #[test]
+        fn group_frames_enclosing_only_grouped_members() {
+            let mut state = initialize_state();
+            let new_frame = |id: &str, position, size| {
+                Frame::new(
+                    id.to_owned(),
+                    "Title".to_owned(),
+                    position,
+                    size,
+                    [1.0, 1.0, 1.0, 1.0],
+                )
+            };
+            state.add_frame(new_frame("frame:0", [190.0, -10.0], [20.0, 20.0]));
+            state.add_frame(new_frame("frame:1", [-10.0, -10.0], [300.0, 20.0]));
+
+            let subgraph_id = state.group_into_subgraph(&["node:2".to_owned()], "Label".to_owned());
+
+            assert_eq!(state.frames()[0].parent, subgraph_id);
+            assert_eq!(state.frames()[1].parent, None);
+        }
+    }
+
+    mod frame {
+        use super::*;
+
+        fn new_frame(id: &str) -> Frame {
+            Frame::new(
+                id.to_owned(),
+                "Title".to_owned(),
+                [10.0, 20.0],
+                [100.0, 50.0],
+                [1.0, 1.0, 1.0, 1.0],
+            )
+        }
+
+        #[test]
+        fn check_whether_point_is_inside() {
+            let frame = new_frame("frame:0");
+
+            assert!(frame.contains([10.0, 20.0]));
+            assert!(frame.contains([60.0, 40.0]));
+            assert!(frame.contains([110.0, 70.0]));
+            assert!(!frame.contains([5.0, 40.0]));
+            assert!(!frame.contains([60.0, 71.0]));
+        }
+
+        #[test]
+        fn generate_unused_id() {
+            let mut state = State::default();
+            state.add_frame(new_frame("frame:0"));
+            state.add_frame(new_frame("frame:2"));
+
+            assert_eq!(state.next_frame_id(), "frame:1");
+        }
+
+        #[test]
+        #[should_panic(expected = "Each Frame within a state must have its unique id")]
+        fn panic_on_duplicate_frame_id() {
+            let mut state = State::default();
+            state.add_frame(new_frame("frame:0"));
+            state.add_frame(new_frame("frame:0"));
+        }
     }
 
     mod from_config {
@@ -1243,6 +4081,8 @@ mod tests {
                             direction: c::Output,
                         },
                     ],
+                    variadic_pins: vec![],
+                    resizable: false,
                     widgets: vec![
                         c::TextBox {
                             key: "text_box".to_owned(),
@@ -1257,6 +4097,8 @@ mod tests {
                             default: 5.0,
                             format: "%.1f".to_owned(),
                             width: 150.0,
+                            scale: c::Scale::Linear,
+                            unit: None,
                         },
                         c::Trigger {
                             label: "Trigger".to_owned(),
@@ -1281,6 +4123,7 @@ mod tests {
                         },
                     ],
                 }],
+                report_layout: false,
             };
             let mut expected_state = State::default();
             expected_state.add_node_template(NodeTemplate::new(
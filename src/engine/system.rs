@@ -5,13 +5,20 @@ use glium::glutin;
 use glium::glutin::event::{Event, WindowEvent};
 use glium::glutin::event_loop::{ControlFlow, EventLoop};
 use glium::glutin::window::WindowBuilder;
-use glium::{Display, Surface};
-use imgui::{Context, FontConfig, FontSource, Ui};
-use imgui_glium_renderer::Renderer;
+use glium::texture::{ClientFormat, RawImage2d};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerBehavior};
+use glium::{Display, Surface, Texture2d};
+use imgui::{Context, FontConfig, FontSource, TextureId, Textures, Ui};
+use imgui_glium_renderer::{Renderer, Texture};
 use imgui_winit_support::{HiDpiMode, WinitPlatform};
+use std::borrow::Cow;
 use std::path::Path;
+use std::rc::Rc;
 use std::time::Instant;
 
+use crate::engine::state::ImageData;
+use crate::engine::texture::TextureUploader;
+
 pub struct System {
     pub event_loop: EventLoop<()>,
     pub display: glium::Display,
@@ -67,8 +74,53 @@ pub fn init(title: &str) -> System {
     }
 }
 
+/// Uploads images into the texture registry of the renderer.
+pub struct GliumTextureUploader<'a> {
+    display: &'a Display,
+    textures: &'a mut Textures<Texture>,
+}
+
+impl GliumTextureUploader<'_> {
+    fn new_texture(&self, image: &ImageData) -> Texture {
+        let raw = RawImage2d {
+            data: Cow::Borrowed(image.data().as_slice()),
+            width: image.width(),
+            height: image.height(),
+            format: ClientFormat::U8U8U8U8,
+        };
+        let texture = Texture2d::new(self.display, raw).expect("Failed to upload texture");
+        Texture {
+            texture: Rc::new(texture),
+            sampler: SamplerBehavior {
+                magnify_filter: MagnifySamplerFilter::Linear,
+                minify_filter: MinifySamplerFilter::Linear,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl TextureUploader for GliumTextureUploader<'_> {
+    fn upload(&mut self, image: &ImageData) -> TextureId {
+        let texture = self.new_texture(image);
+        self.textures.insert(texture)
+    }
+
+    fn replace(&mut self, texture_id: TextureId, image: &ImageData) {
+        let texture = self.new_texture(image);
+        self.textures.replace(texture_id, texture);
+    }
+
+    fn remove(&mut self, texture_id: TextureId) {
+        self.textures.remove(texture_id);
+    }
+}
+
 impl System {
-    pub fn main_loop<F: FnMut(&mut bool, &mut Ui) + 'static>(self, mut run_ui: F) {
+    pub fn main_loop<F>(self, mut run_ui: F)
+    where
+        F: FnMut(&mut bool, &mut Ui, &mut dyn TextureUploader) + 'static,
+    {
         let System {
             event_loop,
             display,
@@ -96,7 +148,13 @@ impl System {
                 let mut ui = imgui.frame();
 
                 let mut run = true;
-                run_ui(&mut run, &mut ui);
+                {
+                    let mut texture_uploader = GliumTextureUploader {
+                        display: &display,
+                        textures: renderer.textures(),
+                    };
+                    run_ui(&mut run, &mut ui, &mut texture_uploader);
+                }
                 if !run {
                     *control_flow = ControlFlow::Exit;
                 }
@@ -0,0 +1,179 @@
+//! Images shown in nodes must be uploaded to the GPU as textures before they
+//! can be drawn. This module keeps track of uploaded textures and makes sure
+//! that each image is uploaded again only after its data changes.
+
+extern crate imgui;
+
+use std::collections::HashMap;
+
+use crate::engine::state::{ImageData, State, Widget};
+
+/// Backend capable of turning RGBA images into textures usable in the UI.
+pub trait TextureUploader {
+    fn upload(&mut self, image: &ImageData) -> imgui::TextureId;
+    fn replace(&mut self, texture_id: imgui::TextureId, image: &ImageData);
+    fn remove(&mut self, texture_id: imgui::TextureId);
+}
+
+struct UploadedImage {
+    texture_id: imgui::TextureId,
+    revision: u64,
+}
+
+/// Textures of `Image` widgets, identified by the node id and the widget key.
+#[derive(Default)]
+pub struct ImageTextures {
+    images: HashMap<(String, String), UploadedImage>,
+}
+
+impl ImageTextures {
+    /// Upload images that changed since the last call and release textures
+    /// of images that are no longer available.
+    pub fn sync(&mut self, state: &State, uploader: &mut dyn TextureUploader) {
+        let mut images = HashMap::new();
+
+        for node in state.nodes().iter() {
+            for widget in node.widgets().iter() {
+                let image = match widget {
+                    Widget::Image(image) => image,
+                    _ => continue,
+                };
+                let data = match image.data() {
+                    Some(data) => data,
+                    None => continue,
+                };
+
+                let address = (node.id().to_owned(), image.key().to_owned());
+                let uploaded = match self.images.remove(&address) {
+                    Some(uploaded) if uploaded.revision == image.revision() => uploaded,
+                    Some(uploaded) => {
+                        uploader.replace(uploaded.texture_id, data);
+                        UploadedImage {
+                            texture_id: uploaded.texture_id,
+                            revision: image.revision(),
+                        }
+                    }
+                    None => UploadedImage {
+                        texture_id: uploader.upload(data),
+                        revision: image.revision(),
+                    },
+                };
+                images.insert(address, uploaded);
+            }
+        }
+
+        for (_, uploaded) in self.images.drain() {
+            uploader.remove(uploaded.texture_id);
+        }
+        self.images = images;
+    }
+
+    pub fn get(&self, node_id: &str, key: &str) -> Option<imgui::TextureId> {
+        self.images
+            .get(&(node_id.to_owned(), key.to_owned()))
+            .map(|uploaded| uploaded.texture_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::engine::state::{Image, NodeTemplate};
+
+    #[derive(Default)]
+    struct TestUploader {
+        next_id: usize,
+        uploaded: Vec<usize>,
+        replaced: Vec<usize>,
+        removed: Vec<usize>,
+    }
+
+    impl TextureUploader for TestUploader {
+        fn upload(&mut self, _image: &ImageData) -> imgui::TextureId {
+            self.next_id += 1;
+            self.uploaded.push(self.next_id);
+            imgui::TextureId::from(self.next_id)
+        }
+
+        fn replace(&mut self, texture_id: imgui::TextureId, _image: &ImageData) {
+            self.replaced.push(texture_id.id());
+        }
+
+        fn remove(&mut self, texture_id: imgui::TextureId) {
+            self.removed.push(texture_id.id());
+        }
+    }
+
+    fn new_state() -> State {
+        let mut state = State::default();
+        state.add_node_template(NodeTemplate::new(
+            "Label".to_owned(),
+            "class".to_owned(),
+            true,
+            vec![],
+            vec![Widget::Image(Image::new("key".to_owned(), [100.0, 100.0]))],
+        ));
+        state.add_node(state.node_templates()[0].instantiate([0.0, 0.0]));
+        state
+    }
+
+    fn set_image_data(state: &mut State, pixel: [u8; 4]) {
+        if let Widget::Image(image) = &mut state.nodes_mut()[0].widgets_mut()[0] {
+            image.set_data(ImageData::new(1, 1, pixel.to_vec()));
+        } else {
+            panic!("invalid widget type");
+        }
+    }
+
+    #[test]
+    fn skip_image_without_data() {
+        let state = new_state();
+        let mut textures = ImageTextures::default();
+        let mut uploader = TestUploader::default();
+
+        textures.sync(&state, &mut uploader);
+
+        assert!(uploader.uploaded.is_empty());
+        assert_eq!(textures.get("class:0", "key"), None);
+    }
+
+    #[test]
+    fn upload_image_only_after_its_change() {
+        let mut state = new_state();
+        let mut textures = ImageTextures::default();
+        let mut uploader = TestUploader::default();
+
+        set_image_data(&mut state, [255, 0, 0, 255]);
+        textures.sync(&state, &mut uploader);
+        textures.sync(&state, &mut uploader);
+
+        assert_eq!(uploader.uploaded, vec![1]);
+        assert!(uploader.replaced.is_empty());
+        assert_eq!(
+            textures.get("class:0", "key"),
+            Some(imgui::TextureId::from(1))
+        );
+
+        set_image_data(&mut state, [0, 255, 0, 255]);
+        textures.sync(&state, &mut uploader);
+
+        assert_eq!(uploader.uploaded, vec![1]);
+        assert_eq!(uploader.replaced, vec![1]);
+    }
+
+    #[test]
+    fn remove_texture_of_removed_node() {
+        let mut state = new_state();
+        let mut textures = ImageTextures::default();
+        let mut uploader = TestUploader::default();
+
+        set_image_data(&mut state, [255, 0, 0, 255]);
+        textures.sync(&state, &mut uploader);
+        state.nodes_mut().clear();
+        textures.sync(&state, &mut uploader);
+
+        assert_eq!(uploader.removed, vec![1]);
+        assert_eq!(textures.get("class:0", "key"), None);
+    }
+}
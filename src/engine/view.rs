@@ -10,31 +10,54 @@ use std::f32;
 use std::ptr;
 use std::rc::Rc;
 
-use crate::engine::action::{Action, Value};
+use crate::engine::action::{Action, Arrangement, Value};
 use crate::engine::state::{
-    Button, ButtonActivationMode, Canvas, Direction, DropDown, FileDialogMode, Node, Patch,
-    PinAddress, Slider, State, TextBox, Widget,
+    Button, ButtonActivationMode, Canvas, CanvasEvent, Direction, DropDown, Envelope,
+    FileDialogMode, Grid, Image, Knob, MouseButton, Node, Number, NumberInput, Patch, Pin,
+    PinAddress, Plot, RangeSlider, Scale, Shape, Slider, State, TextBox, Widget, XYPad,
 };
+use crate::engine::texture::ImageTextures;
 use crate::vec2;
 use crate::widget;
 
 const PATCH_CLICK_MAX_DISTANCE: f32 = 5.0;
-
-pub fn draw(state: &State, ui: &imgui::Ui) -> Vec<Action> {
+const NODE_LABEL_CAPACITY: usize = 256;
+const FRAME_TITLE_CAPACITY: usize = 256;
+const BREADCRUMBS_POSITION: [f32; 2] = [10.0, 10.0];
+const WAYPOINT_RADIUS: f32 = 4.0;
+const GRID_ALPHA: f32 = 0.3;
+const MINIMAP_SIZE: [f32; 2] = [200.0, 150.0];
+const MINIMAP_MARGIN: f32 = 10.0;
+
+pub fn draw(state: &State, image_textures: &ImageTextures, ui: &imgui::Ui) -> Vec<Action> {
     let mut actions = Vec::new();
 
-    if let Some(action) = draw_canvas(ui) {
+    if let Some(action) = draw_canvas(state, ui) {
         actions.push(action);
     }
 
+    actions.extend(draw_frames(state, ui));
+
     if let Some(action) = draw_menu(state, ui) {
         actions.push(action);
     }
 
-    let (node_actions, pin_positions) = draw_nodes(state, ui);
+    let (node_actions, pin_positions) = draw_nodes(state, image_textures, ui);
     actions.extend(node_actions);
 
-    actions.extend(draw_patches(state, pin_positions, ui));
+    actions.extend(draw_patches(state, &pin_positions, ui));
+
+    draw_selection_rectangle(state, ui);
+
+    if *state.minimap_visible() {
+        if let Some(action) = draw_minimap(state, &pin_positions, ui) {
+            actions.push(action);
+        }
+    }
+
+    if let Some(action) = draw_breadcrumbs(state, ui) {
+        actions.push(action);
+    }
 
     if let Some(action) = draw_file_dialog(state, ui) {
         actions.push(action);
@@ -43,7 +66,7 @@ pub fn draw(state: &State, ui: &imgui::Ui) -> Vec<Action> {
     actions
 }
 
-pub fn draw_canvas(ui: &imgui::Ui) -> Option<Action> {
+pub fn draw_canvas(state: &State, ui: &imgui::Ui) -> Option<Action> {
     let draw_list = ui.get_window_draw_list();
     draw_list
         .add_rect(
@@ -54,6 +77,33 @@ pub fn draw_canvas(ui: &imgui::Ui) -> Option<Action> {
         .filled(true)
         .build();
 
+    if state.grid.visible {
+        draw_grid(state, ui);
+    }
+
+    let absolute_mouse_position =
+        vec2::sum(&[ui.io().mouse_pos, [-state.offset[0], -state.offset[1]]]);
+
+    if state.selection_rectangle().is_some() {
+        if ui.is_mouse_released(imgui::MouseButton::Left) {
+            return Some(Action::FinishSelectionRectangle);
+        } else if ui.is_mouse_dragging(imgui::MouseButton::Left) {
+            return Some(Action::UpdateSelectionRectangle {
+                position: absolute_mouse_position,
+            });
+        }
+        return None;
+    }
+
+    if ui.io().key_ctrl
+        && ui.is_mouse_clicked(imgui::MouseButton::Left)
+        && !ui.is_any_item_hovered()
+    {
+        return Some(Action::StartSelectionRectangle {
+            position: absolute_mouse_position,
+        });
+    }
+
     if ui.is_item_active() {
         if ui.is_mouse_down(imgui::MouseButton::Left) {
             ui.set_mouse_cursor(Some(imgui::MouseCursor::ResizeAll));
@@ -71,6 +121,29 @@ pub fn draw_canvas(ui: &imgui::Ui) -> Option<Action> {
     None
 }
 
+fn draw_grid(state: &State, ui: &imgui::Ui) {
+    let draw_list = ui.get_window_draw_list();
+    let window_size = ui.window_size();
+    let mut color = ui.style_color(imgui::StyleColor::Border);
+    color[3] *= GRID_ALPHA;
+
+    let mut x = state.offset[0].rem_euclid(Grid::SPACING);
+    while x < window_size[0] {
+        draw_list
+            .add_line([x, 0.0], [x, window_size[1]], color)
+            .build();
+        x += Grid::SPACING;
+    }
+
+    let mut y = state.offset[1].rem_euclid(Grid::SPACING);
+    while y < window_size[1] {
+        draw_list
+            .add_line([0.0, y], [window_size[0], y], color)
+            .build();
+        y += Grid::SPACING;
+    }
+}
+
 fn draw_menu(state: &State, ui: &imgui::Ui) -> Option<Action> {
     let mut action = None;
 
@@ -92,6 +165,128 @@ fn draw_menu(state: &State, ui: &imgui::Ui) -> Option<Action> {
 
         ui.separator();
 
+        if imgui::MenuItem::new(im_str!("Show grid"))
+            .selected(state.grid.visible)
+            .build(ui)
+        {
+            action = Some(Action::SetGridVisible {
+                visible: !state.grid.visible,
+            })
+        }
+
+        if imgui::MenuItem::new(im_str!("Snap to grid"))
+            .selected(state.grid.snap)
+            .build(ui)
+        {
+            action = Some(Action::SetSnapToGrid {
+                snap: !state.grid.snap,
+            })
+        }
+
+        if imgui::MenuItem::new(im_str!("Show minimap"))
+            .selected(*state.minimap_visible())
+            .build(ui)
+        {
+            action = Some(Action::SetMinimapVisible {
+                visible: !state.minimap_visible(),
+            })
+        }
+
+        if imgui::MenuItem::new(im_str!("Auto layout")).build(ui) {
+            action = Some(Action::AutoLayout { root_node_id: None })
+        }
+
+        ui.separator();
+
+        if imgui::MenuItem::new(im_str!("Add frame")).build(ui) {
+            action = Some(Action::AddFrame {
+                position: absolute_position,
+            })
+        }
+
+        if !state.selection().is_empty()
+            && imgui::MenuItem::new(im_str!("Group into subgraph")).build(ui)
+        {
+            action = Some(Action::GroupSelection)
+        }
+
+        if state.selection().len() > 1 {
+            ui.menu(im_str!("Arrange"), true, || {
+                let arrangements = [
+                    (im_str!("Align left"), Arrangement::AlignLeft),
+                    (im_str!("Align right"), Arrangement::AlignRight),
+                    (im_str!("Align top"), Arrangement::AlignTop),
+                    (im_str!("Align bottom"), Arrangement::AlignBottom),
+                    (
+                        im_str!("Distribute horizontally"),
+                        Arrangement::DistributeHorizontally,
+                    ),
+                    (
+                        im_str!("Distribute vertically"),
+                        Arrangement::DistributeVertically,
+                    ),
+                ];
+                for (label, arrangement) in arrangements.iter() {
+                    if imgui::MenuItem::new(label).build(ui) {
+                        action = Some(Action::ArrangeSelection {
+                            arrangement: *arrangement,
+                        })
+                    }
+                }
+            });
+        }
+
+        for subgraph in state
+            .subgraphs()
+            .iter()
+            .filter(|s| state.selection().iter().any(|id| id == s.id()))
+        {
+            let label =
+                imgui::ImString::from(format!("Ungroup {}##{}", subgraph.label(), subgraph.id()));
+            if imgui::MenuItem::new(&label).build(ui) {
+                action = Some(Action::UngroupSubgraph {
+                    subgraph_id: subgraph.id().to_owned(),
+                })
+            }
+        }
+
+        let level = state.open_subgraph().as_deref();
+        if let Some(frame) = state
+            .frames()
+            .iter()
+            .rev()
+            .find(|f| f.parent.as_deref() == level && f.contains(absolute_position))
+        {
+            let mut title = frame.title_im().clone();
+            title.reserve(FRAME_TITLE_CAPACITY);
+            ui.input_text(im_str!("##frame_title"), &mut title).build();
+            if title.to_str() != frame.title() {
+                action = Some(Action::SetFrameTitle {
+                    frame_id: frame.id().to_owned(),
+                    title: title.to_str().to_owned(),
+                });
+            }
+
+            let mut color = frame.color;
+            if imgui::ColorEdit::new(im_str!("##frame_color"), &mut color)
+                .alpha(true)
+                .build(ui)
+            {
+                action = Some(Action::SetFrameColor {
+                    frame_id: frame.id().to_owned(),
+                    color,
+                });
+            }
+
+            if imgui::MenuItem::new(im_str!("Remove frame")).build(ui) {
+                action = Some(Action::RemoveFrame {
+                    frame_id: frame.id().to_owned(),
+                })
+            }
+        }
+
+        ui.separator();
+
         for template in state.node_templates().iter() {
             if imgui::MenuItem::new(template.label_im()).build(ui) {
                 action = Some(Action::AddNode {
@@ -109,6 +304,85 @@ fn draw_menu(state: &State, ui: &imgui::Ui) -> Option<Action> {
     action
 }
 
+fn draw_frames(state: &State, ui: &imgui::Ui) -> Vec<Action> {
+    let actions = Rc::new(RefCell::new(Vec::new()));
+    let level = state.open_subgraph().as_deref();
+
+    state
+        .frames()
+        .iter()
+        .filter(|f| f.parent.as_deref() == level)
+        .for_each(|frame| {
+            let is_triggered = matches!(state.triggered_frame(), Some(id) if id == frame.id());
+
+            let title_callback = {
+                let frame_id = frame.id().to_string();
+                let actions = Rc::clone(&actions);
+                Box::new(move |ui: &imgui::Ui| {
+                    if ui.is_item_active() {
+                        if ui.is_mouse_down(imgui::MouseButton::Left) && !is_triggered {
+                            actions.borrow_mut().push(Action::SetTriggeredFrame {
+                                frame_id: frame_id.clone(),
+                            });
+                        }
+
+                        if ui.is_mouse_dragging(imgui::MouseButton::Left) {
+                            ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+                            actions.borrow_mut().push(Action::MoveFrame {
+                                frame_id,
+                                offset: ui.io().mouse_delta,
+                            });
+                        }
+                    }
+                })
+            };
+            let resize_callback = {
+                let frame_id = frame.id().to_string();
+                let size = frame.size;
+                let actions = Rc::clone(&actions);
+                Box::new(move |ui: &imgui::Ui| {
+                    if ui.is_item_hovered() || ui.is_item_active() {
+                        ui.set_mouse_cursor(Some(imgui::MouseCursor::ResizeNWSE));
+                    }
+
+                    if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
+                        actions.borrow_mut().push(Action::ResizeFrame {
+                            frame_id,
+                            size: vec2::sum(&[size, ui.io().mouse_delta]),
+                        });
+                    }
+                })
+            };
+
+            widget::frame::Frame::new(
+                imgui::ImString::from(format!("##{}", frame.id())),
+                frame.title_im(),
+            )
+            .position(vec2::sum(&[frame.position, state.offset]))
+            .size(frame.size)
+            .color(frame.color)
+            .thick(is_triggered)
+            .title_callback(title_callback)
+            .resize_callback(resize_callback)
+            .build(ui);
+        });
+
+    if let Some(previously_triggered_frame_id) = state.triggered_frame() {
+        if ui.is_key_pressed(imgui::Key::Delete) {
+            actions.borrow_mut().push(Action::RemoveFrame {
+                frame_id: previously_triggered_frame_id.to_string(),
+            });
+        } else if ui.is_mouse_clicked(imgui::MouseButton::Left)
+            || ui.is_mouse_clicked(imgui::MouseButton::Right)
+            || ui.is_key_pressed(imgui::Key::Escape)
+        {
+            actions.borrow_mut().push(Action::ResetTriggeredFrame)
+        }
+    }
+
+    Rc::try_unwrap(actions).unwrap().into_inner()
+}
+
 // TODO: use constants for colors and sizes
 fn draw_file_dialog(state: &State, ui: &imgui::Ui) -> Option<Action> {
     let mut action = None;
@@ -183,99 +457,288 @@ fn draw_file_dialog(state: &State, ui: &imgui::Ui) -> Option<Action> {
     action
 }
 
-fn draw_nodes(state: &State, ui: &imgui::Ui) -> (Vec<Action>, HashMap<PinAddress, [f32; 2]>) {
+fn draw_nodes(
+    state: &State,
+    image_textures: &ImageTextures,
+    ui: &imgui::Ui,
+) -> (Vec<Action>, HashMap<PinAddress, [f32; 2]>) {
     let actions = Rc::new(RefCell::new(Vec::new()));
     let pin_positions = Rc::new(RefCell::new(HashMap::new()));
     let newly_triggered_pin = Rc::new(RefCell::new(None));
 
-    state.nodes().iter().for_each(|node| {
-        let mut node_widget = widget::node::Node::new(node.id_im())
-            .position(vec2::sum(&[node.position, state.offset]))
-            .add_component(widget::node::Component::Space(10.0));
+    let level = state.open_subgraph().as_deref();
 
-        if let Some(triggered_node_id) = state.triggered_node() {
-            if triggered_node_id == node.id() {
-                node_widget = node_widget.thick(true);
-            }
-        }
+    let is_selected = |id: &str| state.selection().iter().any(|s| s == id);
+    let is_slider_text_input = |node_id: &str, key: &str| matches!(state.slider_text_input(), Some(a) if a.node_id == node_id && a.key == key);
 
-        if node.display_heading {
-            node_widget = node_widget
-                .add_component(widget::node::Component::Label(widget::label::Label::new(
-                    node.label_im(),
-                )))
+    state
+        .nodes()
+        .iter()
+        .filter(|n| n.parent.as_deref() == level)
+        .for_each(|node| {
+            let mut node_widget = widget::node::Node::new(node.id_im())
+                .position(vec2::sum(&[node.position, state.offset]))
+                .collapsed(node.collapsed)
+                .bypassed(node.bypassed)
+                .collapse_callback({
+                    let actions = Rc::clone(&actions);
+                    let node_id = node.id().to_string();
+                    let collapsed = !node.collapsed;
+                    Box::new(move || {
+                        actions
+                            .borrow_mut()
+                            .push(Action::SetNodeCollapsed { node_id, collapsed });
+                    })
+                })
                 .add_component(widget::node::Component::Space(10.0));
-        }
 
-        if !node.pins().is_empty() {
-            let pin_group = new_pin_group_widget(node, &pin_positions, &newly_triggered_pin);
-            node_widget = node_widget
-                .add_component(widget::node::Component::PinGroup(pin_group))
-                .add_component(widget::node::Component::Space(10.0));
-        }
+            if node.resizable {
+                node_widget = node_widget
+                    .min_size(node.custom_size.unwrap_or([0.0, 0.0]))
+                    .resize_callback(new_node_resize_callback(node, &actions));
+            }
 
-        node_widget = node.widgets().iter().fold(node_widget, |n, w| match w {
-            Widget::TextBox(text_box) => n
-                .add_component(widget::node::Component::TextBox(new_text_box_widget(
-                    node.id(),
-                    text_box,
-                    &actions,
-                )))
-                .add_component(widget::node::Component::Space(10.0)),
-            Widget::Button(button) => n
-                .add_component(widget::node::Component::Button(new_button_widget(
-                    node.id(),
-                    button,
-                    &actions,
-                )))
-                .add_component(widget::node::Component::Space(10.0)),
-            Widget::Slider(slider) => n
-                .add_component(widget::node::Component::Slider(new_slider_widget(
-                    node.id(),
-                    slider,
-                    &actions,
-                )))
-                .add_component(widget::node::Component::Space(10.0)),
-            Widget::DropDown(dropdown) => n
-                .add_component(widget::node::Component::DropDown(new_dropdown_widget(
-                    node.id(),
-                    dropdown,
-                    &actions,
-                )))
-                .add_component(widget::node::Component::Space(10.0)),
-            Widget::Canvas(canvas) => n
-                .add_component(widget::node::Component::Canvas(new_canvas_widget(canvas)))
-                .add_component(widget::node::Component::Space(10.0)),
-        });
+            if let Some(triggered_node_id) = state.triggered_node() {
+                if triggered_node_id == node.id() {
+                    node_widget = node_widget.thick(true);
+                }
+            }
 
-        node_widget.build(ui);
+            if is_selected(node.id()) {
+                node_widget = node_widget.thick(true);
+            }
 
-        if ui.is_item_active() {
-            if ui.is_mouse_down(imgui::MouseButton::Left)
-                || ui.is_mouse_dragging(imgui::MouseButton::Left)
-            {
-                ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+            if node.display_heading {
+                let heading = match state.renamed_node() {
+                    Some(renamed_node) if renamed_node.node_id == node.id() => {
+                        widget::node::Component::LabelInput(new_label_input_widget(
+                            node.id(),
+                            &renamed_node.buffer,
+                            &actions,
+                        ))
+                    }
+                    _ => widget::node::Component::Label(new_label_widget(node, &actions)),
+                };
+                node_widget = node_widget
+                    .add_component(heading)
+                    .add_component(widget::node::Component::Space(10.0));
+            }
 
-                let triggered_node_id = node.id().to_string();
-                let new_trigger = match state.triggered_node() {
-                    Some(previously_triggered_node_id) => {
-                        triggered_node_id != *previously_triggered_node_id
+            let pins: Vec<_> = node
+                .pins()
+                .iter()
+                .filter(|p| !node.collapsed || is_pin_connected(state, node, p))
+                .collect();
+            if !pins.is_empty() {
+                let pin_group =
+                    new_pin_group_widget(node, &pins, &pin_positions, &newly_triggered_pin);
+                node_widget = node_widget
+                    .add_component(widget::node::Component::PinGroup(pin_group))
+                    .add_component(widget::node::Component::Space(10.0));
+            }
+
+            let mut widgets = node.widgets().iter().peekable();
+            while let Some(w) = widgets.next() {
+                node_widget = match w {
+                    Widget::TextBox(text_box) => node_widget
+                        .add_component(widget::node::Component::TextBox(new_text_box_widget(
+                            node.id(),
+                            text_box,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Button(button) => node_widget
+                        .add_component(widget::node::Component::Button(new_button_widget(
+                            node.id(),
+                            button,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Slider(slider) => node_widget
+                        .add_component(widget::node::Component::Slider(new_slider_widget(
+                            node.id(),
+                            slider,
+                            is_slider_text_input(node.id(), slider.key()),
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::RangeSlider(range_slider) => node_widget
+                        .add_component(widget::node::Component::RangeSlider(
+                            new_range_slider_widget(node.id(), range_slider, &actions),
+                        ))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::XYPad(xy_pad) => node_widget
+                        .add_component(widget::node::Component::XYPad(new_xy_pad_widget(
+                            node.id(),
+                            xy_pad,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Envelope(envelope) => node_widget
+                        .add_component(widget::node::Component::Envelope(new_envelope_widget(
+                            node.id(),
+                            envelope,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::NumberInput(number_input) => node_widget
+                        .add_component(new_number_input_widget(node.id(), number_input, &actions))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::DropDown(dropdown) => node_widget
+                        .add_component(widget::node::Component::DropDown(new_dropdown_widget(
+                            node.id(),
+                            dropdown,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Plot(plot) => node_widget
+                        .add_component(widget::node::Component::Plot(new_plot_widget(plot)))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Image(image) => node_widget
+                        .add_component(widget::node::Component::Image(new_image_widget(
+                            image,
+                            image_textures.get(node.id(), image.key()),
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Canvas(canvas) => node_widget
+                        .add_component(widget::node::Component::Canvas(new_canvas_widget(
+                            node.id(),
+                            canvas,
+                            &actions,
+                        )))
+                        .add_component(widget::node::Component::Space(10.0)),
+                    Widget::Knob(knob) => {
+                        let mut knob_row = widget::knob::KnobRow::new().add_knob(new_knob_widget(
+                            node.id(),
+                            knob,
+                            &actions,
+                        ));
+                        while let Some(Widget::Knob(knob)) = widgets.next_if(|w| w.is_knob()) {
+                            knob_row =
+                                knob_row.add_knob(new_knob_widget(node.id(), knob, &actions));
+                        }
+                        node_widget
+                            .add_component(widget::node::Component::KnobRow(knob_row))
+                            .add_component(widget::node::Component::Space(10.0))
                     }
-                    None => true,
                 };
-                if new_trigger {
-                    actions.borrow_mut().push(Action::SetTriggeredNode {
-                        node_id: triggered_node_id,
+            }
+
+            node_widget.build(ui);
+
+            let size = ui.item_rect_size();
+            if size != node.size {
+                actions.borrow_mut().push(Action::SetNodeSize {
+                    node_id: node.id().to_string(),
+                    size,
+                });
+            }
+
+            if let Some(action) = draw_node_context_menu(node, ui) {
+                actions.borrow_mut().push(action);
+            }
+
+            if ui.is_item_active() && ui.io().key_ctrl {
+                if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                    actions.borrow_mut().push(Action::ToggleSelection {
+                        id: node.id().to_string(),
+                    });
+                }
+            } else if ui.is_item_active() {
+                if ui.is_mouse_down(imgui::MouseButton::Left)
+                    || ui.is_mouse_dragging(imgui::MouseButton::Left)
+                {
+                    ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+
+                    let triggered_node_id = node.id().to_string();
+                    let new_trigger = match state.triggered_node() {
+                        Some(previously_triggered_node_id) => {
+                            triggered_node_id != *previously_triggered_node_id
+                        }
+                        None => true,
+                    };
+                    if new_trigger {
+                        actions.borrow_mut().push(Action::SetTriggeredNode {
+                            node_id: triggered_node_id,
+                        });
+                    }
+                }
+
+                if ui.is_mouse_dragging(imgui::MouseButton::Left) {
+                    actions.borrow_mut().push(Action::MoveNode {
+                        node_id: node.id().to_string(),
+                        offset: ui.io().mouse_delta,
                     });
                 }
             }
 
-            if ui.is_mouse_dragging(imgui::MouseButton::Left) {
-                actions.borrow_mut().push(Action::MoveNode {
-                    node_id: node.id().to_string(),
-                    offset: ui.io().mouse_delta,
+            unsafe {
+                imgui::sys::igSetItemAllowOverlap();
+            }
+        });
+
+    if !state.drag_positions().is_empty() && ui.is_mouse_released(imgui::MouseButton::Left) {
+        actions.borrow_mut().push(Action::FinishNodeDrag);
+    }
+
+    let subgraphs: Vec<_> = state
+        .subgraphs()
+        .iter()
+        .filter(|s| s.parent.as_deref() == level)
+        .map(|s| (s, state.subgraph_pins(s.id())))
+        .collect();
+
+    subgraphs.iter().for_each(|(subgraph, pins)| {
+        let mut node_widget = widget::node::Node::new(subgraph.id_im())
+            .position(vec2::sum(&[subgraph.position, state.offset]))
+            .thick(is_selected(subgraph.id()))
+            .add_component(widget::node::Component::Space(10.0))
+            .add_component(widget::node::Component::Label(widget::label::Label::new(
+                subgraph.label_im(),
+            )))
+            .add_component(widget::node::Component::Space(10.0));
+
+        if !pins.is_empty() {
+            let pin_group =
+                pins.iter()
+                    .fold(widget::pin_group::PinGroup::new(), |pin_group, pin| {
+                        pin_group.add_pin(new_pin_widget(
+                            imgui::ImString::from(format!(
+                                "{}:{}:{}",
+                                subgraph.id(),
+                                pin.address().node_id(),
+                                pin.address().pin_class()
+                            )),
+                            pin.label(),
+                            pin.direction(),
+                            pin.address(),
+                            &pin_positions,
+                            &newly_triggered_pin,
+                        ))
+                    });
+            node_widget = node_widget
+                .add_component(widget::node::Component::PinGroup(pin_group))
+                .add_component(widget::node::Component::Space(10.0));
+        }
+
+        node_widget.build(ui);
+
+        if ui.is_item_hovered() && ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
+            actions.borrow_mut().push(Action::OpenSubgraph {
+                subgraph_id: Some(subgraph.id().to_string()),
+            });
+        } else if ui.is_item_active() && ui.io().key_ctrl {
+            if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                actions.borrow_mut().push(Action::ToggleSelection {
+                    id: subgraph.id().to_string(),
                 });
             }
+        } else if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+            actions.borrow_mut().push(Action::MoveSubgraph {
+                subgraph_id: subgraph.id().to_string(),
+                offset: ui.io().mouse_delta,
+            });
         }
 
         unsafe {
@@ -283,11 +746,31 @@ fn draw_nodes(state: &State, ui: &imgui::Ui) -> (Vec<Action>, HashMap<PinAddress
         }
     });
 
+    if !state.selection().is_empty()
+        && (ui.is_key_pressed(imgui::Key::Escape)
+            || (ui.is_mouse_clicked(imgui::MouseButton::Left)
+                && !ui.io().key_ctrl
+                && !ui.is_any_item_hovered()))
+    {
+        actions.borrow_mut().push(Action::ResetSelection);
+    }
+
     if let Some(previously_triggered_node_id) = state.triggered_node() {
         if ui.is_key_pressed(imgui::Key::Delete) {
             actions.borrow_mut().push(Action::RemoveNode {
                 node_id: previously_triggered_node_id.to_string(),
             });
+        } else if ui.is_key_pressed(imgui::Key::Space) && !ui.io().want_text_input {
+            if let Some(node) = state
+                .nodes()
+                .iter()
+                .find(|n| n.id() == previously_triggered_node_id)
+            {
+                actions.borrow_mut().push(Action::SetBypass {
+                    node_id: node.id().to_string(),
+                    bypassed: !node.bypassed,
+                });
+            }
         } else if ui.is_mouse_clicked(imgui::MouseButton::Left)
             || ui.is_mouse_clicked(imgui::MouseButton::Right)
             || ui.is_key_pressed(imgui::Key::Escape)
@@ -314,43 +797,167 @@ fn draw_nodes(state: &State, ui: &imgui::Ui) -> (Vec<Action>, HashMap<PinAddress
     )
 }
 
+fn new_label_widget<'a>(
+    node: &'a Node,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::label::Label<'a> {
+    let node_id = node.id().to_string();
+    let actions = Rc::clone(actions);
+    widget::label::Label::new(node.label_im()).double_click_callback(Box::new(move || {
+        actions
+            .borrow_mut()
+            .push(Action::StartNodeRename { node_id });
+    }))
+}
+
+fn new_label_input_widget(
+    node_id: &str,
+    buffer: &str,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::label_input::LabelInput {
+    let id = imgui::ImString::from(format!("##{}:label", node_id));
+    let node_id = node_id.to_string();
+    let original_buffer = buffer.to_owned();
+    let mut content = imgui::ImString::new(buffer);
+    content.reserve(NODE_LABEL_CAPACITY);
+    let actions = Rc::clone(actions);
+    widget::label_input::LabelInput::new(id, content).content_callback(Box::new(
+        move |c, finished| {
+            let label = c.to_str().to_owned();
+            if finished {
+                actions.borrow_mut().push(if label.is_empty() {
+                    Action::CancelNodeRename
+                } else {
+                    Action::SetNodeLabel { node_id, label }
+                });
+            } else if label != original_buffer {
+                actions
+                    .borrow_mut()
+                    .push(Action::SetNodeRenameBuffer { value: label });
+            }
+        },
+    ))
+}
+
+/// Context menu opened by right-clicking the last drawn node.
+fn draw_node_context_menu(node: &Node, ui: &imgui::Ui) -> Option<Action> {
+    let mut action = None;
+
+    let style_vars = ui.push_style_vars(&[imgui::StyleVar::WindowPadding([10.0, 8.0])]);
+
+    if unsafe { imgui_sys::igBeginPopupContextItem(ptr::null(), 1) } {
+        if imgui::MenuItem::new(im_str!("Bypass"))
+            .shortcut(im_str!("Space"))
+            .selected(node.bypassed)
+            .build(ui)
+        {
+            action = Some(Action::SetBypass {
+                node_id: node.id().to_string(),
+                bypassed: !node.bypassed,
+            })
+        }
+
+        if imgui::MenuItem::new(im_str!("Auto layout downstream")).build(ui) {
+            action = Some(Action::AutoLayout {
+                root_node_id: Some(node.id().to_string()),
+            })
+        }
+
+        if imgui::MenuItem::new(im_str!("Remove"))
+            .shortcut(im_str!("Delete"))
+            .build(ui)
+        {
+            action = Some(Action::RemoveNode {
+                node_id: node.id().to_string(),
+            })
+        }
+
+        unsafe { imgui_sys::igEndPopup() };
+    }
+
+    style_vars.pop(ui);
+
+    action
+}
+
+fn new_node_resize_callback(
+    node: &Node,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> Box<dyn FnOnce(&imgui::Ui)> {
+    let node_id = node.id().to_string();
+    let size = node.size;
+    let actions = Rc::clone(actions);
+    Box::new(move |ui: &imgui::Ui| {
+        if ui.is_item_hovered() || ui.is_item_active() {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::ResizeNWSE));
+        }
+        if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
+            actions.borrow_mut().push(Action::ResizeNode {
+                node_id,
+                size: vec2::sum(&[size, ui.io().mouse_delta]),
+            });
+        }
+    })
+}
+
+fn is_pin_connected(state: &State, node: &Node, pin: &Pin) -> bool {
+    state.patches().iter().any(|p| {
+        [p.source(), p.destination()]
+            .iter()
+            .any(|a| a.node_id() == node.id() && a.pin_class() == pin.class())
+    })
+}
+
 fn new_pin_group_widget<'a>(
     node: &'a Node,
+    pins: &[&'a Pin],
     pin_positions: &'a Rc<RefCell<HashMap<PinAddress, [f32; 2]>>>,
     triggered_pin: &'a Rc<RefCell<Option<PinAddress>>>,
 ) -> widget::pin_group::PinGroup<'a> {
-    node.pins()
-        .iter()
+    pins.iter()
         .fold(widget::pin_group::PinGroup::new(), |pin_group, pin| {
-            let ui_callback = {
-                let pin_address = PinAddress::new(node.id().to_string(), pin.class().to_string());
-                let newly_triggered_pin = Rc::clone(triggered_pin);
-                Box::new(move |ui: &imgui::Ui| {
-                    if ui.is_item_active() && ui.is_mouse_clicked(imgui::MouseButton::Left) {
-                        *newly_triggered_pin.borrow_mut() = Some(pin_address);
-                    };
-                })
-            };
-            let patch_position_callback = {
-                let pin_address = PinAddress::new(node.id().to_string(), pin.class().to_string());
-                let pin_positions = Rc::clone(pin_positions);
-                Box::new(move |position| {
-                    pin_positions.borrow_mut().insert(pin_address, position);
-                })
+            pin_group.add_pin(new_pin_widget(
+                imgui::ImString::from(format!("{}:{}", node.id(), pin.class())),
+                pin.label_im(),
+                pin.direction(),
+                &PinAddress::new(node.id().to_string(), pin.class().to_string()),
+                pin_positions,
+                triggered_pin,
+            ))
+        })
+}
+
+fn new_pin_widget<'a>(
+    id: imgui::ImString,
+    label: &'a imgui::ImStr,
+    direction: Direction,
+    pin_address: &PinAddress,
+    pin_positions: &Rc<RefCell<HashMap<PinAddress, [f32; 2]>>>,
+    triggered_pin: &Rc<RefCell<Option<PinAddress>>>,
+) -> widget::pin::Pin<'a> {
+    let ui_callback = {
+        let pin_address = pin_address.clone();
+        let newly_triggered_pin = Rc::clone(triggered_pin);
+        Box::new(move |ui: &imgui::Ui| {
+            if ui.is_item_active() && ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                *newly_triggered_pin.borrow_mut() = Some(pin_address);
             };
-            pin_group.add_pin(
-                widget::pin::Pin::new(
-                    imgui::ImString::from(format!("{}:{}", node.id(), pin.class())),
-                    pin.label_im(),
-                )
-                .orientation(match pin.direction() {
-                    Direction::Input => widget::pin::Orientation::Left,
-                    Direction::Output => widget::pin::Orientation::Right,
-                })
-                .ui_callback(ui_callback)
-                .patch_position_callback(patch_position_callback),
-            )
         })
+    };
+    let patch_position_callback = {
+        let pin_address = pin_address.clone();
+        let pin_positions = Rc::clone(pin_positions);
+        Box::new(move |position| {
+            pin_positions.borrow_mut().insert(pin_address, position);
+        })
+    };
+    widget::pin::Pin::new(id, label)
+        .orientation(match direction {
+            Direction::Input => widget::pin::Orientation::Left,
+            Direction::Output => widget::pin::Orientation::Right,
+        })
+        .ui_callback(ui_callback)
+        .patch_position_callback(patch_position_callback)
 }
 
 fn new_text_box_widget(
@@ -441,16 +1048,42 @@ fn new_button_widget(
 fn new_slider_widget(
     node_id: &str,
     slider: &Slider,
+    text_input: bool,
     actions: &Rc<RefCell<Vec<Action>>>,
 ) -> widget::slider::Slider {
     let id = imgui::ImString::from(format!("##{}:{}", node_id, slider.key()));
+    let text_input_callback = {
+        let node_id = node_id.to_string();
+        let widget_key = slider.key().to_string();
+        let actions = Rc::clone(actions);
+        Box::new(move |started| {
+            actions.borrow_mut().push(if started {
+                Action::StartSliderTextInput {
+                    node_id,
+                    key: widget_key,
+                }
+            } else {
+                Action::FinishSliderTextInput
+            });
+        })
+    };
     let node_id = node_id.to_string();
     let widget_key = slider.key().to_string();
     let original_value = slider.value();
     let actions = Rc::clone(&actions);
     widget::slider::Slider::new(id, slider.min(), slider.max(), slider.value())
+        .default(slider.default())
+        .scale(match slider.scale() {
+            Scale::Linear => widget::slider::Scale::Linear,
+            Scale::Logarithmic => widget::slider::Scale::Logarithmic,
+            Scale::Exponential { curve } => widget::slider::Scale::Exponential { curve },
+            Scale::Steps { step } => widget::slider::Scale::Steps { step },
+        })
         .min_width(slider.width())
         .display_format(slider.display_format_im().clone())
+        .unit(slider.unit().clone())
+        .text_input(text_input)
+        .text_input_callback(text_input_callback)
         .value_callback(Box::new(move |new_value| {
             if (new_value - original_value).abs() > 0.000000001 {
                 actions.borrow_mut().push({
@@ -464,6 +1097,197 @@ fn new_slider_widget(
         }))
 }
 
+fn new_range_slider_widget(
+    node_id: &str,
+    range_slider: &RangeSlider,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::range_slider::RangeSlider {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, range_slider.key()));
+    let node_id = node_id.to_string();
+    let widget_key = range_slider.key().to_string();
+    let original_value = range_slider.value();
+    let actions = Rc::clone(actions);
+    widget::range_slider::RangeSlider::new(
+        id,
+        range_slider.min(),
+        range_slider.max(),
+        range_slider.value(),
+    )
+    .min_width(range_slider.width())
+    .display_format(range_slider.display_format_im().clone())
+    .value_callback(Box::new(move |new_value| {
+        if new_value != original_value {
+            actions.borrow_mut().push(Action::SetValue {
+                node_id,
+                key: widget_key,
+                value: Value::F32F32(new_value),
+            });
+        }
+    }))
+}
+
+fn new_xy_pad_widget(
+    node_id: &str,
+    xy_pad: &XYPad,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::xy_pad::XYPad {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, xy_pad.key()));
+    let node_id = node_id.to_string();
+    let widget_key = xy_pad.key().to_string();
+    let original_value = xy_pad.value();
+    let actions = Rc::clone(actions);
+    widget::xy_pad::XYPad::new(id, xy_pad.x_range(), xy_pad.y_range(), xy_pad.value())
+        .size(xy_pad.size())
+        .value_callback(Box::new(move |new_value| {
+            if new_value != original_value {
+                actions.borrow_mut().push(Action::SetValue {
+                    node_id,
+                    key: widget_key,
+                    value: Value::Point(new_value),
+                });
+            }
+        }))
+}
+
+fn new_envelope_widget(
+    node_id: &str,
+    envelope: &Envelope,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::envelope::Envelope {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, envelope.key()));
+    let node_id = node_id.to_string();
+    let widget_key = envelope.key().to_string();
+    let original_points = envelope.points().clone();
+    let original_curvatures = envelope.curvatures().clone();
+    let curved = envelope.curved();
+    let actions = Rc::clone(actions);
+    widget::envelope::Envelope::new(
+        id,
+        envelope.x_range(),
+        envelope.y_range(),
+        envelope.points().clone(),
+        envelope.curvatures().clone(),
+    )
+    .curved(curved)
+    .size(envelope.size())
+    .value_callback(Box::new(move |points, curvatures| {
+        if points != original_points || curvatures != original_curvatures {
+            let value = if curved {
+                Value::VecF32F32F32(
+                    points
+                        .iter()
+                        .zip(curvatures.iter().chain(std::iter::once(&0.0)))
+                        .map(|((x, y), c)| (*x, *y, *c))
+                        .collect(),
+                )
+            } else {
+                Value::VecF32F32(points)
+            };
+            actions.borrow_mut().push(Action::SetValue {
+                node_id,
+                key: widget_key,
+                value,
+            });
+        }
+    }))
+}
+
+fn new_knob_widget(
+    node_id: &str,
+    knob: &Knob,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::knob::Knob {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, knob.key()));
+    let node_id = node_id.to_string();
+    let widget_key = knob.key().to_string();
+    let original_value = knob.value();
+    let actions = Rc::clone(actions);
+    widget::knob::Knob::new(id, knob.min(), knob.max(), knob.value())
+        .size(knob.size())
+        .display_format(knob.display_format_im().clone())
+        .value_callback(Box::new(move |new_value| {
+            if (new_value - original_value).abs() > 0.000000001 {
+                actions.borrow_mut().push(Action::SetValue {
+                    node_id,
+                    key: widget_key,
+                    value: Value::F32(new_value),
+                });
+            }
+        }))
+}
+
+fn new_number_input_widget<'a>(
+    node_id: &str,
+    number_input: &NumberInput,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::node::Component<'a> {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, number_input.key()));
+    let node_id = node_id.to_string();
+    let widget_key = number_input.key().to_string();
+    let actions = Rc::clone(actions);
+    let has_range = number_input.min().is_some() || number_input.max().is_some();
+    match number_input.value() {
+        Number::F32(original_value) => {
+            let mut widget = widget::number_input::NumberInput::new(id, original_value);
+            if has_range {
+                let min = match number_input.min() {
+                    Some(Number::F32(min)) => min,
+                    _ => f32::MIN,
+                };
+                let max = match number_input.max() {
+                    Some(Number::F32(max)) => max,
+                    _ => f32::MAX,
+                };
+                widget = widget.range(min, max);
+            }
+            widget::node::Component::NumberInputF32(
+                widget
+                    .step(number_input.step())
+                    .min_width(number_input.width())
+                    .display_format(number_input.display_format_im().clone())
+                    .value_callback(Box::new(move |new_value| {
+                        if (new_value - original_value).abs() > 0.000000001 {
+                            actions.borrow_mut().push(Action::SetValue {
+                                node_id,
+                                key: widget_key,
+                                value: Value::F32(new_value),
+                            });
+                        }
+                    })),
+            )
+        }
+        Number::I32(original_value) => {
+            let mut widget = widget::number_input::NumberInput::new(id, original_value);
+            if has_range {
+                let min = match number_input.min() {
+                    Some(Number::I32(min)) => min,
+                    _ => i32::MIN,
+                };
+                let max = match number_input.max() {
+                    Some(Number::I32(max)) => max,
+                    _ => i32::MAX,
+                };
+                widget = widget.range(min, max);
+            }
+            widget::node::Component::NumberInputI32(
+                widget
+                    .step(number_input.step())
+                    .min_width(number_input.width())
+                    .display_format(number_input.display_format_im().clone())
+                    .value_callback(Box::new(move |new_value| {
+                        if new_value != original_value {
+                            actions.borrow_mut().push(Action::SetValue {
+                                node_id,
+                                key: widget_key,
+                                value: Value::I32(new_value),
+                            });
+                        }
+                    })),
+            )
+        }
+    }
+}
+
 fn new_dropdown_widget(
     node_id: &str,
     dropdown: &DropDown,
@@ -502,39 +1326,205 @@ fn new_dropdown_widget(
     }))
 }
 
-fn new_canvas_widget(canvas: &Canvas) -> widget::canvas::Canvas {
-    widget::canvas::Canvas::new(canvas.dots(), canvas.size()[0], canvas.size()[1])
+fn new_plot_widget(plot: &Plot) -> widget::plot::Plot<'_> {
+    plot.series().iter().zip(plot.samples().iter()).fold(
+        widget::plot::Plot::new(plot.y_range(), plot.size()[0], plot.size()[1])
+            .axes(plot.axes())
+            .grid(plot.grid()),
+        |plot_widget, (series, samples)| {
+            plot_widget.add_series(samples, series.label(), series.color())
+        },
+    )
+}
+
+fn new_image_widget(image: &Image, texture_id: Option<imgui::TextureId>) -> widget::image::Image {
+    let image_widget = widget::image::Image::new(image.size()[0], image.size()[1]);
+    match (texture_id, image.data()) {
+        (Some(texture_id), Some(data)) => {
+            image_widget.texture(texture_id, [data.width() as f32, data.height() as f32])
+        }
+        _ => image_widget,
+    }
+}
+
+fn new_canvas_widget<'a>(
+    node_id: &str,
+    canvas: &'a Canvas,
+    actions: &Rc<RefCell<Vec<Action>>>,
+) -> widget::canvas::Canvas<'a> {
+    let id = imgui::ImString::from(format!("##{}:{}", node_id, canvas.key()));
+    let canvas_widget = canvas.shapes().iter().fold(
+        widget::canvas::Canvas::new(id, canvas.dots(), canvas.size()[0], canvas.size()[1]),
+        |canvas_widget, shape| canvas_widget.add_shape(new_canvas_shape(shape)),
+    );
+
+    if !canvas.interactive() {
+        return canvas_widget;
+    }
+
+    let node_id = node_id.to_string();
+    let widget_key = canvas.key().to_string();
+    let actions = Rc::clone(actions);
+    canvas_widget.input_callback(Box::new(move |position, input| {
+        let button = |button| match button {
+            imgui::MouseButton::Right => MouseButton::Right,
+            imgui::MouseButton::Middle => MouseButton::Middle,
+            _ => MouseButton::Left,
+        };
+        actions.borrow_mut().push(Action::CanvasInput {
+            node_id,
+            key: widget_key,
+            position,
+            event: match input {
+                widget::canvas::Input::Press(b) => CanvasEvent::Press(button(b)),
+                widget::canvas::Input::Drag(b) => CanvasEvent::Drag(button(b)),
+                widget::canvas::Input::Release(b) => CanvasEvent::Release(button(b)),
+                widget::canvas::Input::Scroll(delta) => CanvasEvent::Scroll(delta),
+            },
+        });
+    }))
+}
+
+fn new_canvas_shape(shape: &Shape) -> widget::canvas::Shape<'_> {
+    match shape {
+        Shape::Line {
+            from,
+            to,
+            color,
+            thickness,
+        } => widget::canvas::Shape::Line {
+            from: *from,
+            to: *to,
+            color: *color,
+            thickness: *thickness,
+        },
+        Shape::Polyline {
+            points,
+            color,
+            thickness,
+        } => widget::canvas::Shape::Polyline {
+            points,
+            color: *color,
+            thickness: *thickness,
+        },
+        Shape::Rect {
+            min,
+            max,
+            color,
+            filled,
+        } => widget::canvas::Shape::Rect {
+            min: *min,
+            max: *max,
+            color: *color,
+            filled: *filled,
+        },
+        Shape::Circle {
+            center,
+            radius,
+            color,
+            filled,
+        } => widget::canvas::Shape::Circle {
+            center: *center,
+            radius: *radius,
+            color: *color,
+            filled: *filled,
+        },
+        Shape::Triangle {
+            points,
+            color,
+            filled,
+        } => widget::canvas::Shape::Triangle {
+            points: *points,
+            color: *color,
+            filled: *filled,
+        },
+        Shape::Text {
+            position,
+            color,
+            text,
+        } => widget::canvas::Shape::Text {
+            position: *position,
+            color: *color,
+            text,
+        },
+    }
 }
 
 fn draw_patches(
     state: &State,
-    pin_positions: HashMap<PinAddress, [f32; 2]>,
+    pin_positions: &HashMap<PinAddress, [f32; 2]>,
     ui: &imgui::Ui,
 ) -> Vec<Action> {
-    if let Some(triggered_pin_address) = state.triggered_pin() {
-        let source = pin_positions[triggered_pin_address];
+    // The triggered pin may be hidden, e.g. when its node got collapsed.
+    if let Some(source) = state
+        .triggered_pin()
+        .as_ref()
+        .and_then(|p| pin_positions.get(p))
+    {
         let destination = ui.io().mouse_pos;
-        draw_patch(source, destination, 1.0, ui);
+        draw_patch(*source, destination, 1.0, ui);
     }
 
     let mut newly_triggered_patch = None;
+    let mut drop_target_patch = None;
+    let mut waypoint_actions = Vec::new();
+    let dragged_node = dragged_insertable_node(state, ui);
 
-    state.patches().iter().for_each(|p| {
-        let source = pin_positions[p.source()];
-        let destination = pin_positions[p.destination()];
-        let thickness = if is_patch_triggered(state, p) {
-            2.0
-        } else {
-            1.0
-        };
-        draw_patch(source, destination, thickness, ui);
+    state
+        .patches()
+        .iter()
+        .filter(|p| is_patch_visible(state, p))
+        .for_each(|p| {
+            let points = patch_points(state, pin_positions, p);
+            let thickness = if is_patch_triggered(state, p) || is_patch_drop_target(state, p) {
+                2.0
+            } else {
+                1.0
+            };
+            points
+                .windows(2)
+                .for_each(|segment| draw_patch(segment[0], segment[1], thickness, ui));
+
+            if let Some(segment) = patch_segment_under_mouse(&points, ui) {
+                if let Some(node) = dragged_node {
+                    if p.source().node_id() != node.id() && p.destination().node_id() != node.id() {
+                        drop_target_patch = Some(p.clone());
+                    }
+                } else if ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
+                    waypoint_actions.push(Action::AddWaypoint {
+                        patch: p.clone(),
+                        index: segment,
+                        position: vec2::sum(&[
+                            ui.io().mouse_pos,
+                            [-state.offset[0], -state.offset[1]],
+                        ]),
+                    });
+                } else if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                    newly_triggered_patch = Some(p.clone());
+                }
+            }
 
-        if is_patch_clicked(&pin_positions, p, ui) {
-            newly_triggered_patch = Some(p.clone());
-        }
-    });
+            waypoint_actions.extend(draw_waypoints(state, p, ui));
+        });
 
-    let mut actions = Vec::new();
+    let mut actions = waypoint_actions;
+
+    // The target is recomputed on every frame, so it is cleared as soon as the
+    // drag is released or cancelled.
+    if drop_target_patch != *state.drop_target_patch() {
+        actions.push(Action::SetDropTargetPatch {
+            patch: drop_target_patch,
+        });
+    }
+
+    if let (Some(patch), Some(node_id)) = (state.drop_target_patch(), state.triggered_node()) {
+        if ui.is_mouse_released(imgui::MouseButton::Left) {
+            actions.push(Action::InsertNodeIntoPatch {
+                node_id: node_id.clone(),
+                patch: patch.clone(),
+            });
+        }
+    }
 
     if let Some(newly_triggered_patch) = newly_triggered_patch {
         actions.push(Action::SetTriggeredPatch {
@@ -557,6 +1547,113 @@ fn draw_patches(
     actions
 }
 
+/// Patch is visible if it connects two different items on the currently open
+/// level of nesting.
+fn is_patch_visible(state: &State, patch: &Patch) -> bool {
+    let level = state.open_subgraph().as_deref();
+    let source = state.representative_on_level(patch.source().node_id(), level);
+    let destination = state.representative_on_level(patch.destination().node_id(), level);
+    matches!((source, destination), (Some(a), Some(b)) if a != b)
+}
+
+fn draw_minimap(
+    state: &State,
+    pin_positions: &HashMap<PinAddress, [f32; 2]>,
+    ui: &imgui::Ui,
+) -> Option<Action> {
+    let window_size = ui.window_size();
+    let to_absolute = |p: [f32; 2]| vec2::sum(&[p, [-state.offset[0], -state.offset[1]]]);
+    let viewport_top_left = to_absolute([0.0, 0.0]);
+
+    let level = state.open_subgraph().as_deref();
+    let mut minimap = widget::minimap::Minimap::new(im_str!("##minimap"))
+        .position(vec2::sum(&[
+            window_size,
+            [-MINIMAP_SIZE[0], -MINIMAP_SIZE[1]],
+            [-MINIMAP_MARGIN, -MINIMAP_MARGIN],
+        ]))
+        .size(MINIMAP_SIZE)
+        .viewport(viewport_top_left, to_absolute(window_size));
+
+    for node in state
+        .nodes()
+        .iter()
+        .filter(|n| n.parent.as_deref() == level)
+    {
+        minimap = minimap.add_rect(node.position, vec2::sum(&[node.position, node.size]));
+    }
+
+    for patch in state
+        .patches()
+        .iter()
+        .filter(|p| is_patch_visible(state, p))
+    {
+        minimap = minimap.add_line(
+            to_absolute(pin_positions[patch.source()]),
+            to_absolute(pin_positions[patch.destination()]),
+        );
+    }
+
+    let action = Rc::new(RefCell::new(None));
+    {
+        let action = Rc::clone(&action);
+        minimap = minimap.jump_callback(Box::new(move |center| {
+            *action.borrow_mut() = Some(Action::SetOffset {
+                offset: [
+                    window_size[0] / 2.0 - center[0],
+                    window_size[1] / 2.0 - center[1],
+                ],
+            });
+        }));
+    }
+    minimap.build(ui);
+
+    action.take()
+}
+
+fn draw_selection_rectangle(state: &State, ui: &imgui::Ui) {
+    if let Some([a, b]) = state.selection_rectangle() {
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(
+                vec2::sum(&[*a, state.offset]),
+                vec2::sum(&[*b, state.offset]),
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .build();
+    }
+}
+
+fn draw_breadcrumbs(state: &State, ui: &imgui::Ui) -> Option<Action> {
+    let open_subgraph = state.open_subgraph().as_ref()?;
+
+    let mut action = None;
+
+    ui.set_cursor_screen_pos(BREADCRUMBS_POSITION);
+    if ui.small_button(im_str!("Root")) {
+        action = Some(Action::OpenSubgraph { subgraph_id: None });
+    }
+
+    for subgraph in state.subgraph_path(open_subgraph) {
+        ui.same_line(0.0);
+        ui.text(">");
+        ui.same_line(0.0);
+        if subgraph.id() == open_subgraph {
+            ui.text(subgraph.label());
+        } else if ui.small_button(&imgui::ImString::from(format!(
+            "{}##breadcrumb:{}",
+            subgraph.label(),
+            subgraph.id()
+        ))) {
+            action = Some(Action::OpenSubgraph {
+                subgraph_id: Some(subgraph.id().to_owned()),
+            });
+        }
+    }
+
+    action
+}
+
 fn draw_patch(a: [f32; 2], b: [f32; 2], thickness: f32, ui: &imgui::Ui) {
     let draw_list = ui.get_window_draw_list();
     draw_list
@@ -565,6 +1662,32 @@ fn draw_patch(a: [f32; 2], b: [f32; 2], thickness: f32, ui: &imgui::Ui) {
         .build();
 }
 
+fn is_patch_drop_target(state: &State, patch: &Patch) -> bool {
+    matches!(state.drop_target_patch(), Some(p) if p == patch)
+}
+
+/// Node which is being dragged and can be inserted into a patch, i.e. it has
+/// exactly one input and one output pin.
+fn dragged_insertable_node<'a>(state: &'a State, ui: &imgui::Ui) -> Option<&'a Node> {
+    if !ui.is_mouse_dragging(imgui::MouseButton::Left) {
+        return None;
+    }
+
+    let node_id = state.triggered_node().as_ref()?;
+    let node = state.nodes().iter().find(|n| n.id() == node_id)?;
+    let count_pins = |direction| {
+        node.pins()
+            .iter()
+            .filter(|p| p.direction() == direction)
+            .count()
+    };
+    if count_pins(Direction::Input) == 1 && count_pins(Direction::Output) == 1 {
+        Some(node)
+    } else {
+        None
+    }
+}
+
 fn is_patch_triggered(state: &State, patch: &Patch) -> bool {
     if let Some(triggered_patch) = state.triggered_patch() {
         triggered_patch == patch
@@ -573,25 +1696,88 @@ fn is_patch_triggered(state: &State, patch: &Patch) -> bool {
     }
 }
 
-fn is_patch_clicked(
+/// Points the patch line goes through, starting on the source pin, passing all
+/// the waypoints and ending on the destination pin.
+fn patch_points(
+    state: &State,
     pin_positions: &HashMap<PinAddress, [f32; 2]>,
     patch: &Patch,
-    ui: &imgui::Ui,
-) -> bool {
-    if ui.is_mouse_clicked(imgui::MouseButton::Left) {
-        let source = pin_positions[patch.source()];
-        let destination = pin_positions[patch.destination()];
-        let distance_from_line = distance_from_line(ui.io().mouse_pos, (source, destination));
-        let distance_from_source = distance_between_points(ui.io().mouse_pos, source);
-        let distance_from_destination = distance_between_points(ui.io().mouse_pos, destination);
-        if distance_from_line < PATCH_CLICK_MAX_DISTANCE
-            && distance_from_source > PATCH_CLICK_MAX_DISTANCE
-            && distance_from_destination > PATCH_CLICK_MAX_DISTANCE
-        {
-            return true;
+) -> Vec<[f32; 2]> {
+    let mut points = vec![pin_positions[patch.source()]];
+    if let Some(waypoints) = state.waypoints().get(patch) {
+        points.extend(waypoints.iter().map(|w| vec2::sum(&[*w, state.offset])));
+    }
+    points.push(pin_positions[patch.destination()]);
+    points
+}
+
+/// Index of the segment of the patch which is hovered by the mouse. Surrounding
+/// of pins and waypoints is excluded so they can be clicked instead.
+fn patch_segment_under_mouse(points: &[[f32; 2]], ui: &imgui::Ui) -> Option<usize> {
+    let mouse_pos = ui.io().mouse_pos;
+
+    if points
+        .iter()
+        .any(|p| distance_between_points(mouse_pos, *p) <= PATCH_CLICK_MAX_DISTANCE)
+    {
+        return None;
+    }
+
+    points.windows(2).position(|segment| {
+        let (a, b) = (segment[0], segment[1]);
+        let within_bounds = (0..2).all(|i| {
+            mouse_pos[i] >= f32::min(a[i], b[i]) - PATCH_CLICK_MAX_DISTANCE
+                && mouse_pos[i] <= f32::max(a[i], b[i]) + PATCH_CLICK_MAX_DISTANCE
+        });
+        within_bounds && distance_from_line(mouse_pos, (a, b)) < PATCH_CLICK_MAX_DISTANCE
+    })
+}
+
+fn draw_waypoints(state: &State, patch: &Patch, ui: &imgui::Ui) -> Vec<Action> {
+    let mut actions = Vec::new();
+
+    let waypoints = match state.waypoints().get(patch) {
+        Some(waypoints) => waypoints,
+        None => return actions,
+    };
+
+    let draw_list = ui.get_window_draw_list();
+    for (index, waypoint) in waypoints.iter().enumerate() {
+        let center = vec2::sum(&[*waypoint, state.offset]);
+        draw_list
+            .add_circle(center, WAYPOINT_RADIUS, [0.0, 0.0, 0.0])
+            .filled(true)
+            .build();
+
+        ui.set_cursor_screen_pos(vec2::sum(&[center, [-WAYPOINT_RADIUS, -WAYPOINT_RADIUS]]));
+        ui.invisible_button(
+            &imgui::ImString::from(format!(
+                "##waypoint:{}:{}:{}:{}:{}",
+                patch.source().node_id(),
+                patch.source().pin_class(),
+                patch.destination().node_id(),
+                patch.destination().pin_class(),
+                index
+            )),
+            [WAYPOINT_RADIUS * 2.0, WAYPOINT_RADIUS * 2.0],
+        );
+
+        if ui.is_item_hovered() && ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
+            actions.push(Action::RemoveWaypoint {
+                patch: patch.clone(),
+                index,
+            });
+        } else if ui.is_item_active() && ui.is_mouse_dragging(imgui::MouseButton::Left) {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+            actions.push(Action::MoveWaypoint {
+                patch: patch.clone(),
+                index,
+                offset: ui.io().mouse_delta,
+            });
         }
     }
-    false
+
+    actions
 }
 
 // https://en.wikipedia.org/wiki/Distance_from_a_point_to_a_line
@@ -5,6 +5,7 @@
 extern crate imgui;
 
 use crate::engine::system;
+use crate::engine::texture::TextureUploader;
 
 const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
 const BLACK: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
@@ -14,10 +15,10 @@ const INVISIBLE: [f32; 4] = [0.0, 0.0, 0.0, 0.0];
 
 pub fn run<F>(title: &str, mut ui_build_callback: F)
 where
-    F: FnMut(&imgui::Ui) + 'static,
+    F: FnMut(&imgui::Ui, &mut dyn TextureUploader) + 'static,
 {
     let s = system::init(title);
-    s.main_loop(move |_, ui| {
+    s.main_loop(move |_, ui, texture_uploader| {
         set_styles(ui, || {
             imgui::Window::new(im_str!("##main_window"))
                 .position([0.0, 0.0], imgui::Condition::Always)
@@ -29,7 +30,7 @@ where
                 .title_bar(false)
                 .bring_to_front_on_focus(false)
                 .build(ui, || {
-                    ui_build_callback(ui);
+                    ui_build_callback(ui, texture_uploader);
                 });
         });
     });
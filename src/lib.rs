@@ -33,12 +33,15 @@
 //!                     direction: Output,
 //!                 },
 //!             ],
+//!             variadic_pins: vec![],
+//!             resizable: false,
 //!             widgets: vec![Switch {
 //!                 label: "Switch".to_owned(),
 //!                 key: "switch".to_owned(),
 //!             }],
 //!         }
 //!     ],
+//!     report_layout: false,
 //! };
 //! ```
 //!
@@ -50,7 +53,7 @@
 //!
 //! ```no_run
 //! # use gazpatcho::config::*;
-//! # let config = Config { node_templates: vec![] };
+//! # let config = Config { node_templates: vec![], report_layout: false };
 //! gazpatcho::run_with_callback("Application Name", config, |report| {
 //!     // Act upon the current report
 //!     dbg!(report);
@@ -70,7 +73,7 @@
 //! # use gazpatcho::config::*;
 //! # use gazpatcho::report::*;
 //! # use gazpatcho::request::*;
-//! # let config = Config { node_templates: vec![] };
+//! # let config = Config { node_templates: vec![], report_layout: false };
 //! use std::sync::mpsc;
 //! use std::thread;
 //!
@@ -92,12 +95,14 @@
 //!
 //! The `dbg!` output of such a configuration would return something like:
 //!
-//! ```ignore
+//! ```text
 //! Report {
 //!     nodes: [
 //!         Node {
 //!             id: "example_node:0",
 //!             class: "example_node",
+//!             label: "Example node",
+//!             bypassed: false,
 //!             data: {
 //!                 "switch": Bool(
 //!                     false,
@@ -107,6 +112,8 @@
 //!         Node {
 //!             id: "example_node:1",
 //!             class: "example_node",
+//!             label: "Example node",
+//!             bypassed: false,
 //!             data: {
 //!                 "switch": Bool(
 //!                     true,
@@ -126,6 +133,27 @@
 //!             },
 //!         },
 //!     ],
+//!     subgraphs: [],
+//!     layout: None,
+//!     canvas_input: None,
+//! }
+//! ```
+//!
+//! The report is marked as `#[non_exhaustive]`, its fields are read directly
+//! or through a pattern ending with `..`:
+//!
+//! ```
+//! # use gazpatcho::report::*;
+//! fn handle(report: Report) {
+//!     let Report {
+//!         nodes,
+//!         patches,
+//!         subgraphs,
+//!         layout,
+//!         canvas_input,
+//!         ..
+//!     } = report;
+//!     dbg!(nodes, patches, subgraphs, layout, canvas_input);
 //! }
 //! ```
 //!
@@ -157,7 +185,7 @@ mod widget;
 
 use std::sync::mpsc;
 
-use engine::{reducer, state, view};
+use engine::{reducer, state, texture, view};
 
 /// Launch the user interface, use a callback to broadcast updates and accept
 /// additional requests.
@@ -188,14 +216,18 @@ where
     F: Fn(report::Report) -> Vec<request::Request> + 'static,
 {
     let mut state = state::State::from(conf);
-    engine::window::run(title, move |ui| {
-        view::draw(&state, ui).into_iter().for_each(|action| {
-            if reducer::reduce(&mut state, action).model_changed() {
-                for request in callback(report::Report::from(&state)) {
-                    reducer::reduce(&mut state, request.into());
+    let mut image_textures = texture::ImageTextures::default();
+    engine::window::run(title, move |ui, texture_uploader| {
+        image_textures.sync(&state, texture_uploader);
+        view::draw(&state, &image_textures, ui)
+            .into_iter()
+            .for_each(|action| {
+                if reducer::reduce(&mut state, action).model_changed() {
+                    for request in callback(report::Report::from(&state)) {
+                        reducer::reduce(&mut state, request.into());
+                    }
                 }
-            }
-        });
+            });
     });
 }
 
@@ -227,12 +259,16 @@ pub fn run_with_mpsc(
     request_rx: mpsc::Receiver<request::Request>,
 ) {
     let mut state = state::State::from(conf);
-    engine::window::run(title, move |ui| {
-        view::draw(&state, ui).into_iter().for_each(|action| {
-            if reducer::reduce(&mut state, action).model_changed() {
-                report_tx.send(report::Report::from(&state)).unwrap();
-            }
-        });
+    let mut image_textures = texture::ImageTextures::default();
+    engine::window::run(title, move |ui, texture_uploader| {
+        image_textures.sync(&state, texture_uploader);
+        view::draw(&state, &image_textures, ui)
+            .into_iter()
+            .for_each(|action| {
+                if reducer::reduce(&mut state, action).model_changed() {
+                    report_tx.send(report::Report::from(&state)).unwrap();
+                }
+            });
 
         for request in request_rx.try_iter() {
             reducer::reduce(&mut state, request.into());
@@ -257,11 +293,15 @@ where
     F: Fn(report::Report) + 'static,
 {
     let mut state = state::State::from(conf);
-    engine::window::run(title, move |ui| {
-        view::draw(&state, ui).into_iter().for_each(|action| {
-            if reducer::reduce(&mut state, action).model_changed() {
-                report_callback(report::Report::from(&state));
-            }
-        });
+    let mut image_textures = texture::ImageTextures::default();
+    engine::window::run(title, move |ui, texture_uploader| {
+        image_textures.sync(&state, texture_uploader);
+        view::draw(&state, &image_textures, ui)
+            .into_iter()
+            .for_each(|action| {
+                if reducer::reduce(&mut state, action).model_changed() {
+                    report_callback(report::Report::from(&state));
+                }
+            });
     });
 }
@@ -2,30 +2,136 @@
 
 use std::collections::HashMap;
 
+use crate::config;
 use crate::engine::action;
+use crate::engine::state;
 
 /// A structure representing a node and its associated values.
+///
+/// More fields may be added to the node in the future, so it cannot be
+/// constructed or exhaustively destructured outside of this crate.
 #[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
 pub struct Node {
     /// Unique identificator of this node.
     pub id: String,
     /// Class references the node template this node was instantiated from.
     pub class: String,
+    /// Label shown in the heading of the node. It is initialized from the node
+    /// template and can be later changed by the user.
+    pub label: String,
+    /// Whether the node was disabled by the user. Bypassed nodes keep their
+    /// patches, it is up to the backend to route the signal around them.
+    pub bypassed: bool,
     /// Map of all the values set via widgets available on the node. The key is
     /// always the key of the given widget as defined in the node template.
     pub data: HashMap<String, Value>,
 }
 
 /// Enum encapsulating possible values of an item attached to a node.
+///
+/// New variants are added together with new widgets, so matches on the value
+/// outside of this crate must include a wildcard arm.
 #[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
 pub enum Value {
     String(String),
     F32(f32),
+    F32F32((f32, f32)),
+    Point((f32, f32)),
+    I32(i32),
     Bool(bool),
     VecF32F32(Vec<(f32, f32)>),
+    VecF32F32F32(Vec<(f32, f32, f32)>),
+    VecF32(Vec<f32>),
+    VecVecF32(Vec<Vec<f32>>),
+    Shapes(Vec<Shape>),
+    /// Image of given dimensions, with 4 bytes per pixel in RGBA order, rows
+    /// going from top to bottom.
+    Rgba8 {
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+    },
     Unavailable,
 }
 
+/// Mouse input received by an interactive `Canvas` widget.
+#[derive(PartialEq, Clone, Debug)]
+pub struct CanvasInput {
+    /// Id of the node the canvas belongs to.
+    pub node_id: String,
+    /// Key of the canvas widget as defined in the node template.
+    pub key: String,
+    /// Position of the mouse in pixels, relative to the top left corner of the
+    /// canvas. While dragging, the mouse may leave the canvas, so the position
+    /// may be out of its bounds.
+    pub position: [f32; 2],
+    pub event: CanvasEvent,
+}
+
+/// Kind of the mouse input received by an interactive `Canvas` widget.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CanvasEvent {
+    /// The button was pressed within the canvas.
+    Press(MouseButton),
+    /// The mouse moved while the button pressed within the canvas is held.
+    Drag(MouseButton),
+    /// The button pressed within the canvas was released.
+    Release(MouseButton),
+    /// The mouse wheel was scrolled over the canvas. Positive delta stands for
+    /// scrolling up.
+    Scroll(f32),
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// A primitive drawn on the `Canvas` widget. Coordinates are in pixels,
+/// relative to the top left corner of the canvas. Colors are RGBA, each
+/// component between 0.0 and 1.0. Anything reaching beyond the canvas is cut
+/// off.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Shape {
+    Line {
+        from: [f32; 2],
+        to: [f32; 2],
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Polyline {
+        points: Vec<[f32; 2]>,
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Rect {
+        min: [f32; 2],
+        max: [f32; 2],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+        color: [f32; 4],
+        filled: bool,
+    },
+    Triangle {
+        points: [[f32; 2]; 3],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Text {
+        position: [f32; 2],
+        color: [f32; 4],
+        text: String,
+    },
+}
+
 impl Value {
     /// Access string value stored in the enum.
     ///
@@ -53,6 +159,45 @@ impl Value {
         }
     }
 
+    /// Access pair of f32 values stored in the enum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is not `F32F32`.
+    pub fn unwrap_f32_f32(&self) -> (f32, f32) {
+        if let Self::F32F32(value) = self {
+            *value
+        } else {
+            panic!("The value is not of type F32F32");
+        }
+    }
+
+    /// Access `x` and `y` coordinates of a point stored in the enum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is not `Point`.
+    pub fn unwrap_point(&self) -> (f32, f32) {
+        if let Self::Point(value) = self {
+            *value
+        } else {
+            panic!("The value is not of type Point");
+        }
+    }
+
+    /// Access i32 value stored in the enum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is not `I32`.
+    pub fn unwrap_i32(&self) -> i32 {
+        if let Self::I32(value) = self {
+            *value
+        } else {
+            panic!("The value is not of type I32");
+        }
+    }
+
     /// Access bool value stored in the enum.
     ///
     /// # Panics
@@ -72,8 +217,22 @@ impl Into<action::Value> for Value {
         match self {
             Self::Bool(value) => action::Value::Bool(value),
             Self::F32(value) => action::Value::F32(value),
+            Self::F32F32(value) => action::Value::F32F32(value),
+            Self::Point(value) => action::Value::Point(value),
+            Self::I32(value) => action::Value::I32(value),
             Self::String(value) => action::Value::String(value),
             Self::VecF32F32(value) => action::Value::VecF32F32(value),
+            Self::VecF32F32F32(value) => action::Value::VecF32F32F32(value),
+            Self::VecF32(value) => action::Value::VecF32(value),
+            Self::VecVecF32(value) => action::Value::VecVecF32(value),
+            Self::Rgba8 {
+                width,
+                height,
+                data,
+            } => action::Value::Rgba8(state::ImageData::new(width, height, data)),
+            Self::Shapes(value) => {
+                action::Value::Shapes(value.into_iter().map(state::Shape::from).collect())
+            }
             Self::Unavailable => panic!("Unavailable Value cannot be converted"),
         }
     }
@@ -95,3 +254,59 @@ pub struct PinAddress {
     pub node_id: String,
     pub pin_class: String,
 }
+
+/// A structure describing how a node is presented on the canvas.
+#[derive(PartialEq, Clone, Debug)]
+pub struct NodeLayout {
+    /// Unique identificator of the described node.
+    pub id: String,
+    /// Label shown on top of the node.
+    pub label: String,
+    /// Whether the label is shown on top of the node.
+    pub display_heading: bool,
+    /// Position of the top left corner of the node. It is relative to the
+    /// origin of the canvas, i.e. it does not change when the canvas is
+    /// scrolled.
+    pub position: [f32; 2],
+    /// All the pins available on the node, in the order they are listed on the
+    /// node template.
+    pub pins: Vec<config::Pin>,
+}
+
+/// A group of nodes collapsed into a single node on the canvas.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Subgraph {
+    /// Unique identificator of the subgraph.
+    pub id: String,
+    /// Label shown on top of the subgraph node.
+    pub label: String,
+    /// Id of the subgraph this subgraph is nested in, if any.
+    pub parent: Option<String>,
+    /// Ids of nodes placed directly in this subgraph.
+    pub nodes: Vec<String>,
+    /// Input pins of inner nodes which are patched from outside of the
+    /// subgraph.
+    pub inputs: Vec<PinAddress>,
+    /// Output pins of inner nodes which are patched to outside of the
+    /// subgraph.
+    pub outputs: Vec<PinAddress>,
+}
+
+/// A titled rectangle drawn behind nodes to visually group them. Frames do not
+/// affect the graph itself.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Frame {
+    /// Unique identificator of the frame.
+    pub id: String,
+    /// Title shown on top of the frame.
+    pub title: String,
+    /// Position of the top left corner of the frame, relative to the origin of
+    /// the canvas.
+    pub position: [f32; 2],
+    /// Width and height of the frame.
+    pub size: [f32; 2],
+    /// RGBA color of the frame.
+    pub color: [f32; 4],
+    /// Id of the subgraph this frame is drawn in, if any.
+    pub parent: Option<String>,
+}
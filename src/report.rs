@@ -13,6 +13,8 @@
 //!         Node {
 //!             id: "comment:0",
 //!             class: "comment",
+//!             label: "Comment",
+//!             bypassed: false,
 //!             data: {
 //!                 "comment": String(
 //!                     "Content of the comment block.",
@@ -22,6 +24,8 @@
 //!         Node {
 //!             id: "oscillator:0",
 //!             class: "oscillator",
+//!             label: "Oscillator",
+//!             bypassed: false,
 //!             data: {
 //!                 "switch": Bool(
 //!                     true,
@@ -40,6 +44,8 @@
 //!         Node {
 //!             id: "mixer:0",
 //!             class: "mixer",
+//!             label: "Mixer",
+//!             bypassed: false,
 //!             data: {},
 //!         },
 //!     ],
@@ -55,19 +61,215 @@
 //!             },
 //!         },
 //!     ],
+//!     subgraphs: [],
+//!     layout: None,
+//!     canvas_input: None,
 //! }
 //! ```
+//!
+//! # Layout
+//!
+//! When [`Config::report_layout`](../config/struct.Config.html#structfield.report_layout)
+//! is enabled, the report also carries a [`Layout`](struct.Layout.html)
+//! describing how the nodes are presented on the canvas and listing frames
+//! grouping them. Moving nodes and frames, arranging nodes or scrolling the
+//! canvas then triggers a new report too:
+//!
+//! ```ignore
+//! Report {
+//!     nodes: [ ... ],
+//!     patches: [ ... ],
+//!     subgraphs: [ ... ],
+//!     layout: Some(
+//!         Layout {
+//!             offset: [
+//!                 0.0,
+//!                 0.0,
+//!             ],
+//!             nodes: [
+//!                 NodeLayout {
+//!                     id: "mixer:0",
+//!                     label: "Mixer",
+//!                     display_heading: true,
+//!                     position: [
+//!                         120.0,
+//!                         80.0,
+//!                     ],
+//!                     pins: [
+//!                         Pin {
+//!                             label: "Input 1",
+//!                             class: "input1",
+//!                             direction: Input,
+//!                         },
+//!                         ...
+//!                     ],
+//!                 },
+//!             ],
+//!             frames: [
+//!                 Frame {
+//!                     id: "frame:0",
+//!                     title: "Output stage",
+//!                     position: [
+//!                         100.0,
+//!                         40.0,
+//!                     ],
+//!                     size: [
+//!                         300.0,
+//!                         200.0,
+//!                     ],
+//!                     color: [
+//!                         0.6,
+//!                         0.7,
+//!                         0.9,
+//!                         0.3,
+//!                     ],
+//!                     parent: None,
+//!                 },
+//!             ],
+//!         },
+//!     ),
+//! }
+//! ```
+//!
+//! # Subgraphs
+//!
+//! Users can group nodes into subgraphs, which may be nested. The report
+//! offers two views on such a hierarchy. [`Report::nodes`](struct.Report.html#structfield.nodes)
+//! and [`Report::patches`](struct.Report.html#structfield.patches) always
+//! list all the nodes and patches regardless of their nesting, while
+//! [`Report::subgraphs`](struct.Report.html#structfield.subgraphs) describes
+//! the hierarchy:
+//!
+//! ```ignore
+//! Subgraph {
+//!     id: "subgraph:0",
+//!     label: "Subgraph",
+//!     parent: None,
+//!     nodes: [
+//!         "oscillator:0",
+//!     ],
+//!     inputs: [],
+//!     outputs: [
+//!         PinAddress {
+//!             node_id: "oscillator:0",
+//!             pin_class: "output",
+//!         },
+//!     ],
+//! }
+//! ```
+//!
+//! A flattened view, where ids of nested nodes are prefixed by the path of
+//! their subgraphs, e.g. `subgraph:0/oscillator:0`, can be obtained through
+//! [`Report::flatten`](struct.Report.html#method.flatten). Note that
+//! requests always address nodes by their plain id.
+
+use std::collections::HashMap;
 
-use crate::model::{Node, Patch};
+use crate::model::{CanvasInput, Frame, Node, NodeLayout, Patch, PinAddress, Subgraph};
 
 /// Report is a structure holding information about the current "model" of the
 /// graph represented in the UI. It does not report details about the widgets
-/// that were used nor about positions of items on the canvas. It is limited to
-/// the minimal amount of information needed to convert the state into a graph.
+/// that were used. Unless requested through the config, it does not report
+/// positions of items on the canvas either. It is limited to the minimal amount
+/// of information needed to convert the state into a graph.
+///
+/// More fields may be added to the report in the future, so it cannot be
+/// constructed or exhaustively destructured outside of this crate.
 #[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
 pub struct Report {
     /// All instantiated nodes with their values set via widgets.
     pub nodes: Vec<Node>,
     /// List of all patches connecting node pins.
     pub patches: Vec<Patch>,
+    /// Subgraphs grouping nodes, describing the hierarchy of the graph.
+    pub subgraphs: Vec<Subgraph>,
+    /// Presentation of the graph on the canvas. This is available only if
+    /// [`Config::report_layout`](../config/struct.Config.html#structfield.report_layout)
+    /// is enabled.
+    pub layout: Option<Layout>,
+    /// Mouse input received by an interactive `Canvas`, if that is what
+    /// triggered this report. Each input is reported only once, the following
+    /// report will not carry it anymore.
+    pub canvas_input: Option<CanvasInput>,
+}
+
+impl Report {
+    /// Get a flattened view of the graph, where ids of nodes nested in
+    /// subgraphs are prefixed by ids of all their parent subgraphs, separated
+    /// by `/`. The returned report does not list any subgraphs.
+    pub fn flatten(&self) -> Report {
+        let parents: HashMap<&str, &str> = self
+            .subgraphs
+            .iter()
+            .flat_map(|s| {
+                s.nodes
+                    .iter()
+                    .map(move |n| (n.as_str(), s.id.as_str()))
+                    .chain(s.parent.iter().map(move |p| (s.id.as_str(), p.as_str())))
+            })
+            .collect();
+        let prefixed = |id: &str| {
+            let mut path = vec![id];
+            while let Some(parent) = parents.get(path.last().unwrap()) {
+                path.push(parent);
+            }
+            path.reverse();
+            path.join("/")
+        };
+        let prefixed_address = |address: &PinAddress| PinAddress {
+            node_id: prefixed(&address.node_id),
+            pin_class: address.pin_class.clone(),
+        };
+
+        Report {
+            nodes: self
+                .nodes
+                .iter()
+                .map(|n| Node {
+                    id: prefixed(&n.id),
+                    ..n.clone()
+                })
+                .collect(),
+            patches: self
+                .patches
+                .iter()
+                .map(|p| Patch {
+                    source: prefixed_address(&p.source),
+                    destination: prefixed_address(&p.destination),
+                })
+                .collect(),
+            subgraphs: Vec::new(),
+            layout: self.layout.as_ref().map(|l| Layout {
+                nodes: l
+                    .nodes
+                    .iter()
+                    .map(|n| NodeLayout {
+                        id: prefixed(&n.id),
+                        ..n.clone()
+                    })
+                    .collect(),
+                ..l.clone()
+            }),
+            canvas_input: self.canvas_input.as_ref().map(|i| CanvasInput {
+                node_id: prefixed(&i.node_id),
+                ..i.clone()
+            }),
+        }
+    }
+}
+
+/// Layout describes how the graph is presented on the canvas.
+///
+/// Note that moving nodes around does not trigger a new report by itself. The
+/// layout reflects the canvas at the time of the latest change of the model.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Layout {
+    /// Current scroll offset of the canvas.
+    pub offset: [f32; 2],
+    /// Presentation of all instantiated nodes, in the same order as they are
+    /// listed in [`Report::nodes`](struct.Report.html#structfield.nodes).
+    pub nodes: Vec<NodeLayout>,
+    /// Frames drawn behind nodes to group them visually.
+    pub frames: Vec<Frame>,
 }
@@ -36,6 +36,7 @@
 //! gazpatcho::run_with_mpsc("Application Name", config, report_tx, request_rx);
 //! ```
 
+use crate::config::{self, NodeTemplate};
 use crate::engine::action;
 use crate::engine::state;
 use crate::model::{Patch, PinAddress, Value};
@@ -48,23 +49,64 @@ use crate::model::{Patch, PinAddress, Value};
 pub enum Request {
     /// Remove given node.
     RemoveNode { node_id: String },
+    /// Change the label shown in the heading of the given node.
+    SetNodeLabel { node_id: String, label: String },
+    /// Arrange nodes into layers from left to right, following the direction
+    /// of patches. If a root node is given, only the nodes downstream of it
+    /// are arranged.
+    AutoLayout { root_node_id: Option<String> },
+    /// Disable or re-enable the given node. Bypassed nodes are greyed out and
+    /// reported with `bypassed` set.
+    SetBypass { node_id: String, bypassed: bool },
     /// Remove a connection between two pins.
     RemovePatch { patch: Patch },
+    /// Add a new pin to the given node. The class of the pin must be unique
+    /// within the node.
+    AddPin { node_id: String, pin: config::Pin },
+    /// Remove a pin from its node. All patches connected to the pin are
+    /// removed as well.
+    RemovePin { pin_address: PinAddress },
     /// Set value on a node's widget.
     SetValue {
         node_id: String,
         key: String,
         value: Value,
     },
+    /// Change configuration of a node's widget. If the current value of the
+    /// widget is no longer valid under the new configuration, it is adjusted.
+    /// The adjusted value is then available in the following reports. Updates
+    /// which are not applicable on the widget are ignored.
+    UpdateWidget {
+        node_id: String,
+        key: String,
+        update: WidgetUpdate,
+    },
+    /// Register a new node template, making it available in the menu. If a
+    /// template of the same class is already registered, the request is
+    /// ignored.
+    AddNodeTemplate(NodeTemplate),
+    /// Unregister the node template of the given class. All the existing
+    /// instances of the template are removed together with their patches.
+    RemoveNodeTemplate { class: String },
 }
 
 impl From<Request> for action::Action {
     fn from(request: Request) -> Self {
         match request {
             Request::RemoveNode { node_id } => Self::RemoveNode { node_id },
+            Request::SetNodeLabel { node_id, label } => Self::SetNodeLabel { node_id, label },
+            Request::AutoLayout { root_node_id } => Self::AutoLayout { root_node_id },
+            Request::SetBypass { node_id, bypassed } => Self::SetBypass { node_id, bypassed },
             Request::RemovePatch { patch } => Self::RemovePatch {
                 patch: patch.into(),
             },
+            Request::AddPin { node_id, pin } => Self::AddPin {
+                node_id,
+                pin: pin.into(),
+            },
+            Request::RemovePin { pin_address } => Self::RemovePin {
+                pin_address: pin_address.into(),
+            },
             Request::SetValue {
                 node_id,
                 key,
@@ -74,6 +116,53 @@ impl From<Request> for action::Action {
                 key,
                 value: value.into(),
             },
+            Request::UpdateWidget {
+                node_id,
+                key,
+                update,
+            } => Self::UpdateWidget {
+                node_id,
+                key,
+                update: update.into(),
+            },
+            Request::AddNodeTemplate(node_template) => Self::AddNodeTemplate {
+                node_template: node_template.into(),
+            },
+            Request::RemoveNodeTemplate { class } => Self::RemoveNodeTemplate { class },
+        }
+    }
+}
+
+/// Changes applicable on widgets of existing nodes through
+/// [`Request::UpdateWidget`](enum.Request.html#variant.UpdateWidget).
+#[derive(Debug)]
+pub enum WidgetUpdate {
+    /// Change limits of a `Slider`. The current value is clamped into the
+    /// new range. `min` must be below `max` and it must be positive on a
+    /// logarithmic scale.
+    SliderRange { min: f32, max: f32 },
+    /// Change the display format of a `Slider`, e.g. `"%.2f"`.
+    SliderFormat { format: String },
+    /// Replace items of a `DropDown`. If the currently selected value is not
+    /// among the new items, the first item gets selected. The list must not be
+    /// empty.
+    DropDownItems { items: Vec<config::DropDownItem> },
+    /// Change the label of a `Trigger` or `Switch`.
+    Label { label: String },
+    /// Make a `TextBox` read-only or editable.
+    ReadOnly { read_only: bool },
+}
+
+impl From<WidgetUpdate> for action::WidgetUpdate {
+    fn from(update: WidgetUpdate) -> Self {
+        match update {
+            WidgetUpdate::SliderRange { min, max } => Self::SliderRange { min, max },
+            WidgetUpdate::SliderFormat { format } => Self::SliderFormat { format },
+            WidgetUpdate::DropDownItems { items } => Self::DropDownItems {
+                items: items.into_iter().map(state::DropDownItem::from).collect(),
+            },
+            WidgetUpdate::Label { label } => Self::Label { label },
+            WidgetUpdate::ReadOnly { read_only } => Self::ReadOnly { read_only },
         }
     }
 }
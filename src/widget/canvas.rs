@@ -3,21 +3,81 @@ extern crate imgui;
 use crate::vec2;
 
 const HORIZONTAL_MARGIN: f32 = 10.0;
+const BUTTONS: [imgui::MouseButton; 3] = [
+    imgui::MouseButton::Left,
+    imgui::MouseButton::Right,
+    imgui::MouseButton::Middle,
+];
+
+type InputCallback = Box<dyn FnOnce([f32; 2], Input)>;
+
+/// Mouse input received by an interactive canvas.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Input {
+    Press(imgui::MouseButton),
+    Drag(imgui::MouseButton),
+    Release(imgui::MouseButton),
+    Scroll(f32),
+}
+
+/// Primitive drawn on the canvas. Coordinates are relative to the top left
+/// corner of the canvas.
+pub enum Shape<'a> {
+    Line {
+        from: [f32; 2],
+        to: [f32; 2],
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Polyline {
+        points: &'a [[f32; 2]],
+        color: [f32; 4],
+        thickness: f32,
+    },
+    Rect {
+        min: [f32; 2],
+        max: [f32; 2],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+        color: [f32; 4],
+        filled: bool,
+    },
+    Triangle {
+        points: [[f32; 2]; 3],
+        color: [f32; 4],
+        filled: bool,
+    },
+    Text {
+        position: [f32; 2],
+        color: [f32; 4],
+        text: &'a str,
+    },
+}
 
 pub struct Canvas<'a> {
+    id: imgui::ImString,
     dots: &'a [(f32, f32)],
+    shapes: Vec<Shape<'a>>,
     min_width: f32,
     height: f32,
     position: [f32; 2],
+    input_callback: Option<InputCallback>,
 }
 
 impl<'a> Canvas<'a> {
-    pub fn new(dots: &'a [(f32, f32)], min_width: f32, height: f32) -> Self {
+    pub fn new(id: imgui::ImString, dots: &'a [(f32, f32)], min_width: f32, height: f32) -> Self {
         Self {
+            id,
             dots,
+            shapes: Vec::new(),
             min_width,
             height,
             position: [0.0, 0.0],
+            input_callback: None,
         }
     }
 
@@ -26,6 +86,24 @@ impl<'a> Canvas<'a> {
         self
     }
 
+    pub fn add_shape(mut self, shape: Shape<'a>) -> Self {
+        self.shapes.push(shape);
+        self
+    }
+
+    /// Make the canvas interactive. The callback receives mouse input within
+    /// the canvas, together with the position of the mouse relative to the
+    /// top left corner of the canvas.
+    pub fn input_callback(mut self, input_callback: InputCallback) -> Self {
+        self.input_callback = Some(input_callback);
+        self
+    }
+
+    pub fn stretch(mut self, extra_height: f32) -> Self {
+        self.height += extra_height;
+        self
+    }
+
     pub fn get_min_width(&self) -> f32 {
         self.min_width + 2.0 * HORIZONTAL_MARGIN
     }
@@ -34,19 +112,121 @@ impl<'a> Canvas<'a> {
         self.height
     }
 
-    pub fn build(self, ui: &imgui::Ui, width: f32) {
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
         let margin_left = (width - self.get_min_width()) / 2.0 + HORIZONTAL_MARGIN;
-        let draw_list = ui.get_window_draw_list();
-        for (x, y) in self.dots {
-            let dot_position = vec2::sum(&[self.position, [*x, *y], [margin_left, 0.0]]);
-            draw_list
-                .add_rect(
-                    dot_position,
-                    vec2::sum(&[dot_position, [1.0, 1.0]]),
-                    ui.style_color(imgui::StyleColor::Text),
-                )
-                .filled(true)
-                .build();
+        let origin = vec2::sum(&[self.position, [margin_left, 0.0]]);
+        let at = |point: [f32; 2]| vec2::sum(&[origin, point]);
+
+        if let Some(input_callback) = self.input_callback.take() {
+            ui.set_cursor_screen_pos(origin);
+            ui.invisible_button(&self.id, [self.min_width, self.height]);
+            // Only the left button activates the item, other buttons are
+            // tracked while the mouse is above the canvas.
+            let hovered = ui.is_item_hovered();
+            let active = ui.is_item_active();
+            let moved = ui.io().mouse_delta != [0.0, 0.0];
+            let input = BUTTONS
+                .iter()
+                .copied()
+                .find_map(|button| {
+                    if hovered && ui.is_mouse_clicked(button) {
+                        Some(Input::Press(button))
+                    } else if (hovered || active) && ui.is_mouse_dragging(button) && moved {
+                        Some(Input::Drag(button))
+                    } else if (hovered || active) && ui.is_mouse_released(button) {
+                        Some(Input::Release(button))
+                    } else {
+                        None
+                    }
+                })
+                .or_else(|| {
+                    if hovered && ui.io().mouse_wheel != 0.0 {
+                        Some(Input::Scroll(ui.io().mouse_wheel))
+                    } else {
+                        None
+                    }
+                });
+            if let Some(input) = input {
+                let mouse_pos = ui.io().mouse_pos;
+                input_callback([mouse_pos[0] - origin[0], mouse_pos[1] - origin[1]], input);
+            }
         }
+
+        let draw_list = ui.get_window_draw_list();
+
+        draw_list.with_clip_rect_intersect(
+            origin,
+            vec2::sum(&[origin, [self.min_width, self.height]]),
+            || {
+                for (x, y) in self.dots {
+                    let dot_position = at([*x, *y]);
+                    draw_list
+                        .add_rect(
+                            dot_position,
+                            vec2::sum(&[dot_position, [1.0, 1.0]]),
+                            ui.style_color(imgui::StyleColor::Text),
+                        )
+                        .filled(true)
+                        .build();
+                }
+
+                for shape in self.shapes.iter() {
+                    match shape {
+                        Shape::Line {
+                            from,
+                            to,
+                            color,
+                            thickness,
+                        } => draw_list
+                            .add_line(at(*from), at(*to), *color)
+                            .thickness(*thickness)
+                            .build(),
+                        Shape::Polyline {
+                            points,
+                            color,
+                            thickness,
+                        } => {
+                            for segment in points.windows(2) {
+                                draw_list
+                                    .add_line(at(segment[0]), at(segment[1]), *color)
+                                    .thickness(*thickness)
+                                    .build();
+                            }
+                        }
+                        Shape::Rect {
+                            min,
+                            max,
+                            color,
+                            filled,
+                        } => draw_list
+                            .add_rect(at(*min), at(*max), *color)
+                            .filled(*filled)
+                            .build(),
+                        Shape::Circle {
+                            center,
+                            radius,
+                            color,
+                            filled,
+                        } => draw_list
+                            .add_circle(at(*center), *radius, *color)
+                            .filled(*filled)
+                            .build(),
+                        Shape::Triangle {
+                            points,
+                            color,
+                            filled,
+                        } => draw_list
+                            .add_triangle(at(points[0]), at(points[1]), at(points[2]), *color)
+                            .filled(*filled)
+                            .build(),
+                        Shape::Text {
+                            position,
+                            color,
+                            text,
+                        } => draw_list.add_text(at(*position), *color, text),
+                    }
+                }
+            },
+        );
     }
 }
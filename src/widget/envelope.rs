@@ -0,0 +1,288 @@
+extern crate imgui;
+
+use crate::vec2;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const POINT_RADIUS: f32 = 5.0;
+const CURVE_HANDLE_RADIUS: f32 = 3.0;
+const SEGMENT_STEPS: usize = 16;
+// Vertical distance in pixels the mouse has to travel to bend a segment from
+// its minimum to its maximum curvature.
+const DRAG_DISTANCE: f32 = 200.0;
+
+type ValueCallback = Box<dyn FnOnce(Vec<(f32, f32)>, Vec<f32>)>;
+
+/// Relative height of a segment at the relative position `t`, given its
+/// curvature between -1.0 and 1.0.
+fn shape(t: f32, curvature: f32) -> f32 {
+    t.powf(4.0_f32.powf(curvature))
+}
+
+/// Area with a curve given by draggable breakpoints. Double-clicking the area
+/// adds a breakpoint, double-clicking a breakpoint removes it. The `x` value
+/// grows from left to right, the `y` value from bottom to top.
+pub struct Envelope {
+    id: imgui::ImString,
+    position: [f32; 2],
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    points: Vec<(f32, f32)>,
+    curvatures: Vec<f32>,
+    curved: bool,
+    size: [f32; 2],
+    value_callback: Option<ValueCallback>,
+}
+
+impl Envelope {
+    pub fn new(
+        id: imgui::ImString,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+        points: Vec<(f32, f32)>,
+        curvatures: Vec<f32>,
+    ) -> Self {
+        assert_eq!(
+            curvatures.len(),
+            points.len().saturating_sub(1),
+            "There must be a curvature for each segment"
+        );
+        let id = imgui::ImString::from(format!("##{}", id));
+        Self {
+            id,
+            position: [0.0, 0.0],
+            x_range,
+            y_range,
+            points,
+            curvatures,
+            curved: false,
+            size: [200.0, 100.0],
+            value_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Show handles in the middle of segments allowing users to bend them.
+    pub fn curved(mut self, curved: bool) -> Self {
+        self.curved = curved;
+        self
+    }
+
+    pub fn size(mut self, size: [f32; 2]) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// The callback receives the breakpoints and curvatures of segments
+    /// between them.
+    pub fn value_callback(mut self, value_callback: ValueCallback) -> Self {
+        self.value_callback = Some(value_callback);
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.size[0] + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.size[1]
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
+        let area_a = vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]);
+        let area_b = vec2::sum(&[
+            self.position,
+            [width - HORIZONTAL_MARGIN, self.get_height()],
+        ]);
+        let area_size = [area_b[0] - area_a[0], area_b[1] - area_a[1]];
+        let (x_range, y_range) = (self.x_range, self.y_range);
+        let to_screen = |(x, y): (f32, f32)| {
+            [
+                area_a[0] + (x - x_range.0) / (x_range.1 - x_range.0) * area_size[0],
+                area_b[1] - (y - y_range.0) / (y_range.1 - y_range.0) * area_size[1],
+            ]
+        };
+        let to_value = |[x, y]: [f32; 2]| {
+            let relative_x = ((x - area_a[0]) / area_size[0]).clamp(0.0, 1.0);
+            let relative_y = ((area_b[1] - y) / area_size[1]).clamp(0.0, 1.0);
+            (
+                x_range.0 + relative_x * (x_range.1 - x_range.0),
+                y_range.0 + relative_y * (y_range.1 - y_range.0),
+            )
+        };
+        let mouse_pos = ui.io().mouse_pos;
+        let double_clicked = ui.is_mouse_double_clicked(imgui::MouseButton::Left);
+
+        // Breakpoints and curve handles are submitted before the area, so they
+        // take the mouse when they overlap it.
+        let mut active_point = None;
+        let mut removed_point = None;
+        let mut hovered = false;
+        for i in 0..self.points.len() {
+            let point = to_screen(self.points[i]);
+            ui.set_cursor_screen_pos(vec2::sum(&[point, [-POINT_RADIUS, -POINT_RADIUS]]));
+            let id = imgui::ImString::from(format!("{}:point:{}", self.id, i));
+            ui.invisible_button(&id, [2.0 * POINT_RADIUS, 2.0 * POINT_RADIUS]);
+            hovered |= ui.is_item_hovered();
+            if ui.is_item_hovered() && double_clicked {
+                removed_point = Some(i);
+            } else if ui.is_item_active() {
+                active_point = Some(i);
+                if ui.is_mouse_dragging(imgui::MouseButton::Left) {
+                    let (x, y) = to_value(mouse_pos);
+                    let left = if i > 0 {
+                        self.points[i - 1].0
+                    } else {
+                        x_range.0
+                    };
+                    let right = self.points.get(i + 1).map_or(x_range.1, |p| p.0);
+                    self.points[i] = (x.clamp(left, right), y);
+                }
+            }
+        }
+
+        let mut active_curve = None;
+        if self.curved {
+            for i in 0..self.curvatures.len() {
+                let (a, b) = (self.points[i], self.points[i + 1]);
+                let handle = to_screen((
+                    (a.0 + b.0) / 2.0,
+                    a.1 + (b.1 - a.1) * shape(0.5, self.curvatures[i]),
+                ));
+                ui.set_cursor_screen_pos(vec2::sum(&[
+                    handle,
+                    [-CURVE_HANDLE_RADIUS, -CURVE_HANDLE_RADIUS],
+                ]));
+                let id = imgui::ImString::from(format!("{}:curve:{}", self.id, i));
+                ui.invisible_button(&id, [2.0 * CURVE_HANDLE_RADIUS, 2.0 * CURVE_HANDLE_RADIUS]);
+                hovered |= ui.is_item_hovered();
+                if ui.is_item_active() {
+                    active_curve = Some(i);
+                    // Dragging up always raises the middle of the segment.
+                    let direction = if b.1 < a.1 { -1.0 } else { 1.0 };
+                    let delta = ui.io().mouse_delta[1] * direction * 2.0 / DRAG_DISTANCE;
+                    self.curvatures[i] = (self.curvatures[i] + delta).clamp(-1.0, 1.0);
+                }
+            }
+        }
+        if hovered || active_point.is_some() || active_curve.is_some() {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+        }
+
+        ui.set_cursor_screen_pos(area_a);
+        ui.invisible_button(&self.id, area_size);
+        if ui.is_item_hovered() && double_clicked {
+            let point = to_value(mouse_pos);
+            let index = self.points.iter().take_while(|p| p.0 <= point.0).count();
+            // Both halves of the split segment keep its curvature.
+            let curvature = if index > 0 {
+                self.curvatures.get(index - 1).copied().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            if !self.points.is_empty() {
+                self.curvatures
+                    .insert(usize::min(index, self.curvatures.len()), curvature);
+            }
+            self.points.insert(index, point);
+        }
+
+        // Envelope must always keep at least its start and end.
+        if let Some(i) = removed_point {
+            if self.points.len() > 2 {
+                self.points.remove(i);
+                self.curvatures
+                    .remove(usize::min(i, self.curvatures.len() - 1));
+            }
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(area_a, area_b, ui.style_color(imgui::StyleColor::FrameBg))
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(area_a, area_b, ui.style_color(imgui::StyleColor::Border))
+            .build();
+
+        for (i, curvature) in self.curvatures.iter().enumerate() {
+            let (a, b) = (self.points[i], self.points[i + 1]);
+            let point_at = |t: f32| {
+                to_screen((
+                    a.0 + (b.0 - a.0) * t,
+                    a.1 + (b.1 - a.1) * shape(t, *curvature),
+                ))
+            };
+            for step in 0..SEGMENT_STEPS {
+                draw_list
+                    .add_line(
+                        point_at(step as f32 / SEGMENT_STEPS as f32),
+                        point_at((step + 1) as f32 / SEGMENT_STEPS as f32),
+                        ui.style_color(imgui::StyleColor::PlotLines),
+                    )
+                    .thickness(2.0)
+                    .build();
+            }
+            if self.curved {
+                draw_list
+                    .add_circle(
+                        point_at(0.5),
+                        CURVE_HANDLE_RADIUS,
+                        ui.style_color(if active_curve == Some(i) {
+                            imgui::StyleColor::SliderGrabActive
+                        } else {
+                            imgui::StyleColor::Border
+                        }),
+                    )
+                    .filled(true)
+                    .build();
+            }
+        }
+
+        for (i, point) in self.points.iter().enumerate() {
+            draw_list
+                .add_circle(
+                    to_screen(*point),
+                    POINT_RADIUS,
+                    ui.style_color(if active_point == Some(i) {
+                        imgui::StyleColor::SliderGrabActive
+                    } else {
+                        imgui::StyleColor::SliderGrab
+                    }),
+                )
+                .filled(true)
+                .build();
+        }
+
+        if let Some(value_callback) = self.value_callback {
+            value_callback(self.points, self.curvatures);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_segment() {
+        assert_eq!(shape(0.5, 0.0), 0.5);
+    }
+
+    #[test]
+    fn curved_segment_keeps_its_ends() {
+        for curvature in [-1.0, -0.3, 0.7, 1.0].iter() {
+            assert_eq!(shape(0.0, *curvature), 0.0);
+            assert_eq!(shape(1.0, *curvature), 1.0);
+        }
+    }
+
+    #[test]
+    fn positive_curvature_bends_segment_down() {
+        assert!(shape(0.5, 1.0) < 0.5);
+        assert!(shape(0.5, -1.0) > 0.5);
+    }
+}
@@ -0,0 +1,142 @@
+extern crate imgui;
+
+use std::boxed::Box;
+
+use crate::vec2;
+
+const TITLE_HEIGHT: f32 = 20.0;
+const TITLE_PADDING: [f32; 2] = [8.0, 3.0];
+const RESIZE_HANDLE_SIZE: f32 = 12.0;
+
+type UiCallback = Box<dyn FnOnce(&imgui::Ui)>;
+
+pub struct Frame<'a> {
+    id: imgui::ImString,
+    title: &'a imgui::ImStr,
+    position: [f32; 2],
+    size: [f32; 2],
+    color: [f32; 4],
+    thick: bool,
+    title_callback: Option<UiCallback>,
+    resize_callback: Option<UiCallback>,
+}
+
+impl<'a> Frame<'a> {
+    pub fn new(id: imgui::ImString, title: &'a imgui::ImStr) -> Self {
+        Self {
+            id,
+            title,
+            position: [0.0, 0.0],
+            size: [0.0, 0.0],
+            color: [0.0, 0.0, 0.0, 0.0],
+            thick: false,
+            title_callback: None,
+            resize_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn size(mut self, size: [f32; 2]) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn thick(mut self, thick: bool) -> Self {
+        self.thick = thick;
+        self
+    }
+
+    pub fn title_callback(mut self, title_callback: UiCallback) -> Self {
+        self.title_callback = Some(title_callback);
+        self
+    }
+
+    pub fn resize_callback(mut self, resize_callback: UiCallback) -> Self {
+        self.resize_callback = Some(resize_callback);
+        self
+    }
+
+    pub fn build(self, ui: &imgui::Ui) {
+        let draw_list = ui.get_window_draw_list();
+        let bottom_right = vec2::sum(&[self.position, self.size]);
+        let title_bottom_right = vec2::sum(&[self.position, [self.size[0], TITLE_HEIGHT]]);
+
+        draw_list
+            .add_rect(self.position, bottom_right, self.color)
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(
+                self.position,
+                title_bottom_right,
+                [
+                    self.color[0],
+                    self.color[1],
+                    self.color[2],
+                    f32::min(self.color[3] * 2.0, 1.0),
+                ],
+            )
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(
+                self.position,
+                bottom_right,
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .thickness(if self.thick { 2.0 } else { 1.0 })
+            .build();
+        draw_list.with_clip_rect_intersect(self.position, title_bottom_right, || {
+            draw_list.add_text(
+                vec2::sum(&[self.position, TITLE_PADDING]),
+                ui.style_color(imgui::StyleColor::Text),
+                self.title,
+            );
+        });
+
+        let resize_handle_position =
+            vec2::sum(&[bottom_right, [-RESIZE_HANDLE_SIZE, -RESIZE_HANDLE_SIZE]]);
+        draw_list
+            .add_triangle(
+                [bottom_right[0], resize_handle_position[1]],
+                bottom_right,
+                [resize_handle_position[0], bottom_right[1]],
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .filled(true)
+            .build();
+
+        ui.set_cursor_screen_pos(self.position);
+        ui.invisible_button(
+            &imgui::ImString::from(format!("{}:title", self.id)),
+            [self.size[0], TITLE_HEIGHT],
+        );
+        if let Some(title_callback) = self.title_callback {
+            title_callback(ui);
+        }
+        unsafe {
+            imgui::sys::igSetItemAllowOverlap();
+        }
+
+        ui.set_cursor_screen_pos(resize_handle_position);
+        ui.invisible_button(
+            &imgui::ImString::from(format!("{}:resize", self.id)),
+            [RESIZE_HANDLE_SIZE, RESIZE_HANDLE_SIZE],
+        );
+        if let Some(resize_callback) = self.resize_callback {
+            resize_callback(ui);
+        }
+        unsafe {
+            imgui::sys::igSetItemAllowOverlap();
+        }
+    }
+}
@@ -0,0 +1,66 @@
+extern crate imgui;
+
+use crate::vec2;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+
+/// Uploaded texture shown scaled to fit the widget, keeping its aspect ratio.
+pub struct Image {
+    texture: Option<(imgui::TextureId, [f32; 2])>,
+    min_width: f32,
+    height: f32,
+    position: [f32; 2],
+}
+
+impl Image {
+    pub fn new(min_width: f32, height: f32) -> Self {
+        Self {
+            texture: None,
+            min_width,
+            height,
+            position: [0.0, 0.0],
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Texture to draw and its original width and height in pixels. If none
+    /// is given, only an empty frame is shown.
+    pub fn texture(mut self, texture_id: imgui::TextureId, size: [f32; 2]) -> Self {
+        self.texture = Some((texture_id, size));
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.min_width + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn build(self, ui: &imgui::Ui, width: f32) {
+        let area_a = vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]);
+        let area_b = vec2::sum(&[self.position, [width - HORIZONTAL_MARGIN, self.height]]);
+        let area_size = [area_b[0] - area_a[0], area_b[1] - area_a[1]];
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(area_a, area_b, ui.style_color(imgui::StyleColor::FrameBg))
+            .filled(true)
+            .build();
+
+        if let Some((texture_id, size)) = self.texture {
+            let scale = f32::min(area_size[0] / size[0], area_size[1] / size[1]);
+            let image_size = [size[0] * scale, size[1] * scale];
+            ui.set_cursor_screen_pos([
+                area_a[0] + (area_size[0] - image_size[0]) / 2.0,
+                area_a[1] + (area_size[1] - image_size[1]) / 2.0,
+            ]);
+            imgui::Image::new(texture_id, image_size).build(ui);
+        }
+    }
+}
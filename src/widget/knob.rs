@@ -0,0 +1,210 @@
+extern crate imgui;
+
+use std::f32::consts::PI;
+
+use crate::vec2;
+use crate::widget::slider::format_value;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const SPACING: f32 = 10.0;
+const LABEL_MARGIN: f32 = 3.0;
+const MIN_ANGLE: f32 = 0.75 * PI;
+const MAX_ANGLE: f32 = 2.25 * PI;
+const ARC_SEGMENTS: usize = 24;
+const ARC_THICKNESS: f32 = 3.0;
+// Vertical distance in pixels the mouse has to travel to turn the knob from
+// its minimum to its maximum.
+const DRAG_DISTANCE: f32 = 200.0;
+
+/// Rotary dial changing its value as the mouse is dragged up and down over it.
+pub struct Knob {
+    id: imgui::ImString,
+    min: f32,
+    max: f32,
+    value: f32,
+    size: f32,
+    display_format: imgui::ImString,
+    value_callback: Option<Box<dyn FnOnce(f32)>>,
+}
+
+impl Knob {
+    pub fn new(id: imgui::ImString, min: f32, max: f32, value: f32) -> Self {
+        let id = imgui::ImString::from(format!("##{}", id));
+        Self {
+            id,
+            min,
+            max,
+            value,
+            size: 40.0,
+            display_format: imgui::ImString::new("%.3f"),
+            value_callback: None,
+        }
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn display_format(mut self, display_format: imgui::ImString) -> Self {
+        self.display_format = display_format;
+        self
+    }
+
+    pub fn value_callback(mut self, value_callback: Box<dyn FnOnce(f32)>) -> Self {
+        self.value_callback = Some(value_callback);
+        self
+    }
+
+    fn get_label(&self, value: f32) -> imgui::ImString {
+        imgui::ImString::from(format_value(&self.display_format, value))
+    }
+
+    /// The width fits labels of both limits, so it does not change while the
+    /// knob is turned.
+    pub fn get_width(&self, ui: &imgui::Ui) -> f32 {
+        [self.min, self.max]
+            .iter()
+            .map(|v| ui.calc_text_size(&self.get_label(*v), false, 0.0)[0])
+            .fold(self.size, f32::max)
+    }
+
+    pub fn get_height(&self, ui: &imgui::Ui) -> f32 {
+        self.size + LABEL_MARGIN + ui.calc_text_size(&self.get_label(self.value), false, 0.0)[1]
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, position: [f32; 2]) {
+        let width = self.get_width(ui);
+        let knob_position = vec2::sum(&[position, [(width - self.size) / 2.0, 0.0]]);
+
+        ui.set_cursor_screen_pos(knob_position);
+        ui.invisible_button(&self.id, [self.size, self.size]);
+        if ui.is_item_active() {
+            let delta = ui.io().mouse_delta[1];
+            self.value = f32::min(
+                f32::max(
+                    self.value - delta * (self.max - self.min) / DRAG_DISTANCE,
+                    self.min,
+                ),
+                self.max,
+            );
+        }
+        if ui.is_item_hovered() || ui.is_item_active() {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::ResizeNS));
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        let radius = self.size / 2.0;
+        let center = vec2::sum(&[knob_position, [radius, radius]]);
+        let point_at = |angle: f32, distance: f32| {
+            vec2::sum(&[center, [angle.cos() * distance, angle.sin() * distance]])
+        };
+
+        draw_list
+            .add_circle(
+                center,
+                radius - ARC_THICKNESS,
+                ui.style_color(if ui.is_item_active() {
+                    imgui::StyleColor::FrameBgActive
+                } else if ui.is_item_hovered() {
+                    imgui::StyleColor::FrameBgHovered
+                } else {
+                    imgui::StyleColor::FrameBg
+                }),
+            )
+            .filled(true)
+            .build();
+
+        let value_angle =
+            MIN_ANGLE + (MAX_ANGLE - MIN_ANGLE) * (self.value - self.min) / (self.max - self.min);
+        let arc_radius = radius - ARC_THICKNESS / 2.0;
+        for i in 0..ARC_SEGMENTS {
+            let a = MIN_ANGLE + (MAX_ANGLE - MIN_ANGLE) * i as f32 / ARC_SEGMENTS as f32;
+            let b = MIN_ANGLE + (MAX_ANGLE - MIN_ANGLE) * (i + 1) as f32 / ARC_SEGMENTS as f32;
+            let color = if b <= value_angle {
+                ui.style_color(imgui::StyleColor::SliderGrabActive)
+            } else {
+                ui.style_color(imgui::StyleColor::Border)
+            };
+            draw_list
+                .add_line(point_at(a, arc_radius), point_at(b, arc_radius), color)
+                .thickness(ARC_THICKNESS)
+                .build();
+        }
+
+        draw_list
+            .add_line(
+                point_at(value_angle, radius * 0.2),
+                point_at(value_angle, radius - ARC_THICKNESS),
+                ui.style_color(imgui::StyleColor::Text),
+            )
+            .thickness(2.0)
+            .build();
+
+        let label = self.get_label(self.value);
+        let label_width = ui.calc_text_size(&label, false, 0.0)[0];
+        draw_list.add_text(
+            vec2::sum(&[
+                position,
+                [(width - label_width) / 2.0, self.size + LABEL_MARGIN],
+            ]),
+            ui.style_color(imgui::StyleColor::Text),
+            label.to_str(),
+        );
+
+        if let Some(value_callback) = self.value_callback {
+            value_callback(self.value);
+        }
+    }
+}
+
+/// Knobs laid out next to each other, centered within the node.
+#[derive(Default)]
+pub struct KnobRow {
+    position: [f32; 2],
+    knobs: Vec<Knob>,
+}
+
+impl KnobRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn add_knob(mut self, knob: Knob) -> Self {
+        self.knobs.push(knob);
+        self
+    }
+
+    fn get_knobs_width(&self, ui: &imgui::Ui) -> f32 {
+        let widths: f32 = self.knobs.iter().map(|k| k.get_width(ui)).sum();
+        widths + SPACING * self.knobs.len().saturating_sub(1) as f32
+    }
+
+    pub fn get_min_width(&self, ui: &imgui::Ui) -> f32 {
+        self.get_knobs_width(ui) + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self, ui: &imgui::Ui) -> f32 {
+        self.knobs
+            .iter()
+            .map(|k| k.get_height(ui))
+            .fold(0.0, f32::max)
+    }
+
+    pub fn build(self, ui: &imgui::Ui, width: f32) {
+        let mut cursor = vec2::sum(&[
+            self.position,
+            [(width - self.get_knobs_width(ui)) / 2.0, 0.0],
+        ]);
+        for knob in self.knobs.into_iter() {
+            let knob_width = knob.get_width(ui);
+            knob.build(ui, cursor);
+            cursor[0] += knob_width + SPACING;
+        }
+    }
+}
@@ -7,6 +7,7 @@ const HORIZONTAL_PADDING: f32 = 10.0;
 pub struct Label<'a> {
     text: &'a imgui::ImStr,
     position: [f32; 2],
+    double_click_callback: Option<Box<dyn FnOnce()>>,
 }
 
 impl<'a> Label<'a> {
@@ -14,9 +15,15 @@ impl<'a> Label<'a> {
         Self {
             text,
             position: [0.0, 0.0],
+            double_click_callback: None,
         }
     }
 
+    pub fn double_click_callback(mut self, double_click_callback: Box<dyn FnOnce()>) -> Self {
+        self.double_click_callback = Some(double_click_callback);
+        self
+    }
+
     pub fn position(mut self, position: [f32; 2]) -> Self {
         self.position = position;
         self
@@ -37,5 +44,14 @@ impl<'a> Label<'a> {
             ui.style_color(imgui::StyleColor::Text),
             self.text,
         );
+
+        let size = [self.get_width(ui), self.get_height(ui)];
+        if let Some(double_click_callback) = self.double_click_callback {
+            if ui.is_mouse_double_clicked(imgui::MouseButton::Left)
+                && ui.is_mouse_hovering_rect(self.position, vec2::sum(&[self.position, size]))
+            {
+                double_click_callback();
+            }
+        }
     }
 }
@@ -0,0 +1,64 @@
+extern crate imgui;
+
+use crate::vec2;
+
+const HORIZONTAL_PADDING: f32 = 10.0;
+const VERTICAL_PADDING: f32 = 3.0;
+
+type ContentCallback = Box<dyn FnOnce(&imgui::ImString, bool)>;
+
+pub struct LabelInput {
+    id: imgui::ImString,
+    content: imgui::ImString,
+    position: [f32; 2],
+    content_callback: Option<ContentCallback>,
+}
+
+impl LabelInput {
+    pub fn new(id: imgui::ImString, content: imgui::ImString) -> Self {
+        Self {
+            id,
+            content,
+            position: [0.0, 0.0],
+            content_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// The callback receives the current content of the input and a flag
+    /// signalizing whether the editing was finished.
+    pub fn content_callback(mut self, content_callback: ContentCallback) -> Self {
+        self.content_callback = Some(content_callback);
+        self
+    }
+
+    pub fn get_min_width(&self, ui: &imgui::Ui) -> f32 {
+        ui.calc_text_size(&self.content, false, 0.0)[0] + HORIZONTAL_PADDING * 4.0
+    }
+
+    pub fn get_height(&self, ui: &imgui::Ui) -> f32 {
+        ui.calc_text_size(&self.content, false, 0.0)[1] + VERTICAL_PADDING * 2.0
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
+        ui.set_cursor_screen_pos(vec2::sum(&[self.position, [HORIZONTAL_PADDING, 0.0]]));
+        if !ui.is_any_item_active() {
+            ui.set_keyboard_focus_here(imgui::FocusedWidget::Next);
+        }
+        ui.set_next_item_width(width - HORIZONTAL_PADDING * 2.0);
+        let confirmed = ui
+            .input_text(&self.id, &mut self.content)
+            .enter_returns_true(true)
+            .auto_select_all(true)
+            .build();
+        let finished = confirmed || ui.is_item_deactivated();
+
+        if let Some(content_callback) = self.content_callback {
+            content_callback(&self.content, finished);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+extern crate imgui;
+
+use std::boxed::Box;
+
+use crate::vec2;
+
+const PADDING: f32 = 5.0;
+
+type JumpCallback = Box<dyn FnOnce([f32; 2])>;
+
+/// Scaled down overview of the canvas. Rectangles, lines and the viewport are
+/// given in canvas coordinates and fitted into the widget.
+pub struct Minimap<'a> {
+    id: &'a imgui::ImStr,
+    position: [f32; 2],
+    size: [f32; 2],
+    rects: Vec<[[f32; 2]; 2]>,
+    lines: Vec<[[f32; 2]; 2]>,
+    viewport: [[f32; 2]; 2],
+    jump_callback: Option<JumpCallback>,
+}
+
+impl<'a> Minimap<'a> {
+    pub fn new(id: &'a imgui::ImStr) -> Self {
+        Self {
+            id,
+            position: [0.0, 0.0],
+            size: [0.0, 0.0],
+            rects: Vec::new(),
+            lines: Vec::new(),
+            viewport: [[0.0, 0.0], [0.0, 0.0]],
+            jump_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn size(mut self, size: [f32; 2]) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn add_rect(mut self, a: [f32; 2], b: [f32; 2]) -> Self {
+        self.rects.push([a, b]);
+        self
+    }
+
+    pub fn add_line(mut self, a: [f32; 2], b: [f32; 2]) -> Self {
+        self.lines.push([a, b]);
+        self
+    }
+
+    pub fn viewport(mut self, a: [f32; 2], b: [f32; 2]) -> Self {
+        self.viewport = [a, b];
+        self
+    }
+
+    /// The callback is called with the canvas coordinates of the point under
+    /// the mouse when the minimap is clicked or dragged over.
+    pub fn jump_callback(mut self, jump_callback: JumpCallback) -> Self {
+        self.jump_callback = Some(jump_callback);
+        self
+    }
+
+    pub fn build(self, ui: &imgui::Ui) {
+        let (origin, scale) = self.get_transformation();
+        let to_minimap = |point: [f32; 2]| {
+            [
+                self.position[0] + PADDING + (point[0] - origin[0]) * scale,
+                self.position[1] + PADDING + (point[1] - origin[1]) * scale,
+            ]
+        };
+
+        let draw_list = ui.get_window_draw_list();
+        let bottom_right = vec2::sum(&[self.position, self.size]);
+        draw_list
+            .add_rect(
+                self.position,
+                bottom_right,
+                ui.style_color(imgui::StyleColor::PopupBg),
+            )
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(
+                self.position,
+                bottom_right,
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .build();
+
+        draw_list.with_clip_rect_intersect(self.position, bottom_right, || {
+            for [a, b] in self.lines.iter() {
+                draw_list
+                    .add_line(to_minimap(*a), to_minimap(*b), [0.0, 0.0, 0.0])
+                    .build();
+            }
+            for [a, b] in self.rects.iter() {
+                draw_list
+                    .add_rect(
+                        to_minimap(*a),
+                        to_minimap(*b),
+                        ui.style_color(imgui::StyleColor::Border),
+                    )
+                    .filled(true)
+                    .build();
+            }
+            draw_list
+                .add_rect(
+                    to_minimap(self.viewport[0]),
+                    to_minimap(self.viewport[1]),
+                    ui.style_color(imgui::StyleColor::Text),
+                )
+                .thickness(2.0)
+                .build();
+        });
+
+        ui.set_cursor_screen_pos(self.position);
+        ui.invisible_button(self.id, self.size);
+        if ui.is_item_active() && ui.is_mouse_down(imgui::MouseButton::Left) {
+            if let Some(jump_callback) = self.jump_callback {
+                let mouse_pos = ui.io().mouse_pos;
+                jump_callback([
+                    origin[0] + (mouse_pos[0] - self.position[0] - PADDING) / scale,
+                    origin[1] + (mouse_pos[1] - self.position[1] - PADDING) / scale,
+                ]);
+            }
+        }
+    }
+
+    /// Find the top left corner of the displayed area and the scale needed to
+    /// fit all the content, including the viewport, into the minimap.
+    fn get_transformation(&self) -> ([f32; 2], f32) {
+        let points = self
+            .rects
+            .iter()
+            .chain(self.lines.iter())
+            .chain(std::iter::once(&self.viewport))
+            .flat_map(|r| r.iter());
+        let (min, max) = points.fold(
+            ([f32::MAX, f32::MAX], [f32::MIN, f32::MIN]),
+            |(min, max), p| {
+                (
+                    [f32::min(min[0], p[0]), f32::min(min[1], p[1])],
+                    [f32::max(max[0], p[0]), f32::max(max[1], p[1])],
+                )
+            },
+        );
+
+        let available = [self.size[0] - PADDING * 2.0, self.size[1] - PADDING * 2.0];
+        let scale = f32::min(
+            available[0] / f32::max(max[0] - min[0], 1.0),
+            available[1] / f32::max(max[1] - min[1], 1.0),
+        );
+
+        (min, scale)
+    }
+}
@@ -5,9 +5,19 @@
 pub mod button;
 pub mod canvas;
 pub mod dropdown;
+pub mod envelope;
+pub mod frame;
+pub mod image;
+pub mod knob;
 pub mod label;
+pub mod label_input;
+pub mod minimap;
 pub mod node;
+pub mod number_input;
 pub mod pin;
 pub mod pin_group;
+pub mod plot;
+pub mod range_slider;
 pub mod slider;
 pub mod text_box;
+pub mod xy_pad;
@@ -1,29 +1,60 @@
 extern crate imgui;
 
+use std::boxed::Box;
+
 use crate::vec2;
 use crate::widget::button::Button;
 use crate::widget::canvas::Canvas;
 use crate::widget::dropdown::DropDown;
+use crate::widget::envelope::Envelope;
+use crate::widget::image::Image;
+use crate::widget::knob::KnobRow;
 use crate::widget::label::Label;
+use crate::widget::label_input::LabelInput;
+use crate::widget::number_input::NumberInput;
 use crate::widget::pin_group::PinGroup;
+use crate::widget::plot::Plot;
+use crate::widget::range_slider::RangeSlider;
 use crate::widget::slider::Slider;
 use crate::widget::text_box::TextBox;
+use crate::widget::xy_pad::XYPad;
 
 pub enum Component<'a> {
     Label(Label<'a>),
+    LabelInput(LabelInput),
     PinGroup(PinGroup<'a>),
     Space(f32),
     TextBox(TextBox),
     Button(Button),
     Slider(Slider),
+    RangeSlider(RangeSlider),
+    XYPad(XYPad),
+    Envelope(Envelope),
+    KnobRow(KnobRow),
+    NumberInputF32(NumberInput<f32>),
+    NumberInputI32(NumberInput<i32>),
     DropDown(DropDown),
+    Plot(Plot<'a>),
+    Image(Image),
     Canvas(Canvas<'a>),
 }
 
+const COLLAPSE_TOGGLE_WIDTH: f32 = 20.0;
+const COLLAPSE_TOGGLE_SIZE: f32 = 8.0;
+const RESIZE_HANDLE_SIZE: f32 = 12.0;
+const BYPASSED_OVERLAY_ALPHA: f32 = 0.6;
+
+type UiCallback = Box<dyn FnOnce(&imgui::Ui)>;
+
 pub struct Node<'a> {
     id: &'a imgui::ImStr,
     position: [f32; 2],
     thick: bool,
+    collapsed: bool,
+    bypassed: bool,
+    collapse_callback: Option<Box<dyn FnOnce()>>,
+    min_size: [f32; 2],
+    resize_callback: Option<UiCallback>,
     components: Vec<Component<'a>>,
 }
 
@@ -33,6 +64,11 @@ impl<'a> Node<'a> {
             id,
             position: [0.0, 0.0],
             thick: false,
+            collapsed: false,
+            bypassed: false,
+            collapse_callback: None,
+            min_size: [0.0, 0.0],
+            resize_callback: None,
             components: Vec::new(),
         }
     }
@@ -47,16 +83,55 @@ impl<'a> Node<'a> {
         self
     }
 
+    /// Collapsed node hides all its widgets, keeping only the heading and pins.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Bypassed node is drawn greyed out.
+    pub fn bypassed(mut self, bypassed: bool) -> Self {
+        self.bypassed = bypassed;
+        self
+    }
+
+    /// The callback is called when the collapse toggle shown in the heading is
+    /// clicked. The toggle is not shown unless the callback is set and the node
+    /// has a heading.
+    pub fn collapse_callback(mut self, collapse_callback: Box<dyn FnOnce()>) -> Self {
+        self.collapse_callback = Some(collapse_callback);
+        self
+    }
+
+    /// Make the node at least this big. Additional height is split between
+    /// stretchable widgets, i.e. `TextBox`, `Plot` and `Canvas`.
+    pub fn min_size(mut self, min_size: [f32; 2]) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// The callback is called after a resize handle in the bottom right corner
+    /// of the node is drawn. The handle is not shown unless the callback is set.
+    pub fn resize_callback(mut self, resize_callback: UiCallback) -> Self {
+        self.resize_callback = Some(resize_callback);
+        self
+    }
+
     pub fn add_component(mut self, component: Component<'a>) -> Self {
         self.components.push(component);
         self
     }
 
-    pub fn build(self, ui: &imgui::Ui<'_>) {
+    pub fn build(mut self, ui: &imgui::Ui<'_>) {
+        if self.collapsed {
+            self.components = hide_widgets(self.components);
+        }
+        self.stretch_to_min_height(ui);
+
         let position = self.position;
 
-        let width = self.get_width(ui);
-        let height = self.get_height(ui);
+        let width = f32::max(self.get_width(ui), self.min_size[0]);
+        let height = f32::max(self.get_height(ui), self.min_size[1]);
 
         {
             let draw_list = ui.get_window_draw_list();
@@ -95,6 +170,21 @@ impl<'a> Node<'a> {
                 Component::Label(label) => {
                     let component_height = label.get_height(ui);
                     label.position(cursor).build(ui);
+                    if let Some(collapse_callback) = self.collapse_callback.take() {
+                        build_collapse_toggle(
+                            ui,
+                            self.id,
+                            [position[0] + width - COLLAPSE_TOGGLE_WIDTH, cursor[1]],
+                            component_height,
+                            self.collapsed,
+                            collapse_callback,
+                        );
+                    }
+                    cursor[1] += component_height;
+                }
+                Component::LabelInput(label_input) => {
+                    let component_height = label_input.get_height(ui);
+                    label_input.position(cursor).build(ui, width);
                     cursor[1] += component_height;
                 }
                 Component::PinGroup(pin_group) => {
@@ -120,11 +210,51 @@ impl<'a> Node<'a> {
                     slider.position(cursor).build(ui, width);
                     cursor[1] += component_height;
                 }
+                Component::RangeSlider(range_slider) => {
+                    let component_height = range_slider.get_height();
+                    range_slider.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::XYPad(xy_pad) => {
+                    let component_height = xy_pad.get_height();
+                    xy_pad.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::Envelope(envelope) => {
+                    let component_height = envelope.get_height();
+                    envelope.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::KnobRow(knob_row) => {
+                    let component_height = knob_row.get_height(ui);
+                    knob_row.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::NumberInputF32(number_input) => {
+                    let component_height = number_input.get_height();
+                    number_input.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::NumberInputI32(number_input) => {
+                    let component_height = number_input.get_height();
+                    number_input.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
                 Component::DropDown(dropdown) => {
                     let component_height = dropdown.get_height();
                     dropdown.position(cursor).build(ui, width);
                     cursor[1] += component_height;
                 }
+                Component::Plot(plot) => {
+                    let component_height = plot.get_height();
+                    plot.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
+                Component::Image(image) => {
+                    let component_height = image.get_height();
+                    image.position(cursor).build(ui, width);
+                    cursor[1] += component_height;
+                }
                 Component::Canvas(canvas) => {
                     let component_height = canvas.get_height();
                     canvas.position(cursor).build(ui, width);
@@ -133,21 +263,94 @@ impl<'a> Node<'a> {
             };
         }
 
+        if self.bypassed {
+            let mut color = ui.style_color(imgui::StyleColor::WindowBg);
+            color[3] = BYPASSED_OVERLAY_ALPHA;
+            ui.get_window_draw_list()
+                .add_rect(position, vec2::sum(&[position, [width, height]]), color)
+                .filled(true)
+                .build();
+        }
+
+        if let Some(resize_callback) = self.resize_callback {
+            let bottom_right = vec2::sum(&[position, [width, height]]);
+            let handle_position =
+                vec2::sum(&[bottom_right, [-RESIZE_HANDLE_SIZE, -RESIZE_HANDLE_SIZE]]);
+            ui.get_window_draw_list()
+                .add_triangle(
+                    [bottom_right[0], handle_position[1]],
+                    bottom_right,
+                    [handle_position[0], bottom_right[1]],
+                    ui.style_color(imgui::StyleColor::Border),
+                )
+                .filled(true)
+                .build();
+            ui.set_cursor_screen_pos(handle_position);
+            ui.invisible_button(
+                &imgui::ImString::from(format!("{}:resize", self.id)),
+                [RESIZE_HANDLE_SIZE, RESIZE_HANDLE_SIZE],
+            );
+            resize_callback(ui);
+        }
+
         ui.set_cursor_screen_pos(position);
         ui.invisible_button(self.id, [width, height]);
     }
 
+    fn stretch_to_min_height(&mut self, ui: &imgui::Ui) {
+        let extra_height = self.min_size[1] - self.get_height(ui);
+        let stretchable = self
+            .components
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    Component::TextBox(_) | Component::Plot(_) | Component::Canvas(_)
+                )
+            })
+            .count();
+        if extra_height <= 0.0 || stretchable == 0 {
+            return;
+        }
+
+        let extra_per_component = extra_height / stretchable as f32;
+        self.components = self
+            .components
+            .drain(..)
+            .map(|c| match c {
+                Component::TextBox(text_box) => {
+                    Component::TextBox(text_box.stretch(extra_per_component))
+                }
+                Component::Plot(plot) => Component::Plot(plot.stretch(extra_per_component)),
+                Component::Canvas(canvas) => Component::Canvas(canvas.stretch(extra_per_component)),
+                c => c,
+            })
+            .collect();
+    }
+
     fn get_width(&self, ui: &imgui::Ui) -> f32 {
         self.components
             .iter()
             .map(|c| match c {
+                Component::Label(label) if self.collapse_callback.is_some() => {
+                    label.get_width(ui) + COLLAPSE_TOGGLE_WIDTH
+                }
                 Component::Label(label) => label.get_width(ui),
+                Component::LabelInput(label_input) => label_input.get_min_width(ui),
                 Component::PinGroup(pin_group) => pin_group.get_min_width(ui),
                 Component::Space(_) => 0.0,
                 Component::TextBox(text_box) => text_box.get_min_width(),
                 Component::Button(button) => button.get_min_width(ui),
                 Component::Slider(slider) => slider.get_min_width(),
+                Component::RangeSlider(range_slider) => range_slider.get_min_width(),
+                Component::XYPad(xy_pad) => xy_pad.get_min_width(),
+                Component::Envelope(envelope) => envelope.get_min_width(),
+                Component::KnobRow(knob_row) => knob_row.get_min_width(ui),
+                Component::NumberInputF32(number_input) => number_input.get_min_width(),
+                Component::NumberInputI32(number_input) => number_input.get_min_width(),
                 Component::DropDown(dropdown) => dropdown.get_min_width(ui),
+                Component::Plot(plot) => plot.get_min_width(),
+                Component::Image(image) => image.get_min_width(),
                 Component::Canvas(canvas) => canvas.get_min_width(),
             })
             .fold(0.0, f32::max)
@@ -158,14 +361,92 @@ impl<'a> Node<'a> {
             .iter()
             .map(|c| match c {
                 Component::Label(label) => label.get_height(ui),
+                Component::LabelInput(label_input) => label_input.get_height(ui),
                 Component::PinGroup(pin_group) => pin_group.get_height(),
                 Component::Space(space) => *space,
                 Component::TextBox(text_box) => text_box.get_height(),
                 Component::Button(button) => button.get_height(ui),
                 Component::Slider(slider) => slider.get_height(),
+                Component::RangeSlider(range_slider) => range_slider.get_height(),
+                Component::XYPad(xy_pad) => xy_pad.get_height(),
+                Component::Envelope(envelope) => envelope.get_height(),
+                Component::KnobRow(knob_row) => knob_row.get_height(ui),
+                Component::NumberInputF32(number_input) => number_input.get_height(),
+                Component::NumberInputI32(number_input) => number_input.get_height(),
                 Component::DropDown(dropdown) => dropdown.get_height(),
+                Component::Plot(plot) => plot.get_height(),
+                Component::Image(image) => image.get_height(),
                 Component::Canvas(canvas) => canvas.get_height(),
             })
             .sum()
     }
 }
+
+/// Remove all widgets together with the space following each of them.
+fn hide_widgets(components: Vec<Component>) -> Vec<Component> {
+    let mut visible = Vec::new();
+    let mut skip_space = false;
+    for component in components.into_iter() {
+        match component {
+            Component::TextBox(_)
+            | Component::Button(_)
+            | Component::Slider(_)
+            | Component::RangeSlider(_)
+            | Component::XYPad(_)
+            | Component::Envelope(_)
+            | Component::KnobRow(_)
+            | Component::NumberInputF32(_)
+            | Component::NumberInputI32(_)
+            | Component::DropDown(_)
+            | Component::Plot(_)
+            | Component::Image(_)
+            | Component::Canvas(_) => skip_space = true,
+            Component::Space(_) if skip_space => skip_space = false,
+            component => {
+                skip_space = false;
+                visible.push(component);
+            }
+        }
+    }
+    visible
+}
+
+fn build_collapse_toggle(
+    ui: &imgui::Ui,
+    id: &imgui::ImStr,
+    position: [f32; 2],
+    height: f32,
+    collapsed: bool,
+    collapse_callback: Box<dyn FnOnce()>,
+) {
+    let center = vec2::sum(&[position, [COLLAPSE_TOGGLE_WIDTH / 2.0, height / 2.0]]);
+    let half = COLLAPSE_TOGGLE_SIZE / 2.0;
+    let (a, b, c) = if collapsed {
+        (
+            [center[0] - half, center[1] - half],
+            [center[0] + half, center[1]],
+            [center[0] - half, center[1] + half],
+        )
+    } else {
+        (
+            [center[0] - half, center[1] - half],
+            [center[0] + half, center[1] - half],
+            [center[0], center[1] + half],
+        )
+    };
+    ui.get_window_draw_list()
+        .add_triangle(a, b, c, ui.style_color(imgui::StyleColor::Text))
+        .filled(true)
+        .build();
+
+    ui.set_cursor_screen_pos(position);
+    if ui.invisible_button(
+        &imgui::ImString::from(format!("{}:collapse", id)),
+        [COLLAPSE_TOGGLE_WIDTH, height],
+    ) {
+        collapse_callback();
+    }
+    if ui.is_item_hovered() {
+        ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+    }
+}
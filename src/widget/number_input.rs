@@ -0,0 +1,92 @@
+extern crate imgui;
+
+use imgui::internal::DataTypeKind;
+
+use crate::vec2;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const HEIGHT: f32 = 20.0;
+const MIN_WIDTH: f32 = 100.0;
+
+/// Field changing its value while dragged over. Ctrl-click or double-click
+/// turns it into a text input for keyboard entry.
+pub struct NumberInput<T: DataTypeKind> {
+    id: imgui::ImString,
+    position: [f32; 2],
+    value: T,
+    range: Option<(T, T)>,
+    step: f32,
+    min_width: f32,
+    display_format: imgui::ImString,
+    value_callback: Option<Box<dyn FnOnce(T)>>,
+}
+
+impl<T: DataTypeKind> NumberInput<T> {
+    pub fn new(id: imgui::ImString, value: T) -> Self {
+        let id = imgui::ImString::from(format!("##{}", id));
+        Self {
+            id,
+            position: [0.0, 0.0],
+            value,
+            range: None,
+            step: 1.0,
+            min_width: MIN_WIDTH,
+            display_format: imgui::ImString::new("%.3f"),
+            value_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn range(mut self, min: T, max: T) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn display_format(mut self, display_format: imgui::ImString) -> Self {
+        self.display_format = display_format;
+        self
+    }
+
+    pub fn value_callback(mut self, value_callback: Box<dyn FnOnce(T)>) -> Self {
+        self.value_callback = Some(value_callback);
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.min_width + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        HEIGHT
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
+        ui.set_cursor_screen_pos(vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]));
+        ui.push_item_width(width - 2.0 * HORIZONTAL_MARGIN);
+        let mut drag = imgui::Drag::new(&self.id)
+            .speed(self.step)
+            .display_format(&self.display_format);
+        if let Some((min, max)) = self.range {
+            drag = drag.range(min..=max);
+        }
+        drag.build(ui, &mut self.value);
+
+        if let Some(value_callback) = self.value_callback {
+            value_callback(self.value);
+        }
+    }
+}
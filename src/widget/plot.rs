@@ -0,0 +1,182 @@
+extern crate imgui;
+
+use crate::vec2;
+use crate::widget::slider::format_value;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const LABEL_PADDING: f32 = 3.0;
+const LINE_THICKNESS: f32 = 1.5;
+
+struct Series<'a> {
+    samples: &'a [f32],
+    label: &'a str,
+    color: [f32; 4],
+}
+
+/// Area drawing series of samples as connected lines. Samples are borrowed,
+/// so the plot can be rebuilt each frame without copying them.
+pub struct Plot<'a> {
+    position: [f32; 2],
+    y_range: (f32, f32),
+    series: Vec<Series<'a>>,
+    axes: bool,
+    grid: Option<[u32; 2]>,
+    min_width: f32,
+    height: f32,
+}
+
+impl<'a> Plot<'a> {
+    pub fn new(y_range: (f32, f32), min_width: f32, height: f32) -> Self {
+        Self {
+            position: [0.0, 0.0],
+            y_range,
+            series: Vec::new(),
+            axes: false,
+            grid: None,
+            min_width,
+            height,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn add_series(mut self, samples: &'a [f32], label: &'a str, color: [f32; 4]) -> Self {
+        self.series.push(Series {
+            samples,
+            label,
+            color,
+        });
+        self
+    }
+
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    pub fn grid(mut self, grid: Option<[u32; 2]>) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    pub fn stretch(mut self, extra_height: f32) -> Self {
+        self.height += extra_height;
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.min_width + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn build(self, ui: &imgui::Ui, width: f32) {
+        let area_a = vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]);
+        let area_b = vec2::sum(&[self.position, [width - HORIZONTAL_MARGIN, self.height]]);
+        let area_size = [area_b[0] - area_a[0], area_b[1] - area_a[1]];
+        let y_range = self.y_range;
+        let to_y =
+            |value: f32| area_b[1] - (value - y_range.0) / (y_range.1 - y_range.0) * area_size[1];
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(area_a, area_b, ui.style_color(imgui::StyleColor::FrameBg))
+            .filled(true)
+            .build();
+
+        if let Some([columns, rows]) = self.grid {
+            for column in 1..columns {
+                let x = area_a[0] + area_size[0] * column as f32 / columns as f32;
+                draw_list
+                    .add_line(
+                        [x, area_a[1]],
+                        [x, area_b[1]],
+                        ui.style_color(imgui::StyleColor::Separator),
+                    )
+                    .build();
+            }
+            for row in 1..rows {
+                let y = area_a[1] + area_size[1] * row as f32 / rows as f32;
+                draw_list
+                    .add_line(
+                        [area_a[0], y],
+                        [area_b[0], y],
+                        ui.style_color(imgui::StyleColor::Separator),
+                    )
+                    .build();
+            }
+        }
+
+        if self.axes && y_range.0 <= 0.0 && y_range.1 >= 0.0 {
+            draw_list
+                .add_line(
+                    [area_a[0], to_y(0.0)],
+                    [area_b[0], to_y(0.0)],
+                    ui.style_color(imgui::StyleColor::Border),
+                )
+                .build();
+        }
+
+        // Samples out of the range are cut off by the border of the plot.
+        draw_list.with_clip_rect_intersect(area_a, area_b, || {
+            for series in self.series.iter() {
+                let steps = series.samples.len().saturating_sub(1).max(1) as f32;
+                let points: Vec<_> = series
+                    .samples
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sample)| [area_a[0] + area_size[0] * i as f32 / steps, to_y(*sample)])
+                    .collect();
+                for segment in points.windows(2) {
+                    draw_list
+                        .add_line(segment[0], segment[1], series.color)
+                        .thickness(LINE_THICKNESS)
+                        .build();
+                }
+            }
+        });
+
+        draw_list
+            .add_rect(area_a, area_b, ui.style_color(imgui::StyleColor::Border))
+            .build();
+
+        if self.axes {
+            let format = imgui::ImString::new("%g");
+            let max_label = format_value(&format, y_range.1);
+            let min_label = format_value(&format, y_range.0);
+            let min_label_height =
+                ui.calc_text_size(&imgui::ImString::new(&min_label), false, 0.0)[1];
+            draw_list.add_text(
+                vec2::sum(&[area_a, [LABEL_PADDING, LABEL_PADDING]]),
+                ui.style_color(imgui::StyleColor::TextDisabled),
+                max_label,
+            );
+            draw_list.add_text(
+                [
+                    area_a[0] + LABEL_PADDING,
+                    area_b[1] - min_label_height - LABEL_PADDING,
+                ],
+                ui.style_color(imgui::StyleColor::TextDisabled),
+                min_label,
+            );
+        }
+
+        // Legend is listed in the top right corner, each label in the color of
+        // its series.
+        let mut legend_y = area_a[1] + LABEL_PADDING;
+        for series in self.series.iter().filter(|s| !s.label.is_empty()) {
+            let label_size = ui.calc_text_size(&imgui::ImString::new(series.label), false, 0.0);
+            draw_list.add_text(
+                [area_b[0] - label_size[0] - LABEL_PADDING, legend_y],
+                series.color,
+                series.label,
+            );
+            legend_y += label_size[1];
+        }
+    }
+}
@@ -0,0 +1,166 @@
+extern crate imgui;
+
+use crate::vec2;
+use crate::widget::slider::format_value;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const HEIGHT: f32 = 20.0;
+const MIN_WIDTH: f32 = 100.0;
+const GRAB_WIDTH: f32 = 10.0;
+const GRAB_PADDING: f32 = 2.0;
+
+type ValueCallback = Box<dyn FnOnce((f32, f32))>;
+
+#[derive(Clone, Copy)]
+enum Handle {
+    Low,
+    High,
+}
+
+/// Slider with two handles, selecting the lower and the upper end of a range.
+pub struct RangeSlider {
+    id: imgui::ImString,
+    position: [f32; 2],
+    min: f32,
+    max: f32,
+    value: (f32, f32),
+    min_width: f32,
+    display_format: imgui::ImString,
+    value_callback: Option<ValueCallback>,
+}
+
+impl RangeSlider {
+    pub fn new(id: imgui::ImString, min: f32, max: f32, value: (f32, f32)) -> Self {
+        let id = imgui::ImString::from(format!("##{}", id));
+        Self {
+            id,
+            position: [0.0, 0.0],
+            min,
+            max,
+            value,
+            min_width: MIN_WIDTH,
+            display_format: imgui::ImString::new("%.3f"),
+            value_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn display_format(mut self, display_format: imgui::ImString) -> Self {
+        self.display_format = display_format;
+        self
+    }
+
+    pub fn value_callback(mut self, value_callback: ValueCallback) -> Self {
+        self.value_callback = Some(value_callback);
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.min_width + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        HEIGHT
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
+        let track_a = vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]);
+        let track_b = vec2::sum(&[
+            self.position,
+            [width - HORIZONTAL_MARGIN, self.get_height()],
+        ]);
+        let travel = track_b[0] - track_a[0] - GRAB_WIDTH;
+        let (min, max) = (self.min, self.max);
+        let to_x =
+            |value: f32| track_a[0] + GRAB_WIDTH / 2.0 + (value - min) / (max - min) * travel;
+
+        // When both handles overlap, the first submitted one takes the mouse.
+        // Give priority to the one which is free to move away.
+        let handles = if self.value.0 <= min {
+            [Handle::High, Handle::Low]
+        } else {
+            [Handle::Low, Handle::High]
+        };
+        let mut hovered = false;
+        for handle in handles.iter() {
+            let value = match handle {
+                Handle::Low => self.value.0,
+                Handle::High => self.value.1,
+            };
+            ui.set_cursor_screen_pos([to_x(value) - GRAB_WIDTH / 2.0, track_a[1]]);
+            let id = imgui::ImString::from(format!(
+                "{}:{}",
+                self.id,
+                match handle {
+                    Handle::Low => "low",
+                    Handle::High => "high",
+                }
+            ));
+            ui.invisible_button(&id, [GRAB_WIDTH, self.get_height()]);
+            hovered |= ui.is_item_hovered();
+            if ui.is_item_active() {
+                let position = (ui.io().mouse_pos[0] - track_a[0] - GRAB_WIDTH / 2.0) / travel;
+                let new_value = min + position.clamp(0.0, 1.0) * (max - min);
+                self.value = match handle {
+                    Handle::Low => (f32::min(new_value, self.value.1), self.value.1),
+                    Handle::High => (self.value.0, f32::max(new_value, self.value.0)),
+                };
+            }
+        }
+        if hovered {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::ResizeEW));
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(track_a, track_b, ui.style_color(imgui::StyleColor::FrameBg))
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(
+                [to_x(self.value.0), track_a[1] + GRAB_PADDING],
+                [to_x(self.value.1), track_b[1] - GRAB_PADDING],
+                ui.style_color(imgui::StyleColor::FrameBgActive),
+            )
+            .filled(true)
+            .build();
+        for value in [self.value.0, self.value.1].iter() {
+            draw_list
+                .add_rect(
+                    [to_x(*value) - GRAB_WIDTH / 2.0, track_a[1] + GRAB_PADDING],
+                    [to_x(*value) + GRAB_WIDTH / 2.0, track_b[1] - GRAB_PADDING],
+                    ui.style_color(imgui::StyleColor::SliderGrab),
+                )
+                .filled(true)
+                .build();
+        }
+
+        let label = imgui::ImString::from(format!(
+            "{} - {}",
+            format_value(&self.display_format, self.value.0),
+            format_value(&self.display_format, self.value.1)
+        ));
+        let label_size = ui.calc_text_size(&label, false, 0.0);
+        draw_list.add_text(
+            [
+                (track_a[0] + track_b[0] - label_size[0]) / 2.0,
+                (track_a[1] + track_b[1] - label_size[1]) / 2.0,
+            ],
+            ui.style_color(imgui::StyleColor::Text),
+            label.to_str(),
+        );
+
+        if let Some(value_callback) = self.value_callback {
+            value_callback(self.value);
+        }
+    }
+}
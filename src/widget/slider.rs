@@ -1,20 +1,61 @@
 extern crate imgui;
 
+use std::ffi::CStr;
+
 use crate::vec2;
 
 const HORIZONTAL_MARGIN: f32 = 10.0;
 const HEIGHT: f32 = 20.0;
 const MIN_WIDTH: f32 = 100.0;
 
+type TextInputCallback = Box<dyn FnOnce(bool)>;
+
+/// Mapping of the position on the slider, between 0.0 and 1.0, to the value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scale {
+    Linear,
+    Logarithmic,
+    Exponential { curve: f32 },
+    Steps { step: f32 },
+}
+
+impl Scale {
+    fn to_position(self, value: f32, min: f32, max: f32) -> f32 {
+        match self {
+            Scale::Linear | Scale::Steps { .. } => (value - min) / (max - min),
+            Scale::Logarithmic => (value / min).ln() / (max / min).ln(),
+            Scale::Exponential { curve } => ((value - min) / (max - min)).powf(1.0 / curve),
+        }
+    }
+
+    fn to_value(self, position: f32, min: f32, max: f32) -> f32 {
+        let value = match self {
+            Scale::Linear => min + (max - min) * position,
+            Scale::Logarithmic => min * (max / min).powf(position),
+            Scale::Exponential { curve } => min + (max - min) * position.powf(curve),
+            Scale::Steps { step } => {
+                let last_step = ((max - min) / step).floor();
+                min + f32::min(((max - min) * position / step).round(), last_step) * step
+            }
+        };
+        f32::min(f32::max(value, min), max)
+    }
+}
+
 pub struct Slider {
     id: imgui::ImString,
     position: [f32; 2],
     min: f32,
     max: f32,
     value: f32,
+    default: Option<f32>,
+    scale: Scale,
     min_width: f32,
     display_format: imgui::ImString,
+    unit: String,
+    text_input: bool,
     value_callback: Option<Box<dyn FnOnce(f32)>>,
+    text_input_callback: Option<TextInputCallback>,
 }
 
 impl Slider {
@@ -26,9 +67,14 @@ impl Slider {
             min,
             max,
             value,
+            default: None,
+            scale: Scale::Linear,
             min_width: MIN_WIDTH,
             display_format: imgui::ImString::new("%.3f"),
+            unit: String::new(),
+            text_input: false,
             value_callback: None,
+            text_input_callback: None,
         }
     }
 
@@ -37,6 +83,17 @@ impl Slider {
         self
     }
 
+    /// Value the slider is reset to on double-click.
+    pub fn default(mut self, default: f32) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
     pub fn min_width(mut self, min_width: f32) -> Self {
         self.min_width = min_width;
         self
@@ -47,11 +104,29 @@ impl Slider {
         self
     }
 
+    pub fn unit(mut self, unit: String) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Show a text input for typing in an exact value instead of the slider.
+    pub fn text_input(mut self, text_input: bool) -> Self {
+        self.text_input = text_input;
+        self
+    }
+
     pub fn value_callback(mut self, value_callback: Box<dyn FnOnce(f32)>) -> Self {
         self.value_callback = Some(value_callback);
         self
     }
 
+    /// The callback is called with `true` when the user Ctrl-clicks the slider
+    /// to type in a value and with `false` once the typing is finished.
+    pub fn text_input_callback(mut self, text_input_callback: TextInputCallback) -> Self {
+        self.text_input_callback = Some(text_input_callback);
+        self
+    }
+
     pub fn get_min_width(&self) -> f32 {
         self.min_width + 2.0 * HORIZONTAL_MARGIN
     }
@@ -63,13 +138,108 @@ impl Slider {
     pub fn build(mut self, ui: &imgui::Ui, width: f32) {
         ui.set_cursor_screen_pos(vec2::sum(&[self.position, [HORIZONTAL_MARGIN, 0.0]]));
         ui.push_item_width(width - 2.0 * HORIZONTAL_MARGIN);
-        imgui::Slider::new(&self.id)
-            .range(self.min..=self.max)
-            .display_format(&self.display_format)
-            .build(ui, &mut self.value);
+
+        if self.text_input {
+            if !ui.is_any_item_active() {
+                ui.set_keyboard_focus_here(imgui::FocusedWidget::Next);
+            }
+            let confirmed = ui
+                .input_float(&self.id, &mut self.value)
+                .enter_returns_true(true)
+                .auto_select_all(true)
+                .build();
+            if confirmed || ui.is_item_deactivated() {
+                if let Some(text_input_callback) = self.text_input_callback {
+                    text_input_callback(false);
+                }
+            }
+        } else {
+            // The slider itself moves between 0.0 and 1.0. The value is mapped
+            // through the scale and shown as a pre-formatted label.
+            let mut position = self.scale.to_position(self.value, self.min, self.max);
+            let label = imgui::ImString::from(self.get_label().replace('%', "%%"));
+            let changed = imgui::Slider::new(&self.id)
+                .range(0.0..=1.0)
+                .display_format(&label)
+                .flags(imgui::SliderFlags::NO_INPUT | imgui::SliderFlags::NO_ROUND_TO_FORMAT)
+                .build(ui, &mut position);
+
+            if ui.is_item_hovered() && ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
+                if let Some(default) = self.default {
+                    self.value = default;
+                }
+            } else if ui.is_item_clicked(imgui::MouseButton::Left) && ui.io().key_ctrl {
+                if let Some(text_input_callback) = self.text_input_callback {
+                    text_input_callback(true);
+                }
+            } else if changed && !ui.io().key_ctrl {
+                self.value = self.scale.to_value(position, self.min, self.max);
+            }
+        }
 
         if let Some(value_callback) = self.value_callback {
             value_callback(self.value);
         }
     }
+
+    fn get_label(&self) -> String {
+        let value = format_value(&self.display_format, self.value);
+        if self.unit.is_empty() {
+            value
+        } else {
+            format!("{} {}", value, self.unit)
+        }
+    }
+}
+
+/// Format the value using the C-format string, the same way imgui formats
+/// values of its own sliders.
+pub fn format_value(display_format: &imgui::ImStr, value: f32) -> String {
+    unsafe {
+        let buffer = imgui_sys::ImGuiTextBuffer_ImGuiTextBuffer();
+        imgui_sys::ImGuiTextBuffer_appendf(buffer, display_format.as_ptr(), value as f64);
+        let formatted = CStr::from_ptr(imgui_sys::ImGuiTextBuffer_c_str(buffer))
+            .to_string_lossy()
+            .into_owned();
+        imgui_sys::ImGuiTextBuffer_destroy(buffer);
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roundtrip(scale: Scale, value: f32, min: f32, max: f32) {
+        let position = scale.to_position(value, min, max);
+        assert!((0.0..=1.0).contains(&position));
+        assert!((scale.to_value(position, min, max) - value).abs() < 0.01);
+    }
+
+    #[test]
+    fn linear_scale() {
+        assert_eq!(Scale::Linear.to_position(5.0, 0.0, 10.0), 0.5);
+        assert_eq!(Scale::Linear.to_value(0.5, 0.0, 10.0), 5.0);
+        assert_roundtrip(Scale::Linear, 3.0, 0.0, 10.0);
+    }
+
+    #[test]
+    fn logarithmic_scale() {
+        assert!((Scale::Logarithmic.to_value(0.5, 10.0, 1000.0) - 100.0).abs() < 0.01);
+        assert_roundtrip(Scale::Logarithmic, 440.0, 20.0, 20000.0);
+    }
+
+    #[test]
+    fn exponential_scale() {
+        let scale = Scale::Exponential { curve: 2.0 };
+        assert_eq!(scale.to_value(0.5, 0.0, 100.0), 25.0);
+        assert_roundtrip(scale, 42.0, 0.0, 100.0);
+    }
+
+    #[test]
+    fn stepped_scale() {
+        let scale = Scale::Steps { step: 2.0 };
+        assert_eq!(scale.to_value(0.32, 1.0, 11.0), 5.0);
+        assert_eq!(scale.to_value(1.0, 1.0, 10.0), 9.0);
+    }
 }
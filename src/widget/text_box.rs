@@ -38,6 +38,11 @@ impl TextBox {
         self
     }
 
+    pub fn stretch(mut self, extra_height: f32) -> Self {
+        self.height += extra_height;
+        self
+    }
+
     pub fn content_callback(mut self, content_callback: Box<dyn FnOnce(&imgui::ImString)>) -> Self {
         self.content_callback = Some(content_callback);
         self
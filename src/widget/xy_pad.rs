@@ -0,0 +1,130 @@
+extern crate imgui;
+
+use crate::vec2;
+
+const HORIZONTAL_MARGIN: f32 = 10.0;
+const POINT_RADIUS: f32 = 5.0;
+
+type ValueCallback = Box<dyn FnOnce((f32, f32))>;
+
+/// Square area where dragging a point sets a pair of values. The `x` value
+/// grows from left to right, the `y` value from bottom to top.
+pub struct XYPad {
+    id: imgui::ImString,
+    position: [f32; 2],
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    value: (f32, f32),
+    size: f32,
+    value_callback: Option<ValueCallback>,
+}
+
+impl XYPad {
+    pub fn new(
+        id: imgui::ImString,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+        value: (f32, f32),
+    ) -> Self {
+        let id = imgui::ImString::from(format!("##{}", id));
+        Self {
+            id,
+            position: [0.0, 0.0],
+            x_range,
+            y_range,
+            value,
+            size: 100.0,
+            value_callback: None,
+        }
+    }
+
+    pub fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn value_callback(mut self, value_callback: ValueCallback) -> Self {
+        self.value_callback = Some(value_callback);
+        self
+    }
+
+    pub fn get_min_width(&self) -> f32 {
+        self.size + 2.0 * HORIZONTAL_MARGIN
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.size
+    }
+
+    pub fn build(mut self, ui: &imgui::Ui, width: f32) {
+        let margin_left = (width - self.get_min_width()) / 2.0 + HORIZONTAL_MARGIN;
+        let pad_a = vec2::sum(&[self.position, [margin_left, 0.0]]);
+        let pad_b = vec2::sum(&[pad_a, [self.size, self.size]]);
+
+        ui.set_cursor_screen_pos(pad_a);
+        ui.invisible_button(&self.id, [self.size, self.size]);
+        if ui.is_item_active() {
+            let mouse_pos = ui.io().mouse_pos;
+            let relative_x = ((mouse_pos[0] - pad_a[0]) / self.size).clamp(0.0, 1.0);
+            let relative_y = ((pad_b[1] - mouse_pos[1]) / self.size).clamp(0.0, 1.0);
+            self.value = (
+                self.x_range.0 + relative_x * (self.x_range.1 - self.x_range.0),
+                self.y_range.0 + relative_y * (self.y_range.1 - self.y_range.0),
+            );
+        }
+        if ui.is_item_hovered() || ui.is_item_active() {
+            ui.set_mouse_cursor(Some(imgui::MouseCursor::Hand));
+        }
+
+        let point = [
+            pad_a[0]
+                + (self.value.0 - self.x_range.0) / (self.x_range.1 - self.x_range.0) * self.size,
+            pad_b[1]
+                - (self.value.1 - self.y_range.0) / (self.y_range.1 - self.y_range.0) * self.size,
+        ];
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(pad_a, pad_b, ui.style_color(imgui::StyleColor::FrameBg))
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(pad_a, pad_b, ui.style_color(imgui::StyleColor::Border))
+            .build();
+        draw_list
+            .add_line(
+                [point[0], pad_a[1]],
+                [point[0], pad_b[1]],
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .build();
+        draw_list
+            .add_line(
+                [pad_a[0], point[1]],
+                [pad_b[0], point[1]],
+                ui.style_color(imgui::StyleColor::Border),
+            )
+            .build();
+        draw_list
+            .add_circle(
+                point,
+                POINT_RADIUS,
+                ui.style_color(if ui.is_item_active() {
+                    imgui::StyleColor::SliderGrabActive
+                } else {
+                    imgui::StyleColor::SliderGrab
+                }),
+            )
+            .filled(true)
+            .build();
+
+        if let Some(value_callback) = self.value_callback {
+            value_callback(self.value);
+        }
+    }
+}